@@ -0,0 +1,340 @@
+-- Harmony client for CC:Tweaked
+-- Install with: wget {{base_url}}/client/harmony.lua harmony
+
+local BASE_URL = "{{base_url}}"
+local VERSION = "{{version}}"
+
+local dfpwm = require("cc.audio.dfpwm")
+
+-- The most audio a speaker accepts at once, 128 * 1024 samples
+local CHUNK_SIZE = 16 * 1024
+
+settings.define("harmony.token", {
+    description = "The login token of the Harmony client",
+    type = "string",
+})
+settings.define("harmony.refresh_token", {
+    description = "Renews the login token of the Harmony client when it expires",
+    type = "string",
+})
+settings.define("harmony.crossfade_ms", {
+    description = "Crossfade between tracks in milliseconds, unset to play tracks as they are",
+    type = "number",
+})
+
+local function header(response, name)
+    for key, value in pairs(response.getResponseHeaders()) do
+        if key:lower() == name:lower() then
+            return value
+        end
+    end
+end
+
+local function auth_headers(extra)
+    local headers = extra or {}
+    local token = settings.get("harmony.token")
+    if token then
+        headers["Authorization"] = "Bearer " .. token
+    end
+    return headers
+end
+
+local function save_login(response)
+    settings.set("harmony.token", response.token)
+    settings.set("harmony.refresh_token", response.refresh_token)
+    settings.save()
+end
+
+-- Trades the refresh token for a new login token, returns whether that worked
+local function refresh()
+    local refresh_token = settings.get("harmony.refresh_token")
+    if not refresh_token then
+        return false
+    end
+
+    local response = http.post(
+        BASE_URL .. "/user/refresh",
+        textutils.serializeJSON({ refresh_token = refresh_token }),
+        { ["Content-Type"] = "application/json" }
+    )
+    if not response then
+        return false
+    end
+
+    local login = textutils.unserializeJSON(response.readAll())
+    response.close()
+    save_login(login)
+    return true
+end
+
+-- Sends a request and returns the response, errors with the message of the server.
+-- An expired login token is refreshed once and the request sent again
+local function send(method, path, body, binary, retried)
+    local headers = auth_headers()
+    if body then
+        headers["Content-Type"] = "application/json"
+    end
+
+    local response, err, error_response = http.get({
+        url = BASE_URL .. path,
+        method = method,
+        headers = headers,
+        body = body and textutils.serializeJSON(body) or nil,
+        binary = binary,
+    })
+
+    if response then
+        return response
+    end
+
+    if error_response then
+        -- A failed login is not retried, it would only count as another failure
+        local expired = error_response.getResponseCode() == 401 and path ~= "/user/login"
+        if expired and not retried and refresh() then
+            error_response.close()
+            return send(method, path, body, binary, true)
+        end
+
+        local text = error_response.readAll()
+        error_response.close()
+        local decoded = textutils.unserializeJSON(text)
+        if type(decoded) == "table" and decoded.message then
+            text = decoded.message
+        end
+        error(text ~= "" and text or err, 0)
+    end
+
+    error(err, 0)
+end
+
+local function request(method, path, body)
+    local response = send(method, path, body)
+    local text = response.readAll()
+    response.close()
+    return textutils.unserializeJSON(text) or text
+end
+
+local function require_login()
+    if not settings.get("harmony.token") then
+        error("Not logged in, run 'harmony login' first", 0)
+    end
+end
+
+local function login()
+    write("Username: ")
+    local name = read()
+    write("Password: ")
+    local password = read("*")
+
+    local response = request("POST", "/user/login", { name = name, password = password })
+    save_login(response)
+    print("Logged in as " .. response.user.name)
+end
+
+-- Uses a device token created from the account, so no password has to be typed in game
+local function use_token(token)
+    if not token then
+        write("Device token: ")
+        token = read("*")
+    end
+
+    settings.set("harmony.token", token)
+    settings.unset("harmony.refresh_token")
+    settings.save()
+    print("Device token saved")
+end
+
+local function logout()
+    -- Device tokens have no session to end, they are revoked from the account instead
+    if settings.get("harmony.refresh_token") then
+        pcall(send, "POST", "/user/logout")
+    end
+    settings.unset("harmony.token")
+    settings.unset("harmony.refresh_token")
+    settings.save()
+    print("Logged out")
+end
+
+local function print_songs(songs)
+    if #songs == 0 then
+        print("No songs found")
+        return
+    end
+
+    local rows = {}
+    for _, song in ipairs(songs) do
+        table.insert(rows, { tostring(song.id), song.name, song.author or "" })
+    end
+    textutils.pagedTabulate(colors.yellow, { "Id", "Name", "Author" }, colors.white, table.unpack(rows))
+end
+
+local function songs(query)
+    require_login()
+    if query then
+        print_songs(request("GET", "/song/find/" .. textutils.urlEncode(query)))
+    else
+        print_songs(request("GET", "/song/"))
+    end
+end
+
+-- Plays a session until it finishes or the user quits.
+-- Space pauses and resumes, right arrow skips, left arrow goes back and q quits
+local function play(source)
+    require_login()
+    local speaker = peripheral.find("speaker")
+    if not speaker then
+        error("No speaker attached", 0)
+    end
+
+    local session = request("POST", "/session", {
+        source = source,
+        crossfade_ms = settings.get("harmony.crossfade_ms"),
+    })
+    local path = "/session/" .. session.id
+
+    local function stream()
+        local decoder = dfpwm.make_decoder()
+        local track
+
+        while true do
+            local response = send("GET", path .. "/read?length=" .. CHUNK_SIZE, nil, true)
+            local state = header(response, "X-Session-State")
+            local song = header(response, "X-Session-Track")
+            local data = response.readAll() or ""
+            response.close()
+
+            if song and song ~= track then
+                track = song
+                local info = request("GET", path)
+                if info.track then
+                    print(("Now playing %s (%d/%d)"):format(info.track.name, info.index + 1, info.tracks))
+                end
+            end
+
+            if state == "finished" then
+                return
+            end
+
+            if #data == 0 then
+                sleep(0.5)
+            else
+                local buffer = decoder(data)
+                while not speaker.playAudio(buffer) do
+                    os.pullEvent("speaker_audio_empty")
+                end
+            end
+        end
+    end
+
+    local function controls()
+        local paused = false
+        while true do
+            local _, key = os.pullEvent("key")
+            if key == keys.space then
+                paused = not paused
+                request("POST", path .. (paused and "/pause" or "/resume"))
+                print(paused and "Paused" or "Resumed")
+            elseif key == keys.right then
+                request("POST", path .. "/skip")
+                speaker.stop()
+            elseif key == keys.left then
+                request("POST", path .. "/previous")
+                speaker.stop()
+            elseif key == keys.q then
+                return
+            end
+        end
+    end
+
+    print("Space to pause, arrows to skip, q to quit")
+    parallel.waitForAny(stream, controls)
+    speaker.stop()
+    pcall(request, "DELETE", path)
+end
+
+local function playlists()
+    require_login()
+    local list = request("GET", "/playlist/")
+    if #list == 0 then
+        print("No playlists found")
+        return
+    end
+
+    for i, playlist in ipairs(list) do
+        print(("%d. %s%s"):format(i, playlist.name, playlist.public and "" or " (private)"))
+    end
+
+    write("Play which playlist? ")
+    local choice = list[tonumber(read())]
+    if choice then
+        play({ playlist = choice.id })
+    end
+end
+
+local function latest_version()
+    return request("GET", "/client/version")
+end
+
+local function version()
+    print("Harmony client " .. VERSION)
+    local latest = latest_version()
+    if latest.version ~= VERSION then
+        print("Version " .. latest.version .. " is available, run 'harmony update'")
+    end
+end
+
+local function update()
+    local latest = latest_version()
+    if latest.version == VERSION then
+        print("Already up to date")
+        return
+    end
+
+    local response = send("GET", "/client/harmony.lua")
+    local script = response.readAll()
+    response.close()
+
+    local file = fs.open(shell.getRunningProgram(), "w")
+    file.write(script)
+    file.close()
+    print("Updated to " .. latest.version)
+end
+
+local function usage()
+    print("Usage:")
+    print("  harmony login")
+    print("  harmony token [device token]")
+    print("  harmony logout")
+    print("  harmony songs [query]")
+    print("  harmony playlists")
+    print("  harmony play song <id>")
+    print("  harmony play playlist <id>")
+    print("  harmony version")
+    print("  harmony update")
+end
+
+local args = { ... }
+local commands = {
+    login = login,
+    token = function() use_token(args[2]) end,
+    logout = logout,
+    songs = function() songs(args[2]) end,
+    playlists = playlists,
+    play = function()
+        local kind, id = args[2], tonumber(args[3])
+        if (kind ~= "song" and kind ~= "playlist") or not id then
+            usage()
+            return
+        end
+        play({ [kind] = id })
+    end,
+    version = version,
+    update = update,
+}
+
+local command = commands[args[1]]
+if command then
+    command()
+else
+    usage()
+end
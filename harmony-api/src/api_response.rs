@@ -1,6 +1,6 @@
 use harmony_core::core::validation::result::ValidationError;
 use harmony_core::result::{AuthError, ServerError};
-use rocket::http::Status;
+use rocket::http::{Header, Status};
 use rocket::request::Request;
 use rocket::response::{Responder, Response};
 use std::io::Cursor;
@@ -12,6 +12,22 @@ pub enum ApiResponse {
     InternalServerError(String),
     Conflict(String),
     Unauthorized(String),
+    /// Asks to come back after the given number of seconds
+    TooManyRequests(String, u64),
+}
+
+impl ApiResponse {
+    #[must_use]
+    pub fn message(&self) -> &str {
+        match self {
+            ApiResponse::BadRequest(message)
+            | ApiResponse::NotFound(message)
+            | ApiResponse::InternalServerError(message)
+            | ApiResponse::Conflict(message)
+            | ApiResponse::Unauthorized(message)
+            | ApiResponse::TooManyRequests(message, _) => message,
+        }
+    }
 }
 
 impl<'r> Responder<'r, 'static> for ApiResponse {
@@ -37,6 +53,11 @@ impl<'r> Responder<'r, 'static> for ApiResponse {
                 .status(Status::Unauthorized)
                 .sized_body(message.len(), Cursor::new(message))
                 .ok(),
+            ApiResponse::TooManyRequests(message, retry_after) => Response::build()
+                .status(Status::TooManyRequests)
+                .header(Header::new("Retry-After", retry_after.to_string()))
+                .sized_body(message.len(), Cursor::new(message))
+                .ok(),
         }
     }
 }
@@ -55,6 +76,12 @@ impl From<ServerError> for ApiResponse {
                 ApiResponse::InternalServerError("Execution failed".into())
             }
             ServerError::FileNotFound => ApiResponse::NotFound("File not found".into()),
+            ServerError::MailFailed(_) => {
+                ApiResponse::InternalServerError("Failed to send email".into())
+            }
+            ServerError::LimitReached => {
+                ApiResponse::TooManyRequests("Limit reached, try again later".into(), 0)
+            }
             _ => ApiResponse::InternalServerError("Failed".into()),
         }
     }
@@ -63,8 +90,14 @@ impl From<ServerError> for ApiResponse {
 impl From<AuthError> for ApiResponse {
     fn from(error: AuthError) -> Self {
         match error {
-            AuthError::InvalidPassword => ApiResponse::BadRequest("Invalid password".into()),
-            AuthError::UserNotFound => ApiResponse::NotFound("User not found".into()),
+            // The same answer for both, so it can't be used to find out which names exist
+            AuthError::InvalidPassword | AuthError::UserNotFound => {
+                ApiResponse::Unauthorized("Invalid username or password".into())
+            }
+            AuthError::Suspended => ApiResponse::Unauthorized("Account is suspended".into()),
+            AuthError::Pending => {
+                ApiResponse::Unauthorized("Account is waiting for approval".into())
+            }
             AuthError::HashFailed => {
                 ApiResponse::InternalServerError("Hash operation failed".into())
             }
@@ -82,7 +115,10 @@ impl From<ValidationError> for ApiResponse {
             | ValidationError::InvalidEmail(msg)
             | ValidationError::InvalidFilePath(msg)
             | ValidationError::InvalidYouTubeUrl(msg)
-            | ValidationError::InvalidName(msg) => ApiResponse::BadRequest(msg),
+            | ValidationError::InvalidName(msg)
+            | ValidationError::InvalidSchedule(msg)
+            | ValidationError::InvalidScope(msg)
+            | ValidationError::InvalidRole(msg) => ApiResponse::BadRequest(msg),
             _ => ApiResponse::InternalServerError("Failed".into()),
         }
     }
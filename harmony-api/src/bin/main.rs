@@ -1,21 +1,64 @@
 #[macro_use]
 extern crate rocket;
 use harmony_api::error_response;
-use harmony_api::handler::stream_handler;
+use harmony_api::handler::{client_handler, device_handler, jukebox_handler, room_handler};
+use harmony_api::handler::{email_handler, session_handler, socket_handler, stream_handler};
+use harmony_api::handler::{invite_handler, schedule_handler, share_handler, station_handler};
 use harmony_api::handler::{playlist_handler, song_handler, user_handler, version_handler};
-use harmony_api::utils::states::{stream_cleanup_task, StreamState};
+use harmony_api::utils::environment;
+use harmony_api::utils::states::{
+    jukebox_cleanup_task, login_cleanup_task, rate_limit_cleanup_task, room_cleanup_task,
+    session_cleanup_task, stream_cleanup_task, JukeboxState, LoginState, RateLimitState, RoomState,
+    SessionState, StationState, StreamState,
+};
 
 #[rocket::main]
 async fn main() {
     let stream_state = StreamState::new();
+    let session_state = SessionState::new();
+    let room_state = RoomState::new();
+    let station_state = StationState::new();
+    let jukebox_state = JukeboxState::new();
+    let login_state = LoginState::new();
+    let rate_limit_state = RateLimitState::new();
 
-    // Spawn the cleanup task
+    // Spawn the cleanup tasks
     let cleanup_handle = tokio::spawn(stream_cleanup_task(stream_state.clone()));
+    let session_cleanup_handle = tokio::spawn(session_cleanup_task(session_state.clone()));
+    let room_cleanup_handle = tokio::spawn(room_cleanup_task(room_state.clone()));
+    let jukebox_cleanup_handle = tokio::spawn(jukebox_cleanup_task(jukebox_state.clone()));
+    let login_cleanup_handle = tokio::spawn(login_cleanup_task(login_state.clone()));
+    let rate_limit_cleanup_handle = tokio::spawn(rate_limit_cleanup_task(rate_limit_state.clone()));
 
-    let _rocket = rocket::build()
+    // Rocket trusts X-Real-IP by default, without a proxy in front anyone could send it
+    let figment = match environment::get_proxy_ip_header() {
+        Some(header) => rocket::Config::figment().merge(("ip_header", header)),
+        None => rocket::Config::figment().merge(("ip_header", false)),
+    };
+
+    let _rocket = rocket::custom(figment)
         .manage(stream_state)
-        .register("/", catchers![error_response::catch_all])
+        .manage(session_state)
+        .manage(room_state)
+        .manage(station_state)
+        .manage(jukebox_state)
+        .manage(login_state)
+        .manage(rate_limit_state)
+        .register(
+            "/",
+            catchers![
+                error_response::catch_all,
+                error_response::catch_too_many_requests
+            ],
+        )
         .mount("/", routes![version_handler::get_version_handler])
+        .mount(
+            "/client",
+            routes![
+                client_handler::get_client_handler,
+                client_handler::get_client_version_handler
+            ],
+        )
         .mount(
             "/song",
             routes![
@@ -23,6 +66,7 @@ async fn main() {
                 song_handler::get_song_handler,
                 song_handler::find_song_handler,
                 song_handler::create_song_handler,
+                song_handler::remove_song_handler,
             ],
         )
         .mount(
@@ -30,9 +74,55 @@ async fn main() {
             routes![
                 user_handler::list_users_handler,
                 user_handler::login_user_handler,
+                user_handler::refresh_user_handler,
+                user_handler::logout_user_handler,
+                user_handler::logout_all_user_handler,
                 user_handler::get_user_handler,
                 user_handler::create_user_handler,
-                user_handler::find_user_handler
+                user_handler::find_user_handler,
+                user_handler::change_password_handler,
+                user_handler::get_own_quota_handler,
+                user_handler::rename_user_handler,
+                user_handler::remove_own_user_handler,
+                user_handler::set_user_role_handler,
+                user_handler::list_user_details_handler,
+                user_handler::list_pending_users_handler,
+                user_handler::list_lockouts_handler,
+                user_handler::approve_user_handler,
+                user_handler::reject_user_handler,
+                user_handler::suspend_user_handler,
+                user_handler::unsuspend_user_handler,
+                user_handler::reset_user_password_handler,
+                user_handler::remove_user_handler
+            ],
+        )
+        .mount(
+            "/user",
+            routes![
+                email_handler::get_own_email_handler,
+                email_handler::set_own_email_handler,
+                email_handler::remove_own_email_handler,
+                email_handler::verify_email_link_handler,
+                email_handler::verify_email_form_handler,
+                email_handler::verify_email_handler,
+                email_handler::forgot_password_handler,
+                email_handler::reset_password_handler
+            ],
+        )
+        .mount(
+            "/device",
+            routes![
+                device_handler::list_device_handler,
+                device_handler::create_device_handler,
+                device_handler::remove_device_handler
+            ],
+        )
+        .mount(
+            "/invite",
+            routes![
+                invite_handler::list_invite_handler,
+                invite_handler::create_invite_handler,
+                invite_handler::remove_invite_handler
             ],
         )
         .mount(
@@ -48,10 +138,94 @@ async fn main() {
         )
         .mount(
             "/stream",
-            routes![stream_handler::open_stream, stream_handler::read_stream],
+            routes![
+                stream_handler::open_stream,
+                stream_handler::read_stream,
+                stream_handler::get_stream_file,
+                stream_handler::get_stream_song,
+                stream_handler::create_ticket_handler,
+                socket_handler::stream_socket
+            ],
+        )
+        .mount(
+            "/session",
+            routes![
+                session_handler::create_session_handler,
+                session_handler::get_session_handler,
+                session_handler::read_session_handler,
+                session_handler::pause_session_handler,
+                session_handler::resume_session_handler,
+                session_handler::skip_session_handler,
+                session_handler::previous_session_handler,
+                session_handler::seek_session_handler,
+                session_handler::remove_session_handler
+            ],
+        )
+        .mount(
+            "/room",
+            routes![
+                room_handler::create_room_handler,
+                room_handler::get_room_handler,
+                room_handler::join_room_handler,
+                room_handler::leave_room_handler,
+                room_handler::pause_room_handler,
+                room_handler::resume_room_handler,
+                room_handler::skip_room_handler,
+                room_handler::previous_room_handler,
+                room_handler::seek_room_handler,
+                room_handler::remove_room_handler
+            ],
+        )
+        .mount(
+            "/station",
+            routes![
+                station_handler::list_station_handler,
+                station_handler::get_station_handler,
+                station_handler::create_station_handler,
+                station_handler::remove_station_handler
+            ],
+        )
+        .mount(
+            "/jukebox",
+            routes![
+                jukebox_handler::create_jukebox_handler,
+                jukebox_handler::get_jukebox_handler,
+                jukebox_handler::enqueue_jukebox_handler,
+                jukebox_handler::vote_jukebox_handler,
+                jukebox_handler::remove_jukebox_entry_handler,
+                jukebox_handler::skip_jukebox_handler,
+                jukebox_handler::remove_jukebox_handler
+            ],
+        )
+        .mount(
+            "/schedule",
+            routes![
+                schedule_handler::list_schedule_handler,
+                schedule_handler::get_schedule_handler,
+                schedule_handler::scheduled_at_handler,
+                schedule_handler::create_schedule_handler,
+                schedule_handler::remove_schedule_handler
+            ],
+        )
+        .mount(
+            "/share",
+            routes![
+                share_handler::lookup_share_handler,
+                share_handler::get_song_share_handler,
+                share_handler::regenerate_song_share_handler,
+                share_handler::revoke_song_share_handler,
+                share_handler::get_playlist_share_handler,
+                share_handler::regenerate_playlist_share_handler,
+                share_handler::revoke_playlist_share_handler
+            ],
         )
         .launch()
         .await;
 
     cleanup_handle.abort(); // Quit the thread
+    session_cleanup_handle.abort();
+    room_cleanup_handle.abort();
+    jukebox_cleanup_handle.abort();
+    login_cleanup_handle.abort();
+    rate_limit_cleanup_handle.abort();
 }
@@ -1,6 +1,9 @@
+use rocket::http::Header;
 use rocket::serde::json::Json;
 use rocket::serde::Serialize;
-use rocket::{catch, Request};
+use rocket::{catch, Request, Responder};
+
+use crate::guards::rate_limit::RetryAfter;
 
 #[derive(Serialize)]
 pub struct ErrorResponse {
@@ -16,3 +19,25 @@ pub fn catch_all(status: rocket::http::Status, _: &Request) -> Json<ErrorRespons
         message: format!("Error {}: {}", status.code, status.reason_lossy()),
     })
 }
+
+/// A 429 with the `Retry-After` header, so clients know when to come back
+#[derive(Responder)]
+#[response(status = 429)]
+pub struct TooManyRequests {
+    body: Json<ErrorResponse>,
+    retry_after: Header<'static>,
+}
+
+#[catch(429)]
+#[must_use]
+pub fn catch_too_many_requests(request: &Request) -> TooManyRequests {
+    let RetryAfter(seconds) = *request.local_cache(|| RetryAfter(1));
+
+    TooManyRequests {
+        body: Json(ErrorResponse {
+            status: 429,
+            message: format!("Error 429: Too many requests, try again in {seconds} seconds"),
+        }),
+        retry_after: Header::new("Retry-After", seconds.to_string()),
+    }
+}
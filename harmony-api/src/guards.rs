@@ -1 +1,4 @@
+pub mod permission_auth;
+pub mod rate_limit;
+pub mod stream_auth;
 pub mod token_auth;
@@ -0,0 +1,88 @@
+use crate::api_response::ApiResponse;
+use crate::guards::token_auth::TokenAuth;
+use harmony_core::core::model::device_token::DeviceToken;
+use harmony_core::core::model::role::Permission;
+use harmony_core::core::model::user::User;
+use rocket::http::Status;
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest, Request};
+use std::marker::PhantomData;
+
+/// A permission a route requires, see `PermissionAuth`
+pub trait RequiredPermission {
+    const PERMISSION: Permission;
+}
+
+pub struct AddSongs;
+pub struct DeleteAnySong;
+pub struct ManageAnyPlaylist;
+pub struct ManageStations;
+pub struct ManageUsers;
+
+impl RequiredPermission for AddSongs {
+    const PERMISSION: Permission = Permission::AddSongs;
+}
+
+impl RequiredPermission for DeleteAnySong {
+    const PERMISSION: Permission = Permission::DeleteAnySong;
+}
+
+impl RequiredPermission for ManageAnyPlaylist {
+    const PERMISSION: Permission = Permission::ManageAnyPlaylist;
+}
+
+impl RequiredPermission for ManageStations {
+    const PERMISSION: Permission = Permission::ManageStations;
+}
+
+impl RequiredPermission for ManageUsers {
+    const PERMISSION: Permission = Permission::ManageUsers;
+}
+
+/// A logged in user whose role has the permission `P`, such as `PermissionAuth<ManageStations>`
+#[derive(Debug)]
+pub struct PermissionAuth<P: RequiredPermission> {
+    pub user: User,
+    /// Set when the request was made with a device token instead of a login token
+    pub device: Option<DeviceToken>,
+    permission: PhantomData<P>,
+}
+
+impl<P: RequiredPermission> PermissionAuth<P> {
+    /// # Errors
+    /// When the request was made with a device token, for routes that manage the account itself
+    pub fn require_login(&self) -> Result<(), ApiResponse> {
+        match self.device {
+            Some(_) => Err(ApiResponse::Unauthorized(
+                "Device tokens can not be used for this, log in instead".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, P: RequiredPermission> FromRequest<'r> for PermissionAuth<P> {
+    type Error = ApiResponse;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match request.guard::<TokenAuth>().await {
+            Outcome::Success(auth) if auth.user.can(P::PERMISSION) => {
+                Outcome::Success(PermissionAuth {
+                    user: auth.user,
+                    device: auth.device,
+                    permission: PhantomData,
+                })
+            }
+            Outcome::Success(auth) => Outcome::Error((
+                Status::Unauthorized,
+                ApiResponse::Unauthorized(format!(
+                    "The {} role is not allowed to do this",
+                    auth.user.role.as_str()
+                )),
+            )),
+            Outcome::Error(error) => Outcome::Error(error),
+            Outcome::Forward(status) => Outcome::Forward(status),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::api_response::ApiResponse;
+use crate::utils::rate_limit::RouteGroup;
+use crate::utils::states::RateLimitState;
+use crate::utils::{auth_util, clock};
+use harmony_core::utils::secret;
+use rocket::http::Status;
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest, Request};
+use rocket::State;
+use std::marker::PhantomData;
+use tokio::task;
+
+/// The route group a route counts against, see `RateLimit`
+pub trait LimitedGroup {
+    const GROUP: RouteGroup;
+}
+
+pub struct Stream;
+pub struct Search;
+pub struct Ingest;
+pub struct Mail;
+
+impl LimitedGroup for Stream {
+    const GROUP: RouteGroup = RouteGroup::Stream;
+}
+
+impl LimitedGroup for Search {
+    const GROUP: RouteGroup = RouteGroup::Search;
+}
+
+impl LimitedGroup for Ingest {
+    const GROUP: RouteGroup = RouteGroup::Ingest;
+}
+
+impl LimitedGroup for Mail {
+    const GROUP: RouteGroup = RouteGroup::Mail;
+}
+
+/// Seconds until a rate limited request may be made again, left for the catcher
+/// so it can send them as `Retry-After`
+#[derive(Debug, Clone, Copy)]
+pub struct RetryAfter(pub u64);
+
+/// A request that fits in the rate limit of its route group, such as `RateLimit<Stream>`.
+/// Put it before the other guards, so requests over the limit are turned away cheaply
+#[derive(Debug)]
+pub struct RateLimit<G: LimitedGroup> {
+    group: PhantomData<G>,
+}
+
+#[rocket::async_trait]
+impl<'r, G: LimitedGroup> FromRequest<'r> for RateLimit<G> {
+    type Error = ApiResponse;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let state = match request.guard::<&State<RateLimitState>>().await {
+            Outcome::Success(state) => state,
+            Outcome::Error((status, ())) => {
+                return Outcome::Error((
+                    status,
+                    ApiResponse::InternalServerError("Rate limit not set up".to_string()),
+                ))
+            }
+            Outcome::Forward(status) => return Outcome::Forward(status),
+        };
+
+        let token = match request.headers().get_one("Authorization") {
+            Some(token) => Some(token.trim_start_matches("Bearer ").to_string()),
+            None => request
+                .query_value::<&str>("ticket")
+                .and_then(Result::ok)
+                .map(str::to_string),
+        };
+
+        // Only a real token gets its own allowance, made up ones would each get a fresh one.
+        // Tokens are hashed so they are not kept around in memory
+        let token = match token {
+            Some(token) => task::spawn_blocking(move || {
+                auth_util::is_issued_token(&token).then(|| secret::hash(&token))
+            })
+            .await
+            .ok()
+            .flatten(),
+            None => None,
+        };
+
+        match state.take(G::GROUP, request.client_ip(), token, clock::now_ms()) {
+            Ok(()) => Outcome::Success(RateLimit { group: PhantomData }),
+            Err(wait) => {
+                // Rounded up, coming back a moment early would only be turned away again
+                let seconds = u64::try_from(wait).unwrap_or(0).div_ceil(1000);
+                request.local_cache(|| RetryAfter(seconds));
+                Outcome::Error((
+                    Status::TooManyRequests,
+                    ApiResponse::TooManyRequests(
+                        format!(
+                            "Too many {} requests, try again in {seconds} seconds",
+                            G::GROUP.as_str()
+                        ),
+                        seconds,
+                    ),
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::api_response::ApiResponse;
+use crate::utils::auth_util::{self, StreamTicket, TicketScope};
+use crate::utils::states::{JukeboxState, RoomState, StationState};
+use harmony_core::core::model::device_token::{Scope, DEVICE_TOKEN_PREFIX};
+use harmony_core::core::model::song::Song;
+use harmony_core::core::model::user::User;
+use rocket::http::Status;
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest, Request};
+use std::collections::HashSet;
+use tokio::task;
+
+/// The rooms, jukeboxes and stations that are live, to find out what their tickets cover
+#[derive(Debug, Clone, Default)]
+pub struct LiveSources {
+    rooms: RoomState,
+    jukeboxes: JukeboxState,
+    stations: StationState,
+}
+
+/// Access to the stream routes, either through a login token, a device token
+/// with the stream scope, or a stream ticket passed as `?ticket=` or bearer token
+#[derive(Debug, Clone)]
+pub enum StreamAuth {
+    User(User),
+    Ticket(StreamTicket, LiveSources),
+}
+
+fn file_ids<'a>(songs: impl IntoIterator<Item = &'a Song>) -> HashSet<String> {
+    songs.into_iter().map(|song| song.file_id.clone()).collect()
+}
+
+fn not_covered() -> ApiResponse {
+    ApiResponse::Unauthorized("Ticket does not grant access to this file".to_string())
+}
+
+impl StreamAuth {
+    /// The user that is streaming, or that the ticket was issued to
+    #[must_use]
+    pub fn user_id(&self) -> i32 {
+        match self {
+            StreamAuth::User(user) => user.id,
+            StreamAuth::Ticket(ticket, _) => ticket.sub,
+        }
+    }
+
+    /// # Errors
+    /// When the ticket does not cover the file
+    pub async fn check(&self, file_id: &str) -> Result<(), ApiResponse> {
+        self.check_all(&[file_id]).await
+    }
+
+    /// # Errors
+    /// When the ticket does not cover every one of the files
+    pub async fn check_all(&self, file_ids: &[&str]) -> Result<(), ApiResponse> {
+        let StreamAuth::Ticket(ticket, live) = self else {
+            return Ok(());
+        };
+
+        let covered = covered_files(ticket, live).await?;
+        if file_ids.iter().all(|file_id| covered.contains(*file_id)) {
+            return Ok(());
+        }
+
+        Err(not_covered())
+    }
+}
+
+// The files a ticket covers right now, its source may have changed since it was issued
+async fn covered_files(
+    ticket: &StreamTicket,
+    live: &LiveSources,
+) -> Result<HashSet<String>, ApiResponse> {
+    match &ticket.scope {
+        TicketScope::Source(source) => {
+            let source = source.clone();
+            let user_id = ticket.sub;
+            let queue = task::spawn_blocking(move || source.to_queue(Some(user_id)))
+                .await
+                .map_err(ApiResponse::from)??;
+
+            Ok(file_ids(queue.tracks()))
+        }
+        TicketScope::Room(code) => {
+            let room = live.rooms.get(code).await.ok_or_else(not_covered)?;
+            let room = room.lock();
+            if !room.is_member(ticket.sub) {
+                return Err(not_covered());
+            }
+
+            Ok(file_ids(room.broadcast().tracks()))
+        }
+        TicketScope::Jukebox(code) => {
+            let jukebox = live.jukeboxes.get(code).await.ok_or_else(not_covered)?;
+            let jukebox = jukebox.lock();
+            let playing = jukebox.playing().map(|playing| &playing.song);
+            let queued = jukebox.queue().iter().map(|entry| &entry.song);
+
+            Ok(file_ids(playing.into_iter().chain(queued)))
+        }
+        TicketScope::Station(station_id) => {
+            let station = live
+                .stations
+                .get(*station_id)
+                .await
+                .ok_or_else(not_covered)?;
+            let broadcast = station.lock();
+            Ok(file_ids(broadcast.tracks()))
+        }
+    }
+}
+
+// Looks the token up in the database, so it runs on the blocking pool
+fn authenticate(token: &str, live: LiveSources) -> Result<StreamAuth, ApiResponse> {
+    if token.starts_with(DEVICE_TOKEN_PREFIX) {
+        let (user, _) = auth_util::get_user_from_device_token(token, Scope::Stream)?;
+        return Ok(StreamAuth::User(user));
+    }
+
+    if let Ok((user, _)) = auth_util::get_user_from_jwt(token) {
+        return Ok(StreamAuth::User(user));
+    }
+
+    let ticket = auth_util::decode_stream_ticket(token)?;
+    Ok(StreamAuth::Ticket(ticket, live))
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for StreamAuth {
+    type Error = ApiResponse;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let token = match request.headers().get_one("Authorization") {
+            Some(token) => Some(token.trim_start_matches("Bearer ")),
+            None => request.query_value::<&str>("ticket").and_then(Result::ok),
+        };
+
+        let Some(token) = token.map(str::to_string) else {
+            return Outcome::Error((
+                Status::Unauthorized,
+                ApiResponse::Unauthorized("Missing token or ticket".to_string()),
+            ));
+        };
+
+        let rocket = request.rocket();
+        let live = LiveSources {
+            rooms: rocket.state::<RoomState>().cloned().unwrap_or_default(),
+            jukeboxes: rocket.state::<JukeboxState>().cloned().unwrap_or_default(),
+            stations: rocket.state::<StationState>().cloned().unwrap_or_default(),
+        };
+
+        let result = task::spawn_blocking(move || authenticate(&token, live))
+            .await
+            .map_err(ApiResponse::from)
+            .and_then(|result| result);
+
+        match result {
+            Ok(auth) => Outcome::Success(auth),
+            Err(error) => Outcome::Error((Status::Unauthorized, error)),
+        }
+    }
+}
@@ -1,14 +1,69 @@
 use crate::api_response::ApiResponse;
 use crate::utils::auth_util;
+use harmony_core::core::model::device_token::{DeviceToken, Scope, DEVICE_TOKEN_PREFIX};
+use harmony_core::core::model::role::Permission;
 use harmony_core::core::model::user::User;
-use rocket::http::Status;
+use rocket::http::{Method, Status};
 use rocket::outcome::Outcome;
 use rocket::request::{self, FromRequest, Request};
 use serde::{Deserialize, Serialize};
+use tokio::task;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenAuth {
     pub user: User,
+    /// Set when the request was made with a device token instead of a login token
+    pub device: Option<DeviceToken>,
+    /// The login session of the token, unset for device tokens
+    pub session: Option<String>,
+}
+
+impl TokenAuth {
+    /// # Errors
+    /// When the request was made with a device token, for routes that manage the account itself
+    pub fn require_login(&self) -> Result<(), ApiResponse> {
+        match self.device {
+            Some(_) => Err(ApiResponse::Unauthorized(
+                "Device tokens can not be used for this, log in instead".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Lets the owner through, and anyone whose role may act for others
+    /// # Errors
+    /// When the user is not the owner and their role lacks the permission
+    pub fn require_owner(
+        &self,
+        owner: Option<i32>,
+        permission: Permission,
+        message: &str,
+    ) -> Result<(), ApiResponse> {
+        if owner == Some(self.user.id) || self.user.can(permission) {
+            return Ok(());
+        }
+
+        Err(ApiResponse::Unauthorized(message.to_string()))
+    }
+}
+
+// Looks the token up in the database, so it runs on the blocking pool
+fn authenticate(token: &str, scope: Scope) -> Result<TokenAuth, ApiResponse> {
+    if token.starts_with(DEVICE_TOKEN_PREFIX) {
+        let (user, device) = auth_util::get_user_from_device_token(token, scope)?;
+        return Ok(TokenAuth {
+            user,
+            device: Some(device),
+            session: None,
+        });
+    }
+
+    let (user, session) = auth_util::get_user_from_jwt(token)?;
+    Ok(TokenAuth {
+        user,
+        device: None,
+        session: Some(session),
+    })
 }
 
 #[rocket::async_trait]
@@ -16,24 +71,28 @@ impl<'r> FromRequest<'r> for TokenAuth {
     type Error = ApiResponse;
 
     async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
-        let token = request.headers().get_one("Authorization");
-
-        match token {
-            Some(token) => {
-                let token = token.trim_start_matches("Bearer ");
-
-                match auth_util::get_user_from_jwt(token) {
-                    Ok(user) => Outcome::Success(TokenAuth { user }),
-                    Err(_) => Outcome::Error((
-                        Status::Unauthorized,
-                        ApiResponse::Unauthorized("Invalid token".to_string()),
-                    )),
-                }
-            }
-            None => Outcome::Error((
+        let Some(token) = request.headers().get_one("Authorization") else {
+            return Outcome::Error((
                 Status::Unauthorized,
                 ApiResponse::Unauthorized("Missing token".to_string()),
-            )),
+            ));
+        };
+
+        let token = token.trim_start_matches("Bearer ").to_string();
+        // Device tokens may only read or only write, depending on their scopes
+        let scope = match request.method() {
+            Method::Get | Method::Head => Scope::Read,
+            _ => Scope::Write,
+        };
+
+        let result = task::spawn_blocking(move || authenticate(&token, scope))
+            .await
+            .map_err(ApiResponse::from)
+            .and_then(|result| result);
+
+        match result {
+            Ok(auth) => Outcome::Success(auth),
+            Err(error) => Outcome::Error((Status::Unauthorized, error)),
         }
     }
 }
@@ -1,12 +1,45 @@
+#[allow(clippy::module_name_repetitions)]
+pub mod client_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod device_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod email_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod invite_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod jukebox_handler;
+
 #[allow(clippy::module_name_repetitions)]
 pub mod playlist_handler;
 
 #[allow(clippy::module_name_repetitions)]
 pub mod user_handler;
 
+#[allow(clippy::module_name_repetitions)]
+pub mod room_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod schedule_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod session_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod share_handler;
+
+#[allow(clippy::module_name_repetitions)]
+pub mod socket_handler;
+
 #[allow(clippy::module_name_repetitions)]
 pub mod song_handler;
 
+#[allow(clippy::module_name_repetitions)]
+pub mod station_handler;
+
 #[allow(clippy::module_name_repetitions)]
 pub mod stream_handler;
 
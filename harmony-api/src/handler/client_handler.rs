@@ -0,0 +1,25 @@
+use rocket::get;
+use rocket::http::{uri::Host, ContentType};
+use rocket::serde::json::Json;
+
+use crate::api_response::ApiResponse;
+use crate::model::client_version::ClientVersion;
+use crate::utils::client::{base_url, render_client};
+use crate::utils::environment;
+
+/// The ComputerCraft client, install it in game with `wget <server>/client/harmony.lua harmony`
+#[get("/harmony.lua")]
+pub fn get_client_handler(host: Option<&Host<'_>>) -> Result<(ContentType, String), ApiResponse> {
+    let script = render_client(&base_url(host)?, &environment::get_version());
+    Ok((ContentType::Plain, script))
+}
+
+#[get("/version")]
+pub fn get_client_version_handler(
+    host: Option<&Host<'_>>,
+) -> Result<Json<ClientVersion>, ApiResponse> {
+    Ok(Json(ClientVersion {
+        version: environment::get_version(),
+        url: format!("{}/client/harmony.lua", base_url(host)?),
+    }))
+}
@@ -0,0 +1,32 @@
+use harmony_core::core::model::device_token::DeviceToken;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+
+use crate::api_response::ApiResponse;
+use crate::guards::token_auth::TokenAuth;
+use crate::model::new_device_token::{DeviceTokenResponse, NewDeviceToken};
+
+#[get("/")]
+pub fn list_device_handler(auth: TokenAuth) -> Result<Json<Vec<DeviceToken>>, ApiResponse> {
+    auth.require_login()?;
+    let tokens = DeviceToken::get_by_user(auth.user.id).map_err(ApiResponse::from)?;
+    Ok(Json(tokens))
+}
+
+/// Creates a device token, the response is the only time the token is shown
+#[post("/", format = "application/json", data = "<device>")]
+pub fn create_device_handler(
+    auth: TokenAuth,
+    device: Json<NewDeviceToken>,
+) -> Result<Json<DeviceTokenResponse>, ApiResponse> {
+    auth.require_login()?;
+    let template = device.to_template(auth.user.id)?;
+    let (device, token) = template.create().map_err(ApiResponse::from)?;
+    Ok(Json(DeviceTokenResponse { token, device }))
+}
+
+#[delete("/<device_id>")]
+pub fn remove_device_handler(auth: TokenAuth, device_id: i32) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    DeviceToken::remove(auth.user.id, device_id).map_err(ApiResponse::from)
+}
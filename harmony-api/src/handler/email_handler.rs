@@ -0,0 +1,196 @@
+use harmony_core::core::model::auth_session::AuthSession;
+use harmony_core::core::model::email_token::{EmailPurpose, EmailToken};
+use harmony_core::core::model::user::User;
+use harmony_core::core::validation::validated_types::{Email, Password};
+use harmony_core::result::ServerError;
+use harmony_core::utils::mail;
+use rocket::form::Form;
+use rocket::http::uri::Host;
+use rocket::response::content::RawHtml;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+use tokio::task;
+
+use crate::api_response::ApiResponse;
+use crate::guards::rate_limit::{Mail, RateLimit};
+use crate::guards::token_auth::TokenAuth;
+use crate::handler::user_handler::verify_password;
+use crate::model::email_change::EmailChange;
+use crate::model::email_status::EmailStatus;
+use crate::model::email_verification::EmailVerification;
+use crate::model::forgotten_password::ForgottenPassword;
+use crate::model::password_reset::PasswordReset;
+use crate::utils::client::base_url;
+
+#[get("/me/email")]
+pub fn get_own_email_handler(auth: TokenAuth) -> Json<EmailStatus> {
+    Json(EmailStatus {
+        email: auth.user.email,
+        verified: auth.user.email_verified,
+    })
+}
+
+/// Sets the email address of the user and mails them a link to verify it.
+/// Until it is verified the address can't be used to reset the password
+#[post("/me/email", format = "application/json", data = "<change>")]
+pub async fn set_own_email_handler(
+    _limit: RateLimit<Mail>,
+    auth: TokenAuth,
+    host: Option<&Host<'_>>,
+    change: Json<EmailChange>,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    if !mail::is_configured() {
+        return Err(ApiResponse::BadRequest(
+            "Email is not set up on this server".to_string(),
+        ));
+    }
+
+    verify_password(&auth, &change.password)?;
+    let email = Email::new(change.email.trim())?;
+    let link = format!("{}/user/email/verify?token=", base_url(host)?);
+
+    User::set_email(auth.user.id, Some(&email)).map_err(ApiResponse::from)?;
+    let token = EmailToken::issue(auth.user.id, EmailPurpose::Verify, email.value())
+        .map_err(ApiResponse::from)?;
+
+    let body = format!(
+        "Hello {},\n\nOpen this link to verify your email address on Harmony:\n\n{link}{token}\n\n\
+         The link works for a day. If you did not ask for this, you can ignore this mail.\n",
+        auth.user.name
+    );
+    let to = email.value().clone();
+    let blocking_task =
+        task::spawn_blocking(move || mail::send(&to, "Verify your email address", body));
+    blocking_task
+        .await
+        .map_err(ApiResponse::from)?
+        .map_err(ApiResponse::from)
+}
+
+/// Removes the email address of the user, the password can't be reset by mail after this
+#[delete("/me/email")]
+pub fn remove_own_email_handler(auth: TokenAuth) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    User::set_email(auth.user.id, None).map_err(ApiResponse::from)?;
+    EmailToken::remove_all(auth.user.id).map_err(ApiResponse::from)?;
+    Ok(())
+}
+
+/// Where the link in the verification mail leads, so it can be opened in a browser.
+/// Only asks to confirm, link scanners in mail clients open it without the user
+#[get("/email/verify?<token>")]
+pub fn verify_email_link_handler(token: &str) -> RawHtml<String> {
+    RawHtml(format!(
+        "<!DOCTYPE html>\n<html>\n<head><title>Verify your email address</title></head>\n<body>\n\
+         <form method=\"post\" action=\"verify\">\n\
+         <input type=\"hidden\" name=\"token\" value=\"{}\">\n\
+         <button type=\"submit\">Verify my email address</button>\n\
+         </form>\n</body>\n</html>\n",
+        escape_html(token)
+    ))
+}
+
+/// Sent by the page of the link, the token is only used up here
+#[post("/email/verify", format = "form", data = "<verification>")]
+pub fn verify_email_form_handler(
+    verification: Form<EmailVerification>,
+) -> Result<String, ApiResponse> {
+    verify_email(&verification.token)?;
+    Ok("Your email address is verified, you can close this page".to_string())
+}
+
+#[post("/email/verify", format = "application/json", data = "<verification>")]
+pub fn verify_email_handler(verification: Json<EmailVerification>) -> Result<(), ApiResponse> {
+    verify_email(&verification.token)
+}
+
+/// Mails a reset token to the account, named by its name or its verified email address.
+/// Always succeeds, so it can't be used to find out which accounts exist
+#[post("/password/forgot", format = "application/json", data = "<forgotten>")]
+pub fn forgot_password_handler(
+    _limit: RateLimit<Mail>,
+    forgotten: Json<ForgottenPassword>,
+) -> Result<(), ApiResponse> {
+    if !mail::is_configured() {
+        return Err(ApiResponse::BadRequest(
+            "Email is not set up on this server, ask an admin to reset your password".to_string(),
+        ));
+    }
+
+    // Looking up the account and sending take longer when it exists, so it all happens
+    // after the response and the time it takes can't tell which accounts exist
+    let account = forgotten.0.account.trim().to_string();
+    task::spawn_blocking(move || send_reset_mail(&account));
+    Ok(())
+}
+
+fn send_reset_mail(account: &str) -> Result<(), ServerError> {
+    let user = if account.contains('@') {
+        User::get_by_verified_email(account)?
+    } else {
+        User::get_by_name(account)?
+    };
+    let Some(email) = user.verified_email().map(str::to_string) else {
+        return Ok(());
+    };
+
+    let token = EmailToken::issue(user.id, EmailPurpose::Reset, &email)?;
+    let body = format!(
+        "Hello {},\n\nSomeone asked to reset the password of your Harmony account. \
+         Use this token to set a new one:\n\n{token}\n\n\
+         It works for an hour. If you did not ask for this, you can ignore this mail.\n",
+        user.name
+    );
+    mail::send(&email, "Reset your password", body)
+}
+
+/// Sets a new password with a mailed reset token, every device of the user is logged out
+#[post("/password/reset", format = "application/json", data = "<reset>")]
+pub fn reset_password_handler(reset: Json<PasswordReset>) -> Result<(), ApiResponse> {
+    let new_password = Password::new(&reset.new_password)?;
+    let token = EmailToken::consume(&reset.token, EmailPurpose::Reset).map_err(invalid_token)?;
+
+    // The token only counts while it was sent to the address the user still has verified
+    let user = User::get_by_id(token.user_id).map_err(invalid_token)?;
+    let still_verified = user
+        .verified_email()
+        .is_some_and(|email| email.eq_ignore_ascii_case(&token.email));
+    if !still_verified {
+        return Err(invalid_token(ServerError::NotFound));
+    }
+
+    User::set_password(user.id, &new_password).map_err(ApiResponse::from)?;
+    AuthSession::remove_all(user.id).map_err(ApiResponse::from)?;
+    EmailToken::remove_all(user.id).map_err(ApiResponse::from)?;
+    Ok(())
+}
+
+fn verify_email(token: &str) -> Result<(), ApiResponse> {
+    let token = EmailToken::consume(token, EmailPurpose::Verify).map_err(invalid_token)?;
+
+    match User::verify_email(token.user_id, &token.email) {
+        Ok(_) => Ok(()),
+        Err(ServerError::AlreadyExists) => Err(ApiResponse::Conflict(
+            "Email is already used by another account".to_string(),
+        )),
+        Err(error) => Err(invalid_token(error)),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn invalid_token(error: ServerError) -> ApiResponse {
+    match error {
+        ServerError::NotFound => {
+            ApiResponse::BadRequest("Token is invalid, used up or expired".to_string())
+        }
+        error => ApiResponse::from(error),
+    }
+}
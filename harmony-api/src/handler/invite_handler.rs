@@ -0,0 +1,55 @@
+use chrono::{TimeDelta, Utc};
+use harmony_core::core::model::invite::Invite;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+
+use crate::api_response::ApiResponse;
+use crate::guards::permission_auth::{ManageUsers, PermissionAuth};
+use crate::model::new_invite::NewInvite;
+
+#[get("/")]
+pub fn list_invite_handler(
+    _auth: PermissionAuth<ManageUsers>,
+) -> Result<Json<Vec<Invite>>, ApiResponse> {
+    let invites = Invite::get_list().map_err(ApiResponse::from)?;
+    Ok(Json(invites))
+}
+
+/// Creates an invite code to hand out, see `REGISTRATION_MODE`
+#[post("/", format = "application/json", data = "<invite>")]
+pub fn create_invite_handler(
+    auth: PermissionAuth<ManageUsers>,
+    invite: Json<NewInvite>,
+) -> Result<Json<Invite>, ApiResponse> {
+    auth.require_login()?;
+
+    if invite.max_uses.is_some_and(|uses| uses < 1) {
+        return Err(ApiResponse::BadRequest(
+            "An invite needs at least one use".to_string(),
+        ));
+    }
+
+    let expires = match invite.expires_in_hours {
+        Some(0) => {
+            return Err(ApiResponse::BadRequest(
+                "An invite has to be valid for at least an hour".to_string(),
+            ))
+        }
+        Some(hours) => Some(Utc::now() + TimeDelta::hours(i64::from(hours))),
+        None => None,
+    };
+
+    let invite =
+        Invite::generate(auth.user.id, invite.max_uses, expires).map_err(ApiResponse::from)?;
+    Ok(Json(invite))
+}
+
+/// Revokes an invite, accounts already created with it are kept
+#[delete("/<code>")]
+pub fn remove_invite_handler(
+    auth: PermissionAuth<ManageUsers>,
+    code: &str,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    Invite::remove(code).map_err(ApiResponse::from)
+}
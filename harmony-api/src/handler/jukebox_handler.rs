@@ -0,0 +1,236 @@
+use chrono::Utc;
+use harmony_core::core::model::{playlist::Playlist, schedule::Schedule, song::Song};
+use harmony_core::core::playback::jukebox::Jukebox;
+use harmony_core::result::ServerError;
+use harmony_core::utils::song_file;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+use std::sync::Arc;
+use tokio::task;
+
+use crate::api_response::ApiResponse;
+use crate::guards::token_auth::TokenAuth;
+use crate::model::jukebox_info::{JukeboxInfo, JukeboxVote};
+use crate::model::new_jukebox::{NewJukebox, NewJukeboxEntry};
+use crate::utils::auth_util::{self, TicketScope};
+use crate::utils::clock;
+use crate::utils::environment;
+use crate::utils::states::{JukeboxState, SharedJukebox};
+
+/// Switches the fallback playlist to the one scheduled right now
+fn sync_schedule(jukebox: &SharedJukebox) -> Result<(), ServerError> {
+    let schedules = Schedule::get_by_jukebox(&jukebox.code)?;
+    let in_effect = Schedule::in_effect(&schedules, Utc::now());
+
+    let schedule_id = in_effect.map(|(schedule, _)| schedule.id);
+    if jukebox.lock().fallback_id() == schedule_id {
+        return Ok(());
+    }
+
+    // Look the songs up without holding the lock
+    let fallback = match in_effect {
+        Some((schedule, _)) => {
+            let tracks = song_file::with_durations(
+                Playlist::get_visible(schedule.playlist, schedule.created_by)?.get_songs()?,
+            );
+
+            Some((schedule.id, tracks))
+        }
+        None => None,
+    };
+
+    jukebox.lock().set_fallback(fallback, clock::now_ms());
+    Ok(())
+}
+
+async fn get_jukebox(
+    code: &str,
+    jukeboxes: &JukeboxState,
+) -> Result<Arc<SharedJukebox>, ApiResponse> {
+    let jukebox = jukeboxes
+        .get(code)
+        .await
+        .ok_or_else(|| ApiResponse::NotFound("Could not find jukebox".to_string()))?;
+
+    // Schedules and playlists come from the database and durations from the song files
+    let blocking_jukebox = jukebox.clone();
+    let blocking_task = task::spawn_blocking(move || sync_schedule(&blocking_jukebox));
+    blocking_task.await.map_err(ApiResponse::from)??;
+    Ok(jukebox)
+}
+
+fn info(jukebox: &SharedJukebox, user_id: i32) -> Json<JukeboxInfo> {
+    let now = clock::now_ms();
+    let mut lock = jukebox.lock();
+    lock.update(now);
+    Json(JukeboxInfo::new(
+        &jukebox.code,
+        jukebox.owner,
+        &lock,
+        user_id,
+        now,
+    ))
+}
+
+#[post("/", format = "application/json", data = "<jukebox>")]
+pub async fn create_jukebox_handler(
+    auth: TokenAuth,
+    jukebox: Json<NewJukebox>,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<JukeboxInfo>, ApiResponse> {
+    let skip_threshold = jukebox
+        .0
+        .skip_threshold
+        .unwrap_or_else(environment::get_jukebox_skip_threshold);
+
+    if skip_threshold == 0 {
+        return Err(ApiResponse::BadRequest(
+            "Skip threshold must be at least 1".to_string(),
+        ));
+    }
+
+    let jukebox = jukeboxes
+        .create(auth.user.id, Jukebox::new(skip_threshold))
+        .await
+        .map_err(ApiResponse::from)?;
+    Ok(info(&jukebox, auth.user.id))
+}
+
+/// Describes what the jukebox is playing and its queue. With `ticket` the response includes
+/// a stream ticket for the playing and queued songs
+#[get("/<code>?<ticket>")]
+pub async fn get_jukebox_handler(
+    auth: TokenAuth,
+    code: &str,
+    ticket: Option<bool>,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<JukeboxInfo>, ApiResponse> {
+    let jukebox = get_jukebox(code, jukeboxes).await?;
+    let mut info = info(&jukebox, auth.user.id);
+
+    if ticket.unwrap_or(false) {
+        let scope = TicketScope::Jukebox(jukebox.code.clone());
+        let ticket = auth_util::get_stream_ticket(&auth.user, scope)?;
+        info.ticket = Some(auth_util::encode_stream_ticket(&ticket)?);
+    }
+
+    Ok(info)
+}
+
+#[post("/<code>/queue", format = "application/json", data = "<entry>")]
+pub async fn enqueue_jukebox_handler(
+    auth: TokenAuth,
+    code: &str,
+    entry: Json<NewJukeboxEntry>,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<JukeboxInfo>, ApiResponse> {
+    let jukebox = get_jukebox(code, jukeboxes).await?;
+
+    // Songs without audio would play as silence, so they are refused up front
+    let song = Song::get_by_id(entry.0.song)?;
+    let duration_ms = song_file::get_song_duration(&song)?;
+
+    jukebox
+        .lock()
+        .enqueue(song, duration_ms, auth.user.id, clock::now_ms())
+        .map_err(|_| ApiResponse::Conflict("Song is already in the jukebox".to_string()))?;
+
+    Ok(info(&jukebox, auth.user.id))
+}
+
+#[post(
+    "/<code>/queue/<entry_id>/vote",
+    format = "application/json",
+    data = "<vote>"
+)]
+pub async fn vote_jukebox_handler(
+    auth: TokenAuth,
+    code: &str,
+    entry_id: u64,
+    vote: Json<JukeboxVote>,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<JukeboxInfo>, ApiResponse> {
+    let jukebox = get_jukebox(code, jukeboxes).await?;
+
+    jukebox
+        .lock()
+        .vote(entry_id, auth.user.id, vote.0.vote, clock::now_ms())
+        .map_err(|_| ApiResponse::NotFound("Song is not in the queue".to_string()))?;
+
+    Ok(info(&jukebox, auth.user.id))
+}
+
+/// Takes a song out of the queue, only the user who added it and the jukebox owner can
+#[delete("/<code>/queue/<entry_id>")]
+pub async fn remove_jukebox_entry_handler(
+    auth: TokenAuth,
+    code: &str,
+    entry_id: u64,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<JukeboxInfo>, ApiResponse> {
+    let jukebox = get_jukebox(code, jukeboxes).await?;
+
+    {
+        let mut lock = jukebox.lock();
+        let now = clock::now_ms();
+        lock.update(now);
+
+        let Some(entry) = lock.queue().iter().find(|entry| entry.id == entry_id) else {
+            return Err(ApiResponse::NotFound(
+                "Song is not in the queue".to_string(),
+            ));
+        };
+
+        if entry.added_by != auth.user.id && jukebox.owner != auth.user.id {
+            return Err(ApiResponse::Unauthorized(
+                "Only the user who added the song or the owner can remove it".to_string(),
+            ));
+        }
+
+        lock.remove(entry_id, now)?;
+    }
+
+    Ok(info(&jukebox, auth.user.id))
+}
+
+/// Votes to skip the current song, the owner skips it right away
+#[post("/<code>/skip")]
+pub async fn skip_jukebox_handler(
+    auth: TokenAuth,
+    code: &str,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<JukeboxInfo>, ApiResponse> {
+    let jukebox = get_jukebox(code, jukeboxes).await?;
+
+    {
+        let mut lock = jukebox.lock();
+        let now = clock::now_ms();
+
+        if jukebox.owner == auth.user.id {
+            lock.skip(now);
+        } else {
+            lock.vote_skip(auth.user.id, now)
+                .map_err(|_| ApiResponse::NotFound("Nothing is playing".to_string()))?;
+        }
+    }
+
+    Ok(info(&jukebox, auth.user.id))
+}
+
+#[delete("/<code>")]
+pub async fn remove_jukebox_handler(
+    auth: TokenAuth,
+    code: &str,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<(), ApiResponse> {
+    let jukebox = get_jukebox(code, jukeboxes).await?;
+
+    if jukebox.owner != auth.user.id {
+        return Err(ApiResponse::Unauthorized(
+            "Not the owner of this jukebox".to_string(),
+        ));
+    }
+
+    jukeboxes.remove(code).await.map_err(ApiResponse::from)?;
+    Ok(())
+}
@@ -1,9 +1,15 @@
 use harmony_core::core::model::playlist::Playlist;
+use harmony_core::core::model::role::Permission;
 // use application::post::read;
 use rocket::{delete, get, post, serde::json::Json};
 
 use crate::{
-    api_response::ApiResponse, guards::token_auth::TokenAuth, model::new_playlist::NewPlaylist,
+    api_response::ApiResponse,
+    guards::{
+        rate_limit::{RateLimit, Search},
+        token_auth::TokenAuth,
+    },
+    model::new_playlist::NewPlaylist,
 };
 
 #[get("/")]
@@ -19,7 +25,10 @@ pub fn get_playlist_handler(
 ) -> Result<Json<Playlist>, ApiResponse> {
     match Playlist::get_by_id(playlist_id) {
         Ok(playlist) => {
-            if playlist.public || (playlist.owner == auth.user.id) {
+            if playlist.public
+                || playlist.owner == auth.user.id
+                || auth.user.can(Permission::ManageAnyPlaylist)
+            {
                 return Ok(Json(playlist));
             }
 
@@ -36,11 +45,11 @@ pub fn add_song_to_playlist_handler(
     song_id: i32,
 ) -> Result<(), ApiResponse> {
     let playlist = Playlist::get_by_id(playlist_id)?;
-    if playlist.owner != auth.user.id {
-        return Err(ApiResponse::Unauthorized(
-            "Not the owner of this playlist".to_string(),
-        ));
-    };
+    auth.require_owner(
+        Some(playlist.owner),
+        Permission::ManageAnyPlaylist,
+        "Not the owner of this playlist",
+    )?;
 
     playlist.add_song(song_id)?;
     Ok(())
@@ -53,11 +62,11 @@ pub fn remove_song_from_playlist_handler(
     song_id: i32,
 ) -> Result<(), ApiResponse> {
     let playlist = Playlist::get_by_id(playlist_id)?;
-    if playlist.owner != auth.user.id {
-        return Err(ApiResponse::Unauthorized(
-            "Not the owner of this playlist".to_string(),
-        ));
-    };
+    auth.require_owner(
+        Some(playlist.owner),
+        Permission::ManageAnyPlaylist,
+        "Not the owner of this playlist",
+    )?;
 
     playlist.remove_song(song_id)?;
     Ok(())
@@ -65,16 +74,18 @@ pub fn remove_song_from_playlist_handler(
 
 #[get("/name/<name>")]
 pub fn find_playlist_handler(
+    _limit: RateLimit<Search>,
     auth: TokenAuth,
     name: &str,
 ) -> Result<Json<Vec<Playlist>>, ApiResponse> {
     let playlists: Vec<Playlist> =
-        Playlist::find_by_name(&name, auth.user.id).map_err(ApiResponse::from)?;
+        Playlist::find_by_name(name, auth.user.id).map_err(ApiResponse::from)?;
     Ok(Json(playlists))
 }
 
 #[get("/user/<user_id>")]
 pub fn find_playlist_by_user_handler(
+    _limit: RateLimit<Search>,
     auth: TokenAuth,
     user_id: i32,
 ) -> Result<Json<Vec<Playlist>>, ApiResponse> {
@@ -96,11 +107,11 @@ pub fn create_playlist_handler(
 #[delete("/id/<playlist_id>")]
 pub fn remove_playlist_handler(auth: TokenAuth, playlist_id: i32) -> Result<(), ApiResponse> {
     let playlist = Playlist::get_by_id(playlist_id)?;
-    if playlist.owner != auth.user.id {
-        return Err(ApiResponse::Unauthorized(
-            "Not the owner of this playlist".to_string(),
-        ));
-    }
+    auth.require_owner(
+        Some(playlist.owner),
+        Permission::ManageAnyPlaylist,
+        "Not the owner of this playlist",
+    )?;
 
     playlist.remove()?;
     Ok(())
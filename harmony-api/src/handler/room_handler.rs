@@ -0,0 +1,206 @@
+use harmony_core::core::model::user::User;
+use harmony_core::core::playback::room::Room;
+use harmony_core::utils::song_file;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+use std::sync::Arc;
+use tokio::task;
+
+use crate::api_response::ApiResponse;
+use crate::guards::token_auth::TokenAuth;
+use crate::model::new_room::NewRoom;
+use crate::model::room_info::RoomInfo;
+use crate::utils::auth_util::{self, TicketScope};
+use crate::utils::clock;
+use crate::utils::position_util::resolve_offset;
+use crate::utils::states::{RoomState, SharedRoom};
+
+async fn get_room(code: &str, rooms: &RoomState) -> Result<Arc<SharedRoom>, ApiResponse> {
+    rooms
+        .get(code)
+        .await
+        .ok_or_else(|| ApiResponse::NotFound("Could not find room".to_string()))
+}
+
+async fn get_hosted_room(
+    auth: &TokenAuth,
+    code: &str,
+    rooms: &RoomState,
+) -> Result<Arc<SharedRoom>, ApiResponse> {
+    let room = get_room(code, rooms).await?;
+
+    if room.lock().host() != auth.user.id {
+        return Err(ApiResponse::Unauthorized(
+            "Not the host of this room".to_string(),
+        ));
+    }
+
+    Ok(room)
+}
+
+/// Describes the room with a ticket for its tracks, so members can stream them right away
+fn member_info(room: &SharedRoom, user: &User) -> Result<Json<RoomInfo>, ApiResponse> {
+    let mut info = RoomInfo::new(&room.code, &room.lock(), clock::now_ms());
+
+    let ticket = auth_util::get_stream_ticket(user, TicketScope::Room(room.code.clone()))?;
+    info.ticket = Some(auth_util::encode_stream_ticket(&ticket)?);
+
+    Ok(Json(info))
+}
+
+fn info(room: &SharedRoom) -> Json<RoomInfo> {
+    Json(RoomInfo::new(&room.code, &room.lock(), clock::now_ms()))
+}
+
+#[post("/", format = "application/json", data = "<room>")]
+pub async fn create_room_handler(
+    auth: TokenAuth,
+    room: Json<NewRoom>,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    // The songs come from the database and their durations from the song files
+    let host = auth.user.id;
+    let NewRoom { source, repeat } = room.into_inner();
+    let blocking_task = task::spawn_blocking(move || -> Result<Room, ApiResponse> {
+        let queue = source.to_queue(Some(host))?;
+        let tracks = song_file::with_durations(queue.tracks().to_vec());
+        Ok(Room::new(host, tracks, repeat, clock::now_ms()))
+    });
+    let room = blocking_task.await.map_err(ApiResponse::from)??;
+
+    let room = rooms.create(room).await;
+    member_info(&room, &auth.user)
+}
+
+#[get("/<code>")]
+pub async fn get_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_room(code, rooms).await?;
+
+    if !room.lock().is_member(auth.user.id) {
+        return Err(ApiResponse::Unauthorized(
+            "Not a member of this room".to_string(),
+        ));
+    }
+
+    member_info(&room, &auth.user)
+}
+
+#[post("/<code>/join")]
+pub async fn join_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_room(code, rooms).await?;
+    room.lock().join(auth.user.id);
+    member_info(&room, &auth.user)
+}
+
+#[post("/<code>/leave")]
+pub async fn leave_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<(), ApiResponse> {
+    let room = get_room(code, rooms).await?;
+    let mut room = room.lock();
+
+    if room.host() == auth.user.id {
+        return Err(ApiResponse::BadRequest(
+            "The host cannot leave the room, close it instead".to_string(),
+        ));
+    }
+
+    room.leave(auth.user.id);
+    Ok(())
+}
+
+#[post("/<code>/pause")]
+pub async fn pause_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_hosted_room(&auth, code, rooms).await?;
+    room.lock().broadcast_mut().pause(clock::now_ms());
+    Ok(info(&room))
+}
+
+#[post("/<code>/resume")]
+pub async fn resume_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_hosted_room(&auth, code, rooms).await?;
+    room.lock().broadcast_mut().resume(clock::now_ms());
+    Ok(info(&room))
+}
+
+#[post("/<code>/skip")]
+pub async fn skip_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_hosted_room(&auth, code, rooms).await?;
+    room.lock().broadcast_mut().skip(clock::now_ms());
+    Ok(info(&room))
+}
+
+#[post("/<code>/previous")]
+pub async fn previous_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_hosted_room(&auth, code, rooms).await?;
+    room.lock().broadcast_mut().previous(clock::now_ms());
+    Ok(info(&room))
+}
+
+#[post("/<code>/seek?<offset>&<position_ms>&<position_seconds>")]
+pub async fn seek_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    offset: Option<usize>,
+    position_ms: Option<u64>,
+    position_seconds: Option<f64>,
+    rooms: &rocket::State<RoomState>,
+) -> Result<Json<RoomInfo>, ApiResponse> {
+    let room = get_hosted_room(&auth, code, rooms).await?;
+
+    {
+        let mut room = room.lock();
+        let now = clock::now_ms();
+
+        let encoding = room
+            .broadcast()
+            .current(now)
+            .map(|(_, song)| song.encoding)
+            .unwrap_or_default();
+
+        let Some(offset) = resolve_offset(offset, position_ms, position_seconds, &encoding)? else {
+            return Err(ApiResponse::BadRequest("Missing position".to_string()));
+        };
+
+        room.broadcast_mut().seek(now, encoding.duration_ms(offset));
+    }
+
+    Ok(info(&room))
+}
+
+#[delete("/<code>")]
+pub async fn remove_room_handler(
+    auth: TokenAuth,
+    code: &str,
+    rooms: &rocket::State<RoomState>,
+) -> Result<(), ApiResponse> {
+    get_hosted_room(&auth, code, rooms).await?;
+    rooms.remove(code).await;
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use harmony_core::core::model::role::Permission;
+use harmony_core::core::model::schedule::Schedule;
+use harmony_core::core::template::new_schedule::ScheduleTarget;
+use harmony_core::utils::short_code;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+use tokio::task;
+
+use crate::api_response::ApiResponse;
+use crate::guards::token_auth::TokenAuth;
+use crate::model::new_schedule::NewSchedule;
+use crate::model::scheduled_at::{ScheduledAt, ScheduledWindow};
+use crate::utils::states::JukeboxState;
+
+#[get("/")]
+pub fn list_schedule_handler(_auth: TokenAuth) -> Result<Json<Vec<Schedule>>, ApiResponse> {
+    let schedules = Schedule::get_list().map_err(ApiResponse::from)?;
+    Ok(Json(schedules))
+}
+
+#[get("/id/<schedule_id>")]
+pub fn get_schedule_handler(
+    _auth: TokenAuth,
+    schedule_id: i32,
+) -> Result<Json<Schedule>, ApiResponse> {
+    let schedule = Schedule::get_by_id(schedule_id).map_err(ApiResponse::from)?;
+    Ok(Json(schedule))
+}
+
+/// Lists the schedules with a window at the given time, now when no time is given.
+/// The time is an RFC 3339 timestamp such as `2026-06-06T20:30:00+02:00`
+#[get("/at?<time>&<station>&<jukebox>")]
+pub fn scheduled_at_handler(
+    _auth: TokenAuth,
+    time: Option<&str>,
+    station: Option<i32>,
+    jukebox: Option<&str>,
+) -> Result<Json<ScheduledAt>, ApiResponse> {
+    let time = match time {
+        Some(time) => DateTime::parse_from_rfc3339(time)
+            .map_err(|_| ApiResponse::BadRequest("Time must be an RFC 3339 timestamp".to_string()))?
+            .with_timezone(&Utc),
+        None => Utc::now(),
+    };
+
+    let schedules = match (station, jukebox) {
+        (Some(station), None) => Schedule::get_by_station(station)?,
+        (None, Some(jukebox)) => Schedule::get_by_jukebox(&short_code::normalize(jukebox))?,
+        (None, None) => Schedule::get_list()?,
+        (Some(_), Some(_)) => {
+            return Err(ApiResponse::BadRequest(
+                "Only one of station or jukebox can be given".to_string(),
+            ))
+        }
+    };
+
+    let active: Vec<ScheduledWindow> = Schedule::active(&schedules, time)
+        .into_iter()
+        .map(|(schedule, window)| ScheduledWindow {
+            schedule: schedule.clone(),
+            window,
+        })
+        .collect();
+
+    let in_effect = if station.is_some() || jukebox.is_some() {
+        Schedule::in_effect(&schedules, time).map(|(schedule, window)| ScheduledWindow {
+            schedule: schedule.clone(),
+            window,
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(ScheduledAt {
+        time,
+        in_effect,
+        active,
+    }))
+}
+
+/// Schedules a playlist. Stations can only be scheduled by roles that manage them,
+/// jukeboxes by their owner
+#[post("/", format = "application/json", data = "<schedule>")]
+pub async fn create_schedule_handler(
+    auth: TokenAuth,
+    schedule: Json<NewSchedule>,
+    jukeboxes: &rocket::State<JukeboxState>,
+) -> Result<Json<Schedule>, ApiResponse> {
+    let template = schedule.0.to_template(auth.user.id)?;
+
+    let allowed = match &template.target {
+        ScheduleTarget::Station(_) => auth.user.can(Permission::ManageStations),
+        ScheduleTarget::Jukebox(code) => {
+            let Some(jukebox) = jukeboxes.get(code).await else {
+                return Err(ApiResponse::NotFound("Could not find jukebox".to_string()));
+            };
+
+            auth.user.can(Permission::ManageStations) || jukebox.owner == auth.user.id
+        }
+    };
+
+    if !allowed {
+        return Err(ApiResponse::Unauthorized(
+            "Not allowed to schedule this station or jukebox".to_string(),
+        ));
+    }
+
+    let blocking_task = task::spawn_blocking(move || template.create());
+    let schedule = blocking_task.await.map_err(ApiResponse::from)??;
+
+    Ok(Json(schedule))
+}
+
+/// Removes a schedule, only the user who created it and roles that manage stations can
+#[delete("/id/<schedule_id>")]
+pub fn remove_schedule_handler(auth: TokenAuth, schedule_id: i32) -> Result<(), ApiResponse> {
+    let schedule = Schedule::get_by_id(schedule_id).map_err(ApiResponse::from)?;
+
+    auth.require_owner(
+        schedule.created_by,
+        Permission::ManageStations,
+        "Not the creator of this schedule",
+    )?;
+
+    schedule.remove().map_err(ApiResponse::from)
+}
@@ -0,0 +1,225 @@
+use harmony_core::result::ServerError;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+use std::sync::Arc;
+
+use crate::api_response::ApiResponse;
+use crate::guards::rate_limit::{RateLimit, Stream};
+use crate::guards::stream_auth::StreamAuth;
+use crate::model::new_session::NewSession;
+use crate::model::session_chunk::SessionChunk;
+use crate::model::session_info::{PlaybackState, SessionInfo};
+use crate::utils::player::{Loaded, Player, MAX_CROSSFADE_MS};
+use crate::utils::position_util::resolve_offset;
+use crate::utils::states::{PlaybackSession, SessionState, StreamState};
+
+// The amount of audio a read returns when no length is given,
+// the most a CC:Tweaked speaker accepts at once
+const DEFAULT_READ_LENGTH: usize = 16 * 1024;
+
+async fn get_owned_session(
+    auth: &StreamAuth,
+    session_id: &str,
+    sessions: &SessionState,
+) -> Result<Arc<PlaybackSession>, ApiResponse> {
+    let Some(session) = sessions.get(session_id).await else {
+        return Err(ApiResponse::NotFound("Could not find session".to_string()));
+    };
+
+    if session.owner != auth.user_id() {
+        return Err(ApiResponse::Unauthorized(
+            "Not the owner of this session".to_string(),
+        ));
+    }
+
+    Ok(session)
+}
+
+/// Opens the current track so it can be described or read, tracks with missing audio are skipped
+async fn load(player: &mut Player, streams: &StreamState) -> Result<Loaded, ApiResponse> {
+    loop {
+        match player.load(streams).await {
+            Ok(loaded) => return Ok(loaded),
+            Err((_, ServerError::FileNotFound)) => {}
+            Err((_, error)) => return Err(ApiResponse::from(error)),
+        }
+    }
+}
+
+#[post("/", format = "application/json", data = "<session>")]
+pub async fn create_session_handler(
+    auth: StreamAuth,
+    session: Json<NewSession>,
+    sessions: &rocket::State<SessionState>,
+    streams: &rocket::State<StreamState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = session.into_inner();
+    let queue = session.source.into_stream_queue(&auth).await?;
+    let mut player = match session.crossfade_ms {
+        Some(crossfade_ms) if crossfade_ms > MAX_CROSSFADE_MS => {
+            return Err(ApiResponse::BadRequest(format!(
+                "Crossfade can be at most {MAX_CROSSFADE_MS} ms"
+            )));
+        }
+        Some(crossfade_ms) => Player::with_crossfade(queue, crossfade_ms),
+        None => Player::new(queue),
+    };
+    load(&mut player, streams).await?;
+
+    let session = sessions.create(auth.user_id(), player).await;
+    let player = session.player.lock().await;
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+#[get("/<session_id>")]
+pub async fn get_session_handler(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
+    session_id: &str,
+    sessions: &rocket::State<SessionState>,
+    streams: &rocket::State<StreamState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+    load(&mut player, streams).await?;
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+/// Returns the next bytes of the session and moves its cursor past them.
+/// A read never spans two tracks, the next read starts the following track,
+/// unless the session renders a continuous stream with a crossfade
+#[get("/<session_id>/read?<length>&<length_ms>")]
+pub async fn read_session_handler(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
+    session_id: &str,
+    length: Option<usize>,
+    length_ms: Option<u64>,
+    sessions: &rocket::State<SessionState>,
+    streams: &rocket::State<StreamState>,
+) -> Result<SessionChunk, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+
+    if !player.queue().is_paused() {
+        load(&mut player, streams).await?;
+    }
+
+    let queue = player.queue();
+    let state = PlaybackState::of(&player);
+    let index = queue.index();
+    let offset = queue.offset();
+    let song = queue.current().map(|song| song.id);
+    let encoding = queue
+        .current()
+        .map(|song| song.encoding)
+        .unwrap_or_default();
+
+    let chunk = match state {
+        PlaybackState::Playing => {
+            let length =
+                resolve_offset(length, length_ms, None, &encoding)?.unwrap_or(DEFAULT_READ_LENGTH);
+            player.take(length)
+        }
+        PlaybackState::Paused | PlaybackState::Finished => None,
+    };
+
+    Ok(SessionChunk {
+        state,
+        index,
+        song,
+        offset,
+        chunk,
+    })
+}
+
+#[post("/<session_id>/pause")]
+pub async fn pause_session_handler(
+    auth: StreamAuth,
+    session_id: &str,
+    sessions: &rocket::State<SessionState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+    player.pause();
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+#[post("/<session_id>/resume")]
+pub async fn resume_session_handler(
+    auth: StreamAuth,
+    session_id: &str,
+    sessions: &rocket::State<SessionState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+    player.resume();
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+#[post("/<session_id>/skip")]
+pub async fn skip_session_handler(
+    auth: StreamAuth,
+    session_id: &str,
+    sessions: &rocket::State<SessionState>,
+    streams: &rocket::State<StreamState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+    player.skip();
+    load(&mut player, streams).await?;
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+#[post("/<session_id>/previous")]
+pub async fn previous_session_handler(
+    auth: StreamAuth,
+    session_id: &str,
+    sessions: &rocket::State<SessionState>,
+    streams: &rocket::State<StreamState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+    player.previous();
+    load(&mut player, streams).await?;
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+#[post("/<session_id>/seek?<offset>&<position_ms>&<position_seconds>")]
+pub async fn seek_session_handler(
+    auth: StreamAuth,
+    session_id: &str,
+    offset: Option<usize>,
+    position_ms: Option<u64>,
+    position_seconds: Option<f64>,
+    sessions: &rocket::State<SessionState>,
+    streams: &rocket::State<StreamState>,
+) -> Result<Json<SessionInfo>, ApiResponse> {
+    let session = get_owned_session(&auth, session_id, sessions).await?;
+    let mut player = session.player.lock().await;
+
+    let encoding = player
+        .queue()
+        .current()
+        .map(|song| song.encoding)
+        .unwrap_or_default();
+
+    let Some(offset) = resolve_offset(offset, position_ms, position_seconds, &encoding)? else {
+        return Err(ApiResponse::BadRequest("Missing position".to_string()));
+    };
+
+    player.seek(offset);
+    load(&mut player, streams).await?;
+    Ok(Json(SessionInfo::new(&session.id, &player)))
+}
+
+#[delete("/<session_id>")]
+pub async fn remove_session_handler(
+    auth: StreamAuth,
+    session_id: &str,
+    sessions: &rocket::State<SessionState>,
+) -> Result<(), ApiResponse> {
+    get_owned_session(&auth, session_id, sessions).await?;
+    sessions.remove(session_id).await;
+    Ok(())
+}
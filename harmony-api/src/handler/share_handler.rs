@@ -0,0 +1,115 @@
+use harmony_core::core::model::playlist::Playlist;
+use harmony_core::core::model::role::Permission;
+use harmony_core::core::model::share_code::{ShareCode, ShareTarget};
+use harmony_core::core::model::song::Song;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+
+use crate::api_response::ApiResponse;
+use crate::guards::token_auth::TokenAuth;
+use crate::model::share_info::{ShareInfo, SharedItem};
+
+/// Resolves what a code points to, private playlists are only shown to their owner
+fn share_info(auth: &TokenAuth, share_code: ShareCode) -> Result<ShareInfo, ApiResponse> {
+    let item = match share_code.target {
+        ShareTarget::Song(song_id) => SharedItem::Song(Song::get_by_id(song_id)?),
+        ShareTarget::Playlist(playlist_id) => {
+            let playlist = Playlist::get_by_id(playlist_id)?;
+            if !playlist.public
+                && playlist.owner != auth.user.id
+                && !auth.user.can(Permission::ManageAnyPlaylist)
+            {
+                return Err(ApiResponse::Unauthorized("Playlist is private".to_string()));
+            }
+
+            SharedItem::Playlist(playlist)
+        }
+    };
+
+    Ok(ShareInfo {
+        code: share_code.code,
+        created: share_code.created,
+        item,
+    })
+}
+
+/// Songs can have their code changed by whoever added them, playlists by their owner,
+/// and either by roles that may manage them for others
+fn check_manage(auth: &TokenAuth, target: ShareTarget) -> Result<(), ApiResponse> {
+    let (owner, permission) = match target {
+        ShareTarget::Song(song_id) => (
+            Song::get_by_id(song_id)?.added_by,
+            Permission::DeleteAnySong,
+        ),
+        ShareTarget::Playlist(playlist_id) => (
+            Some(Playlist::get_by_id(playlist_id)?.owner),
+            Permission::ManageAnyPlaylist,
+        ),
+    };
+
+    auth.require_owner(owner, permission, "Not allowed to change this share code")
+}
+
+#[get("/<code>")]
+pub fn lookup_share_handler(auth: TokenAuth, code: &str) -> Result<Json<ShareInfo>, ApiResponse> {
+    let share_code = ShareCode::get_by_code(code)?;
+    Ok(Json(share_info(&auth, share_code)?))
+}
+
+#[get("/song/<song_id>")]
+pub fn get_song_share_handler(
+    auth: TokenAuth,
+    song_id: i32,
+) -> Result<Json<ShareInfo>, ApiResponse> {
+    let share_code = ShareCode::get_by_target(ShareTarget::Song(song_id))?;
+    Ok(Json(share_info(&auth, share_code)?))
+}
+
+/// Gives the song a new code, the old one stops working
+#[post("/song/<song_id>")]
+pub fn regenerate_song_share_handler(
+    auth: TokenAuth,
+    song_id: i32,
+) -> Result<Json<ShareInfo>, ApiResponse> {
+    let target = ShareTarget::Song(song_id);
+    check_manage(&auth, target)?;
+    let share_code = ShareCode::generate(target)?;
+    Ok(Json(share_info(&auth, share_code)?))
+}
+
+#[delete("/song/<song_id>")]
+pub fn revoke_song_share_handler(auth: TokenAuth, song_id: i32) -> Result<(), ApiResponse> {
+    let target = ShareTarget::Song(song_id);
+    check_manage(&auth, target)?;
+    ShareCode::revoke(target)?;
+    Ok(())
+}
+
+#[get("/playlist/<playlist_id>")]
+pub fn get_playlist_share_handler(
+    auth: TokenAuth,
+    playlist_id: i32,
+) -> Result<Json<ShareInfo>, ApiResponse> {
+    let share_code = ShareCode::get_by_target(ShareTarget::Playlist(playlist_id))?;
+    Ok(Json(share_info(&auth, share_code)?))
+}
+
+/// Gives the playlist a new code, the old one stops working
+#[post("/playlist/<playlist_id>")]
+pub fn regenerate_playlist_share_handler(
+    auth: TokenAuth,
+    playlist_id: i32,
+) -> Result<Json<ShareInfo>, ApiResponse> {
+    let target = ShareTarget::Playlist(playlist_id);
+    check_manage(&auth, target)?;
+    let share_code = ShareCode::generate(target)?;
+    Ok(Json(share_info(&auth, share_code)?))
+}
+
+#[delete("/playlist/<playlist_id>")]
+pub fn revoke_playlist_share_handler(auth: TokenAuth, playlist_id: i32) -> Result<(), ApiResponse> {
+    let target = ShareTarget::Playlist(playlist_id);
+    check_manage(&auth, target)?;
+    ShareCode::revoke(target)?;
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::get;
+use rocket_ws::{
+    result::Result as SocketResult, stream::DuplexStream, Channel, Message, WebSocket,
+};
+
+use crate::api_response::ApiResponse;
+use crate::guards::rate_limit::{RateLimit, Stream};
+use crate::guards::stream_auth::StreamAuth;
+use crate::model::socket_message::{ClientMessage, ServerMessage};
+use crate::utils::player::{Loaded, Player};
+use crate::utils::states::StreamState;
+
+// A CC:Tweaked speaker accepts at most 128 * 1024 samples at once,
+// which is exactly what 16 KiB of DFPWM decodes to
+const MAX_CHUNK_SIZE: usize = 16 * 1024;
+const DEFAULT_WINDOW: usize = 2;
+const MAX_WINDOW: usize = 8;
+
+/// The playback state of a single socket connection
+struct SocketPlayer {
+    auth: StreamAuth,
+    streams: StreamState,
+    player: Option<Player>,
+    credits: usize,
+    window: usize,
+    chunk_size: usize,
+}
+
+impl SocketPlayer {
+    fn new(auth: StreamAuth, streams: StreamState) -> Self {
+        Self {
+            auth,
+            streams,
+            player: None,
+            credits: 0,
+            window: DEFAULT_WINDOW,
+            chunk_size: MAX_CHUNK_SIZE,
+        }
+    }
+
+    async fn send(stream: &mut DuplexStream, message: &ServerMessage) -> SocketResult<()> {
+        let text = serde_json::to_string(message).expect("Server messages always serialize");
+        stream.send(Message::Text(text)).await
+    }
+
+    async fn handle(
+        &mut self,
+        stream: &mut DuplexStream,
+        message: ClientMessage,
+    ) -> SocketResult<()> {
+        if let ClientMessage::Play {
+            source,
+            window,
+            chunk_size,
+            crossfade_ms,
+        } = message
+        {
+            match source.into_stream_queue(&self.auth).await {
+                Ok(queue) => {
+                    self.window = window.unwrap_or(DEFAULT_WINDOW).clamp(1, MAX_WINDOW);
+                    self.chunk_size = chunk_size
+                        .unwrap_or(MAX_CHUNK_SIZE)
+                        .clamp(1, MAX_CHUNK_SIZE);
+                    self.credits = self.window;
+                    self.player = Some(match crossfade_ms {
+                        Some(crossfade_ms) => Player::with_crossfade(queue, crossfade_ms),
+                        None => Player::new(queue),
+                    });
+                }
+                Err(error) => {
+                    let message = ServerMessage::Error {
+                        message: error.message().to_string(),
+                    };
+                    Self::send(stream, &message).await?;
+                }
+            }
+
+            return Ok(());
+        }
+
+        if let ClientMessage::Ack { count } = message {
+            self.credits = self
+                .credits
+                .saturating_add(count.unwrap_or(1))
+                .min(self.window);
+            return Ok(());
+        }
+
+        let Some(player) = &mut self.player else {
+            return Ok(());
+        };
+
+        match message {
+            ClientMessage::Pause => {
+                player.pause();
+                Self::send(stream, &ServerMessage::Paused).await?;
+            }
+            ClientMessage::Resume => {
+                player.resume();
+                Self::send(stream, &ServerMessage::Resumed).await?;
+            }
+            ClientMessage::Seek {
+                offset,
+                position_ms,
+            } => {
+                let encoding = player
+                    .queue()
+                    .current()
+                    .map(|song| song.encoding)
+                    .unwrap_or_default();
+                match (offset, position_ms) {
+                    (Some(offset), _) => player.seek(offset),
+                    (None, Some(position_ms)) => player.seek(encoding.byte_offset(position_ms)),
+                    (None, None) => {}
+                }
+            }
+            ClientMessage::Skip => player.skip(),
+            ClientMessage::Previous => player.previous(),
+            ClientMessage::Stop => self.player = None,
+            ClientMessage::Play { .. } | ClientMessage::Ack { .. } => {}
+        }
+
+        Ok(())
+    }
+
+    /// Sends audio until the client runs out of credits or playback stops.
+    /// Track changes and the end of the queue are announced without using a credit
+    async fn pump(&mut self, stream: &mut DuplexStream) -> SocketResult<()> {
+        while let Some(player) = &mut self.player {
+            if player.queue().is_paused() {
+                break;
+            }
+
+            match player.load(&self.streams).await {
+                Ok(Loaded::Finished) => {
+                    self.player = None;
+                    Self::send(stream, &ServerMessage::End).await?;
+                    break;
+                }
+                Ok(Loaded::New) => {
+                    let queue = player.queue();
+                    let (Some(song), Some(current)) = (queue.current(), player.current()) else {
+                        continue;
+                    };
+
+                    let message = ServerMessage::Track {
+                        index: queue.index(),
+                        size: current.data.len(),
+                        offset: queue.offset(),
+                        duration_ms: song.encoding.duration_ms(current.data.len()),
+                        song: song.clone(),
+                    };
+                    Self::send(stream, &message).await?;
+                }
+                Ok(Loaded::Current) => {}
+                Err((song, error)) => {
+                    // Skip tracks whose audio is missing instead of ending playback
+                    let message = ServerMessage::Error {
+                        message: format!(
+                            "Could not open song {}: {}",
+                            song.id,
+                            ApiResponse::from(error).message()
+                        ),
+                    };
+                    Self::send(stream, &message).await?;
+                    continue;
+                }
+            }
+
+            if self.credits == 0 {
+                break;
+            }
+
+            if let Some(chunk) = player.take(self.chunk_size) {
+                self.credits -= 1;
+                stream
+                    .send(Message::Binary(chunk.as_ref().to_vec()))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[get("/socket")]
+pub fn stream_socket(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
+    ws: WebSocket,
+    state: &rocket::State<StreamState>,
+) -> Channel<'static> {
+    let streams = state.inner().clone();
+
+    ws.channel(move |mut stream| {
+        Box::pin(async move {
+            let mut player = SocketPlayer::new(auth, streams);
+
+            while let Some(message) = stream.next().await {
+                let text = match message? {
+                    Message::Text(text) => text,
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+
+                match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(message) => player.handle(&mut stream, message).await?,
+                    Err(error) => {
+                        let message = ServerMessage::Error {
+                            message: format!("Invalid message: {error}"),
+                        };
+                        SocketPlayer::send(&mut stream, &message).await?;
+                    }
+                }
+
+                player.pump(&mut stream).await?;
+            }
+
+            Ok(())
+        })
+    })
+}
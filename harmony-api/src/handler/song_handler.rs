@@ -1,12 +1,27 @@
+use chrono::Utc;
+use harmony_core::core::model::quota::{Quota, QuotaUsage};
+use harmony_core::core::model::role::Permission;
 use harmony_core::core::model::song::Song;
-// use application::post::read;
-use rocket::{get, post, serde::json::Json};
+use harmony_core::result::ServerError;
+use harmony_core::utils::byte_size;
+use rocket::{delete, get, post, serde::json::Json};
 use tokio::task;
 
-use crate::{api_response::ApiResponse, guards::token_auth::TokenAuth, model::new_song::NewSong};
+use crate::{
+    api_response::ApiResponse,
+    guards::{
+        permission_auth::{AddSongs, PermissionAuth},
+        rate_limit::{Ingest, RateLimit, Search},
+        token_auth::TokenAuth,
+    },
+    model::new_song::NewSong,
+};
 
 #[get("/")]
-pub fn list_song_handler(_auth: TokenAuth) -> Result<Json<Vec<Song>>, ApiResponse> {
+pub fn list_song_handler(
+    _limit: RateLimit<Search>,
+    _auth: TokenAuth,
+) -> Result<Json<Vec<Song>>, ApiResponse> {
     let songs: Vec<Song> = Song::get_list().map_err(ApiResponse::from)?;
     Ok(Json(songs))
 }
@@ -20,23 +35,107 @@ pub fn get_song_handler(_auth: TokenAuth, song_id: i32) -> Result<Json<Song>, Ap
 }
 
 #[get("/find/<query>")]
-pub fn find_song_handler(_auth: TokenAuth, query: &str) -> Result<Json<Vec<Song>>, ApiResponse> {
-    let songs: Vec<Song> = Song::find(&query).map_err(ApiResponse::from)?;
+pub fn find_song_handler(
+    _limit: RateLimit<Search>,
+    _auth: TokenAuth,
+    query: &str,
+) -> Result<Json<Vec<Song>>, ApiResponse> {
+    let songs: Vec<Song> = Song::find(query).map_err(ApiResponse::from)?;
     Ok(Json(songs))
 }
 
 #[post("/", format = "application/json", data = "<song>")]
 pub async fn create_song_handler(
-    auth: TokenAuth,
+    _limit: RateLimit<Ingest>,
+    auth: PermissionAuth<AddSongs>,
     song: Json<NewSong>,
 ) -> Result<Json<Song>, ApiResponse> {
     let template = song.0.to_template(auth.user.id)?;
+    let quota = Quota::for_user(&auth.user);
+    let user_id = auth.user.id;
 
     // Spawn a blocking task for the create operation
-    let blocking_task = task::spawn_blocking(move || template.create());
+    let blocking_task = task::spawn_blocking(move || -> Result<Song, ApiResponse> {
+        if quota == Quota::default() {
+            return Ok(template.create(None)?);
+        }
+
+        // Turns the request away before downloading, the limit itself is held when adding
+        check_quota(&quota.usage(user_id)?)?;
+        let song = match template.create(quota.songs_per_day) {
+            // Other requests of the user took the last songs of the day while this one downloaded
+            Err(ServerError::LimitReached) => {
+                check_quota(&quota.usage(user_id)?)?;
+                return Err(ServerError::LimitReached.into());
+            }
+            result => result?,
+        };
+
+        // Someone else may have been adding the same url at the same time, then the song
+        // is theirs and counts against their quota, not this user's
+        if song.added_by != Some(user_id) {
+            return Ok(song);
+        }
+
+        // How big a song is only shows once it is downloaded, one that does not fit is removed again
+        let usage = quota.usage(user_id)?;
+        if usage.storage_exceeded() {
+            match song.remove() {
+                // Another request of the same user got the song too and already removed it
+                Ok(()) | Err(ServerError::NotFound) => {}
+                Err(error) => return Err(error.into()),
+            }
+            return Err(storage_full(&usage));
+        }
+
+        Ok(song)
+    });
 
     // Await the result of the blocking task
     let song = blocking_task.await.map_err(ApiResponse::from)??;
 
     Ok(Json(song))
 }
+
+/// Removes a song from the server, only the user who added it and roles that may
+/// delete any song can
+#[delete("/id/<song_id>")]
+pub fn remove_song_handler(auth: TokenAuth, song_id: i32) -> Result<(), ApiResponse> {
+    let song = Song::get_by_id(song_id).map_err(ApiResponse::from)?;
+    auth.require_owner(
+        song.added_by,
+        Permission::DeleteAnySong,
+        "Not the user who added this song",
+    )?;
+
+    song.remove().map_err(ApiResponse::from)
+}
+
+// Turns the song away when the user added their share for the day or filled their storage
+fn check_quota(usage: &QuotaUsage) -> Result<(), ApiResponse> {
+    if !usage.songs_left() {
+        let wait = usage
+            .next_song_freed
+            .map_or(0, |freed| (freed - Utc::now()).num_seconds().max(1));
+        return Err(ApiResponse::TooManyRequests(
+            format!(
+                "Daily quota of {} songs reached, try again in {wait} seconds",
+                usage.quota.songs_per_day.unwrap_or(0)
+            ),
+            u64::try_from(wait).unwrap_or(0),
+        ));
+    }
+
+    if !usage.storage_left() {
+        return Err(storage_full(usage));
+    }
+
+    Ok(())
+}
+
+fn storage_full(usage: &QuotaUsage) -> ApiResponse {
+    ApiResponse::BadRequest(format!(
+        "Storage quota of {} reached, remove songs to make room",
+        byte_size::format(usage.quota.storage.unwrap_or(0))
+    ))
+}
@@ -0,0 +1,74 @@
+use harmony_core::core::model::station::Station;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+use tokio::task;
+
+use crate::api_response::ApiResponse;
+use crate::guards::permission_auth::{ManageStations, PermissionAuth};
+use crate::guards::token_auth::TokenAuth;
+use crate::model::new_station::NewStation;
+use crate::model::station_info::StationInfo;
+use crate::model::timeline_info::TimelineInfo;
+use crate::utils::auth_util::{self, TicketScope};
+use crate::utils::clock;
+use crate::utils::states::StationState;
+
+#[get("/")]
+pub fn list_station_handler(_auth: TokenAuth) -> Result<Json<Vec<Station>>, ApiResponse> {
+    let stations = Station::get_list().map_err(ApiResponse::from)?;
+    Ok(Json(stations))
+}
+
+/// Describes what the station is playing. With `ticket` the response includes a stream ticket
+/// for the tracks of the station, so a client can tune in with a single request
+#[get("/<station_id>?<ticket>")]
+pub async fn get_station_handler(
+    auth: TokenAuth,
+    station_id: i32,
+    ticket: Option<bool>,
+    stations: &rocket::State<StationState>,
+) -> Result<Json<StationInfo>, ApiResponse> {
+    let station = Station::get_by_id(station_id).map_err(ApiResponse::from)?;
+    let live = stations.tune(&station).await.map_err(ApiResponse::from)?;
+
+    let timeline = TimelineInfo::new(&live.lock(), clock::now_ms());
+
+    let ticket = if ticket.unwrap_or(false) {
+        let ticket = auth_util::get_stream_ticket(&auth.user, TicketScope::Station(station.id))?;
+        Some(auth_util::encode_stream_ticket(&ticket)?)
+    } else {
+        None
+    };
+
+    Ok(Json(StationInfo {
+        station,
+        program: live.program,
+        timeline,
+        ticket,
+    }))
+}
+
+#[post("/", format = "application/json", data = "<station>")]
+pub async fn create_station_handler(
+    auth: PermissionAuth<ManageStations>,
+    station: Json<NewStation>,
+) -> Result<Json<Station>, ApiResponse> {
+    let template = station.0.to_template(auth.user.id)?;
+
+    let blocking_task = task::spawn_blocking(move || template.create());
+    let station = blocking_task.await.map_err(ApiResponse::from)??;
+
+    Ok(Json(station))
+}
+
+#[delete("/<station_id>")]
+pub async fn remove_station_handler(
+    _auth: PermissionAuth<ManageStations>,
+    station_id: i32,
+    stations: &rocket::State<StationState>,
+) -> Result<(), ApiResponse> {
+    let station = Station::get_by_id(station_id).map_err(ApiResponse::from)?;
+    station.remove().map_err(ApiResponse::from)?;
+    stations.remove(station_id).await;
+    Ok(())
+}
@@ -1,68 +1,126 @@
-use harmony_core::utils::song_file;
-use rocket::http::ContentType;
+use harmony_core::core::model::song::Song;
 use rocket::serde::json::Json;
 use rocket::{get, post};
-use std::time::{Duration, Instant};
+use tokio::task;
 
 use crate::api_response::ApiResponse;
-use crate::utils::environment;
+use crate::guards::rate_limit::{RateLimit, Stream};
+use crate::guards::{stream_auth::StreamAuth, token_auth::TokenAuth};
+use crate::model::playback_source::PlaybackSource;
+use crate::model::ranged_file::RangedFile;
+use crate::model::stream_chunk::StreamChunk;
+use crate::model::stream_info::StreamInfo;
+use crate::model::ticket_response::TicketResponse;
+use crate::utils::auth_util::{self, TicketScope};
+use crate::utils::position_util::resolve_offset;
 use crate::utils::states::StreamState;
 
-#[post("/open/<file_id>")]
+#[post("/open/<file_id>?<position_ms>&<position_seconds>")]
 pub async fn open_stream(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
     file_id: &str,
+    position_ms: Option<u64>,
+    position_seconds: Option<f64>,
     state: &rocket::State<StreamState>,
-) -> Result<Json<usize>, ApiResponse> {
-    let mut file_data_map = state.file_data.write().await;
-    let id_str = file_id.to_string();
+) -> Result<Json<StreamInfo>, ApiResponse> {
+    auth.check(file_id).await?;
+    let stream = state.open(file_id).await.map_err(ApiResponse::from)?;
+    let offset = resolve_offset(None, position_ms, position_seconds, &stream.encoding)?;
 
-    // Check if the file stream already exists
-    if let Some((data, expiration_time)) = file_data_map.get_mut(&id_str) {
-        // Update the expiration time
-        *expiration_time = Instant::now() + Duration::from_secs(environment::get_stream_lifetime());
-        Ok(Json(data.len()))
-    } else {
-        // File stream does not exist, read the file data
-        let file_data: Vec<u8> = song_file::get_song_data(file_id).map_err(ApiResponse::from)?;
-        let size = file_data.len();
-
-        let expiration_time =
-            Instant::now() + Duration::from_secs(environment::get_stream_lifetime()); // 5 minutes TTL
-        file_data_map.insert(id_str, (file_data, expiration_time));
-
-        Ok(Json(size))
-    }
+    Ok(Json(StreamInfo {
+        size: stream.data.len(),
+        duration_ms: stream.encoding.duration_ms(stream.data.len()),
+        offset: offset.unwrap_or(0),
+        encoding: stream.encoding,
+    }))
 }
 
-#[get("/read/<file_id>?<start>&<length>")]
+#[allow(clippy::too_many_arguments)]
+#[get("/read/<file_id>?<start>&<length>&<start_ms>&<length_ms>&<start_seconds>&<length_seconds>")]
 pub async fn read_stream(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
     file_id: &str,
     start: Option<usize>,
     length: Option<usize>,
+    start_ms: Option<u64>,
+    length_ms: Option<u64>,
+    start_seconds: Option<f64>,
+    length_seconds: Option<f64>,
     state: &rocket::State<StreamState>,
-) -> Result<(ContentType, Vec<u8>), ApiResponse> {
-    let mut file_data_map = state.file_data.write().await;
-    let Some((file_data, expiration_time)) = file_data_map.get_mut(&file_id.to_string()) else {
+) -> Result<StreamChunk, ApiResponse> {
+    auth.check(file_id).await?;
+    let Some(stream) = state.get(file_id).await else {
         return Err(ApiResponse::NotFound(
             "Could not find open stream".to_string(),
         ));
     };
 
-    // Reset expiration time
-    *expiration_time = Instant::now() + Duration::from_secs(environment::get_stream_lifetime());
-
-    let start = start.unwrap_or(0);
+    let size = stream.data.len();
+    let start = resolve_offset(start, start_ms, start_seconds, &stream.encoding)?.unwrap_or(0);
+    let length = resolve_offset(length, length_ms, length_seconds, &stream.encoding)?;
 
-    if start >= file_data.len() {
+    if start >= size {
         return Err(ApiResponse::BadRequest("Range not satisfiable".to_string()));
     }
 
     let end = match length {
-        Some(length) => start.saturating_add(length).min(file_data.len()),
-        None => file_data.len(),
+        Some(length) => start.saturating_add(length).min(size),
+        None => size,
     };
 
-    let chunk = file_data[start..end].to_vec();
+    Ok(StreamChunk::new(stream.data.clone(), start, end))
+}
+
+#[get("/file/<file_id>")]
+pub async fn get_stream_file(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
+    file_id: &str,
+    state: &rocket::State<StreamState>,
+) -> Result<RangedFile, ApiResponse> {
+    auth.check(file_id).await?;
+    let stream = state.open(file_id).await.map_err(ApiResponse::from)?;
+    Ok(RangedFile::new(file_id, stream.data.clone()))
+}
+
+#[get("/song/<song_id>")]
+pub async fn get_stream_song(
+    _limit: RateLimit<Stream>,
+    auth: StreamAuth,
+    song_id: i32,
+    state: &rocket::State<StreamState>,
+) -> Result<RangedFile, ApiResponse> {
+    let blocking_task = task::spawn_blocking(move || Song::get_by_id(song_id));
+    let song = blocking_task
+        .await
+        .map_err(ApiResponse::from)?
+        .map_err(ApiResponse::from)?;
+    auth.check(&song.file_id).await?;
+    let stream = state.open_song(&song).await.map_err(ApiResponse::from)?;
+    Ok(RangedFile::new(&song.file_id, stream.data.clone()))
+}
+
+#[post("/ticket", format = "application/json", data = "<source>")]
+pub fn create_ticket_handler(
+    auth: TokenAuth,
+    source: Json<PlaybackSource>,
+) -> Result<Json<TicketResponse>, ApiResponse> {
+    let queue = source.0.to_queue(Some(auth.user.id))?;
+    let mut files: Vec<String> = queue
+        .tracks()
+        .iter()
+        .map(|song| song.file_id.clone())
+        .collect();
+    files.sort();
+    files.dedup();
+
+    let ticket = auth_util::get_stream_ticket(&auth.user, TicketScope::Source(source.0))?;
 
-    Ok((ContentType::Binary, chunk))
+    Ok(Json(TicketResponse {
+        ticket: auth_util::encode_stream_ticket(&ticket)?,
+        files,
+        expires: ticket.exp,
+    }))
 }
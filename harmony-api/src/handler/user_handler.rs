@@ -1,15 +1,45 @@
-use harmony_core::core::model::user::User;
-use rocket::{get, post, serde::json::Json};
+use chrono::{DateTime, TimeDelta, Utc};
+use harmony_core::core::model::auth_session::AuthSession;
+use harmony_core::core::model::login_lockout::{LockoutScope, LoginLockout};
+use harmony_core::core::model::quota::{Quota, QuotaUsage};
+use harmony_core::core::model::registration_mode::RegistrationMode;
+use harmony_core::core::model::role::Role;
+use harmony_core::core::model::user::{User, UserDetails};
+use harmony_core::core::template::new_user::UserTemplate;
+use harmony_core::core::validation::validated_types::{Password, Username};
+use harmony_core::result::{AuthError, ServerError};
+use rocket::{delete, get, post, serde::json::Json, State};
+use std::net::IpAddr;
 
 use crate::{
     api_response::ApiResponse,
-    guards::token_auth::TokenAuth,
-    model::{login_response::LoginResponse, new_user::NewUser},
-    utils::auth_util,
+    guards::{
+        permission_auth::{ManageUsers, PermissionAuth},
+        rate_limit::{RateLimit, Search},
+        token_auth::TokenAuth,
+    },
+    model::{
+        account_removal::{AccountRemoval, OwnedPlaylists},
+        login_response::LoginResponse,
+        new_name::NewName,
+        new_user::NewUser,
+        password_change::PasswordChange,
+        refresh_token::RefreshToken,
+        suspension::Suspension,
+        temporary_password::TemporaryPassword,
+        user_role::UserRole,
+    },
+    utils::{auth_util, clock, environment, login_throttle::Lockout, states::LoginState},
 };
 
+// Enough to see what happened lately, older lockouts stay in the database
+const LOCKOUT_LIST_LENGTH: i64 = 100;
+
 #[get("/")]
-pub fn list_users_handler(_auth: TokenAuth) -> Result<Json<Vec<User>>, ApiResponse> {
+pub fn list_users_handler(
+    _limit: RateLimit<Search>,
+    _auth: TokenAuth,
+) -> Result<Json<Vec<User>>, ApiResponse> {
     let users: Vec<User> = User::get_list().map_err(ApiResponse::from)?;
     Ok(Json(users))
 }
@@ -23,23 +53,382 @@ pub fn get_user_handler(_auth: TokenAuth, user_id: i32) -> Result<Json<User>, Ap
 }
 
 #[get("/name/<username>")]
-pub fn find_user_handler(_auth: TokenAuth, username: &str) -> Result<Json<Vec<User>>, ApiResponse> {
-    let users: Vec<User> = User::find_by_name(&username).map_err(ApiResponse::from)?;
+pub fn find_user_handler(
+    _limit: RateLimit<Search>,
+    _auth: TokenAuth,
+    username: &str,
+) -> Result<Json<Vec<User>>, ApiResponse> {
+    let users: Vec<User> = User::find_by_name(username).map_err(ApiResponse::from)?;
     Ok(Json(users))
 }
 
+/// Logs in, failed attempts for a name or from an address have to wait longer each time
+/// until they are locked out for a while
 #[post("/login", format = "application/json", data = "<user>")]
-pub fn login_user_handler(user: Json<NewUser>) -> Result<Json<LoginResponse>, ApiResponse> {
+pub fn login_user_handler(
+    user: Json<NewUser>,
+    address: Option<IpAddr>,
+    login_state: &State<LoginState>,
+) -> Result<Json<LoginResponse>, ApiResponse> {
+    let now = clock::now_ms();
+    if let Some(wait) = login_state.wait(user.name(), address, now) {
+        return Err(too_many_logins(wait));
+    }
+
     let template = user.0.to_template()?;
-    let user_id = template.verify_auth().map_err(ApiResponse::from)?;
+    let user_id = match template.verify_auth() {
+        Ok(user_id) => user_id,
+        Err(error @ (AuthError::UserNotFound | AuthError::InvalidPassword)) => {
+            let (account, address_lockout) = login_state.fail(user.name(), address, now);
+            if let Some(lockout) = account {
+                record_lockout(LockoutScope::Account, Some(user.name()), address, lockout);
+            }
+            if let Some(lockout) = address_lockout {
+                record_lockout(LockoutScope::Address, None, address, lockout);
+            }
+            return Err(ApiResponse::from(error));
+        }
+        Err(error) => return Err(ApiResponse::from(error)),
+    };
+
+    login_state.succeed(user.name());
     let user = User::get_by_id(user_id).map_err(ApiResponse::from)?;
-    let token = auth_util::get_jwt_token(&user)?;
-    Ok(Json(LoginResponse { token, user }))
+    let (session, refresh_token) =
+        AuthSession::start(user.id, session_lifetime()).map_err(ApiResponse::from)?;
+    Ok(Json(login_response(user, &session, refresh_token)?))
+}
+
+/// Trades a refresh token for a new access and refresh token.
+/// Each refresh token works once, using one again ends its session
+#[post("/refresh", format = "application/json", data = "<refresh>")]
+pub fn refresh_user_handler(
+    refresh: Json<RefreshToken>,
+) -> Result<Json<LoginResponse>, ApiResponse> {
+    let (session, refresh_token) = AuthSession::refresh(&refresh.refresh_token, session_lifetime())
+        .map_err(|error| match error {
+            ServerError::NotFound => {
+                ApiResponse::Unauthorized("Invalid or expired refresh token".to_string())
+            }
+            error => ApiResponse::from(error),
+        })?;
+    let user = User::get_by_id(session.user_id).map_err(ApiResponse::from)?;
+    auth_util::check_active(&user)?;
+    Ok(Json(login_response(user, &session, refresh_token)?))
+}
+
+/// Ends the session the request was made with
+#[post("/logout")]
+pub fn logout_user_handler(auth: TokenAuth) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    if let Some(session) = &auth.session {
+        AuthSession::remove(session).map_err(ApiResponse::from)?;
+    }
+    Ok(())
 }
 
+/// Ends every session of the user, logging out all of their devices.
+/// Device tokens are left alone, they are revoked one by one at `/device`
+#[post("/logout/all")]
+pub fn logout_all_user_handler(auth: TokenAuth) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    AuthSession::remove_all(auth.user.id).map_err(ApiResponse::from)?;
+    Ok(())
+}
+
+/// Registers a user as far as the registration mode allows,
+/// accounts that need approval are returned as pending
 #[post("/", format = "application/json", data = "<user>")]
 pub fn create_user_handler(user: Json<NewUser>) -> Result<Json<User>, ApiResponse> {
     let template = user.0.to_template()?;
-    let user = template.create().map_err(ApiResponse::from)?;
+
+    let result = match (environment::get_registration_mode(), user.invite()) {
+        (RegistrationMode::Closed, _) => {
+            return Err(ApiResponse::Unauthorized(
+                "Registration is closed".to_string(),
+            ))
+        }
+        (RegistrationMode::Open, _) => template.create(),
+        (RegistrationMode::Invite | RegistrationMode::Approval, Some(code)) => {
+            template.create_with_invite(code)
+        }
+        (RegistrationMode::Invite, None) => {
+            return Err(ApiResponse::Unauthorized(
+                "An invite code is needed to register".to_string(),
+            ))
+        }
+        (RegistrationMode::Approval, None) => template.create_pending(),
+    };
+
+    let user = result.map_err(|error| match error {
+        ServerError::NotFound => {
+            ApiResponse::BadRequest("Invite code is invalid, used up or expired".to_string())
+        }
+        error => ApiResponse::from(error),
+    })?;
+    Ok(Json(user))
+}
+
+/// Changes the password of the user, other devices are logged out
+#[post("/me/password", format = "application/json", data = "<change>")]
+pub fn change_password_handler(
+    auth: TokenAuth,
+    change: Json<PasswordChange>,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    verify_password(&auth, &change.old_password)?;
+
+    let new_password = Password::new(&change.new_password)?;
+    User::set_password(auth.user.id, &new_password).map_err(ApiResponse::from)?;
+
+    if let Some(session) = &auth.session {
+        AuthSession::remove_others(auth.user.id, session).map_err(ApiResponse::from)?;
+    }
+    Ok(())
+}
+
+/// Shows how much of their quota the user has used
+#[get("/me/quota")]
+pub fn get_own_quota_handler(auth: TokenAuth) -> Result<Json<QuotaUsage>, ApiResponse> {
+    let usage = Quota::for_user(&auth.user)
+        .usage(auth.user.id)
+        .map_err(ApiResponse::from)?;
+    Ok(Json(usage))
+}
+
+#[post("/me/name", format = "application/json", data = "<new_name>")]
+pub fn rename_user_handler(
+    auth: TokenAuth,
+    new_name: Json<NewName>,
+) -> Result<Json<User>, ApiResponse> {
+    auth.require_login()?;
+    let name = Username::new(&new_name.name)?;
+    let user = User::rename(auth.user.id, &name).map_err(|error| match error {
+        ServerError::AlreadyExists => ApiResponse::Conflict("Name is already taken".to_string()),
+        error => ApiResponse::from(error),
+    })?;
+    Ok(Json(user))
+}
+
+/// Deletes the account of the user, their playlists are deleted or handed to someone else.
+/// Songs they added stay on the server
+#[delete("/me", format = "application/json", data = "<removal>")]
+pub fn remove_own_user_handler(
+    auth: TokenAuth,
+    removal: Json<AccountRemoval>,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    verify_password(&auth, &removal.password)?;
+
+    if auth.user.is_last_admin().map_err(ApiResponse::from)? {
+        return Err(ApiResponse::BadRequest(
+            "Make someone else an admin before deleting the last admin account".to_string(),
+        ));
+    }
+
+    match removal.owned_playlists {
+        OwnedPlaylists::Delete => User::remove(auth.user.id).map_err(ApiResponse::from),
+        OwnedPlaylists::TransferTo(new_owner) => {
+            if new_owner == auth.user.id {
+                return Err(ApiResponse::BadRequest(
+                    "Playlists can't be transferred to the account being deleted".to_string(),
+                ));
+            }
+
+            User::get_by_id(new_owner).map_err(|error| match error {
+                ServerError::NotFound => ApiResponse::BadRequest(
+                    "Could not find the user to transfer the playlists to".to_string(),
+                ),
+                error => ApiResponse::from(error),
+            })?;
+            User::remove_transferring(auth.user.id, new_owner).map_err(ApiResponse::from)
+        }
+    }
+}
+
+/// Gives a user another role
+#[post("/id/<user_id>/role", format = "application/json", data = "<role>")]
+pub fn set_user_role_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+    role: Json<UserRole>,
+) -> Result<Json<User>, ApiResponse> {
+    auth.require_login()?;
+    let role = Role::parse(&role.role).map_err(ApiResponse::from)?;
+
+    require_other(&auth, user_id)?;
+    let user = User::set_role(user_id, role).map_err(ApiResponse::from)?;
+    Ok(Json(user))
+}
+
+/// Lists every user with their suspension and how much they added
+#[get("/details")]
+pub fn list_user_details_handler(
+    _auth: PermissionAuth<ManageUsers>,
+) -> Result<Json<Vec<UserDetails>>, ApiResponse> {
+    let users = User::get_details_list().map_err(ApiResponse::from)?;
+    Ok(Json(users))
+}
+
+/// Lists the accounts waiting for approval, oldest first
+#[get("/pending")]
+pub fn list_pending_users_handler(
+    _auth: PermissionAuth<ManageUsers>,
+) -> Result<Json<Vec<User>>, ApiResponse> {
+    let users = User::get_pending().map_err(ApiResponse::from)?;
+    Ok(Json(users))
+}
+
+#[post("/id/<user_id>/approve")]
+pub fn approve_user_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+) -> Result<Json<User>, ApiResponse> {
+    auth.require_login()?;
+    let user = User::approve(user_id).map_err(|error| match error {
+        ServerError::NotFound => {
+            ApiResponse::NotFound("No such user is waiting for approval".to_string())
+        }
+        error => ApiResponse::from(error),
+    })?;
     Ok(Json(user))
 }
+
+/// Turns down an account waiting for approval, removing it
+#[post("/id/<user_id>/reject")]
+pub fn reject_user_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    User::reject(user_id).map_err(|error| match error {
+        ServerError::NotFound => {
+            ApiResponse::NotFound("No such user is waiting for approval".to_string())
+        }
+        error => ApiResponse::from(error),
+    })
+}
+
+/// Suspends a user, they are logged out and can't log in until it is lifted
+#[post(
+    "/id/<user_id>/suspend",
+    format = "application/json",
+    data = "<suspension>"
+)]
+pub fn suspend_user_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+    suspension: Json<Suspension>,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    require_other(&auth, user_id)?;
+    User::suspend(user_id, suspension.reason.as_deref()).map_err(ApiResponse::from)
+}
+
+#[post("/id/<user_id>/unsuspend")]
+pub fn unsuspend_user_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    User::unsuspend(user_id).map_err(ApiResponse::from)
+}
+
+/// Replaces the password of a user with a temporary one to hand to them,
+/// their sessions are ended
+#[post("/id/<user_id>/reset-password")]
+pub fn reset_user_password_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+) -> Result<Json<TemporaryPassword>, ApiResponse> {
+    auth.require_login()?;
+    require_other(&auth, user_id)?;
+    let password = User::reset_password(user_id).map_err(ApiResponse::from)?;
+    Ok(Json(TemporaryPassword { password }))
+}
+
+/// Deletes a user and their playlists, songs they added stay on the server
+#[delete("/id/<user_id>")]
+pub fn remove_user_handler(
+    auth: PermissionAuth<ManageUsers>,
+    user_id: i32,
+) -> Result<(), ApiResponse> {
+    auth.require_login()?;
+    require_other(&auth, user_id)?;
+    User::remove(user_id).map_err(ApiResponse::from)
+}
+
+/// Gets the recent login lockouts, newest first
+#[get("/lockouts")]
+pub fn list_lockouts_handler(
+    auth: PermissionAuth<ManageUsers>,
+) -> Result<Json<Vec<LoginLockout>>, ApiResponse> {
+    auth.require_login()?;
+    let lockouts = LoginLockout::get_recent(LOCKOUT_LIST_LENGTH).map_err(ApiResponse::from)?;
+    Ok(Json(lockouts))
+}
+
+fn too_many_logins(wait: i64) -> ApiResponse {
+    // Rounded up, coming back a moment early would only be turned away again
+    let seconds = u64::try_from(wait).unwrap_or(0).div_ceil(1000);
+    ApiResponse::TooManyRequests(
+        format!("Too many failed logins, try again in {seconds} seconds"),
+        seconds,
+    )
+}
+
+// The login is refused either way, a lockout that could not be written down should not change that
+fn record_lockout(
+    scope: LockoutScope,
+    name: Option<&str>,
+    address: Option<IpAddr>,
+    lockout: Lockout,
+) {
+    let locked_until = DateTime::from_timestamp_millis(lockout.until).unwrap_or_else(Utc::now);
+    let _ = LoginLockout::record(
+        scope,
+        name,
+        address.map(|address| address.to_string()),
+        lockout.failures,
+        locked_until,
+    );
+}
+
+// The user is known here, so a wrong password is reported as such
+pub(crate) fn verify_password(auth: &TokenAuth, password: &str) -> Result<(), ApiResponse> {
+    match UserTemplate::new(&auth.user.name, password)?.verify_auth() {
+        Ok(_) => Ok(()),
+        Err(AuthError::InvalidPassword) => {
+            Err(ApiResponse::BadRequest("Invalid password".to_string()))
+        }
+        Err(error) => Err(ApiResponse::from(error)),
+    }
+}
+
+fn session_lifetime() -> TimeDelta {
+    let seconds = i64::try_from(environment::get_refresh_token_time_valid()).unwrap_or(i64::MAX);
+    TimeDelta::try_seconds(seconds).unwrap_or(TimeDelta::MAX)
+}
+
+fn login_response(
+    user: User,
+    session: &AuthSession,
+    refresh_token: String,
+) -> Result<LoginResponse, ApiResponse> {
+    Ok(LoginResponse {
+        token: auth_util::get_jwt_token(user.id, &session.id)?,
+        refresh_token,
+        expires_in: environment::get_token_time_valid(),
+        user,
+    })
+}
+
+/// Admins can't act on their own account here, so there is always one left to undo a mistake
+fn require_other(auth: &PermissionAuth<ManageUsers>, user_id: i32) -> Result<(), ApiResponse> {
+    if user_id == auth.user.id {
+        return Err(ApiResponse::BadRequest(
+            "You can't do this to your own account".to_string(),
+        ));
+    }
+
+    Ok(())
+}
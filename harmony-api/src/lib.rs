@@ -4,3 +4,5 @@ pub mod guards;
 pub mod handler;
 pub mod model;
 pub mod utils;
+
+mod tests;
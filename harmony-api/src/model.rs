@@ -1,4 +1,38 @@
+pub mod account_removal;
+pub mod client_version;
+pub mod email_change;
+pub mod email_status;
+pub mod email_verification;
+pub mod forgotten_password;
+pub mod jukebox_info;
 pub mod login_response;
+pub mod new_device_token;
+pub mod new_invite;
+pub mod new_jukebox;
+pub mod new_name;
 pub mod new_playlist;
+pub mod new_room;
+pub mod new_schedule;
+pub mod new_session;
 pub mod new_song;
+pub mod new_station;
 pub mod new_user;
+pub mod password_change;
+pub mod password_reset;
+pub mod playback_source;
+pub mod ranged_file;
+pub mod refresh_token;
+pub mod room_info;
+pub mod scheduled_at;
+pub mod session_chunk;
+pub mod session_info;
+pub mod share_info;
+pub mod socket_message;
+pub mod station_info;
+pub mod stream_chunk;
+pub mod stream_info;
+pub mod suspension;
+pub mod temporary_password;
+pub mod ticket_response;
+pub mod timeline_info;
+pub mod user_role;
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens to the playlists of a deleted account, either `"delete"`
+/// or `{ "transfer_to": user_id }`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnedPlaylists {
+    Delete,
+    TransferTo(i32),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountRemoval {
+    /// The current password, so a token left logged in can't delete the account
+    pub password: String,
+    pub owned_playlists: OwnedPlaylists,
+}
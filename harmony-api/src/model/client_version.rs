@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// The client version the server hands out, so installed clients can update themselves
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClientVersion {
+    pub version: String,
+    pub url: String,
+}
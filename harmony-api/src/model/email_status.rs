@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailStatus {
+    pub email: Option<String>,
+    /// When the address was verified, mail is only sent to verified addresses
+    pub verified: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,7 @@
+use rocket::FromForm;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, FromForm)]
+pub struct EmailVerification {
+    pub token: String,
+}
@@ -0,0 +1,91 @@
+use harmony_core::core::model::song::Song;
+use harmony_core::core::playback::jukebox::{Jukebox, Vote};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JukeboxVote {
+    pub vote: Vote,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayingSong {
+    pub song: Song,
+    /// `None` when the song came from the scheduled playlist
+    pub added_by: Option<i32>,
+    /// The server time the song started at, in milliseconds since the unix epoch
+    pub started_at: i64,
+    pub position_ms: u64,
+    /// The byte offset into the song at `server_time`
+    pub offset: usize,
+    pub duration_ms: u64,
+    pub skip_votes: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueuedSong {
+    pub id: u64,
+    pub song: Song,
+    pub added_by: i32,
+    pub score: i64,
+    /// The vote of the user asking
+    pub vote: Vote,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JukeboxInfo {
+    pub code: String,
+    pub owner: i32,
+    pub skip_threshold: usize,
+    /// The schedule whose playlist plays while the queue is empty
+    pub schedule: Option<i32>,
+    /// The server time the jukebox was read at, in milliseconds since the unix epoch
+    pub server_time: i64,
+    pub playing: Option<PlayingSong>,
+    /// The queued songs in the order they will play
+    pub queue: Vec<QueuedSong>,
+    /// A stream ticket for the playing and queued songs, when one was asked for
+    pub ticket: Option<String>,
+}
+
+impl JukeboxInfo {
+    /// Describes the jukebox as seen by the given user, the jukebox should be updated first
+    #[must_use]
+    pub fn new(code: &str, owner: i32, jukebox: &Jukebox, user_id: i32, now: i64) -> Self {
+        let playing = jukebox.playing().map(|playing| {
+            let position_ms = playing.position_ms(now);
+
+            PlayingSong {
+                song: playing.song.clone(),
+                added_by: playing.added_by,
+                started_at: playing.started_at,
+                position_ms,
+                offset: playing.song.encoding.byte_offset(position_ms),
+                duration_ms: playing.duration_ms,
+                skip_votes: playing.skip_votes(),
+            }
+        });
+
+        let queue = jukebox
+            .queue()
+            .iter()
+            .map(|entry| QueuedSong {
+                id: entry.id,
+                song: entry.song.clone(),
+                added_by: entry.added_by,
+                score: entry.score(),
+                vote: entry.vote_of(user_id),
+            })
+            .collect();
+
+        Self {
+            code: code.to_string(),
+            owner,
+            skip_threshold: jukebox.skip_threshold(),
+            schedule: jukebox.fallback_id(),
+            server_time: now,
+            playing,
+            queue,
+            ticket: None,
+        }
+    }
+}
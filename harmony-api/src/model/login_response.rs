@@ -4,5 +4,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginResponse {
     pub token: String,
+    /// Trade this in at `/user/refresh` for new tokens before the session expires
+    pub refresh_token: String,
+    /// Seconds until the access token expires
+    pub expires_in: u64,
     pub user: User,
 }
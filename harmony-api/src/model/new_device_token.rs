@@ -0,0 +1,25 @@
+use harmony_core::core::model::device_token::DeviceToken;
+use harmony_core::core::template::new_device_token::DeviceTokenTemplate;
+use serde::{Deserialize, Serialize};
+
+use crate::api_response::ApiResponse;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewDeviceToken {
+    pub name: String,
+    /// Any of `stream`, `read` and `write`
+    pub scopes: Vec<String>,
+}
+
+impl NewDeviceToken {
+    pub fn to_template(&self, user_id: i32) -> Result<DeviceTokenTemplate, ApiResponse> {
+        DeviceTokenTemplate::new(&self.name, &self.scopes, user_id).map_err(ApiResponse::from)
+    }
+}
+
+/// A new device token, the token itself can't be retrieved again
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeviceTokenResponse {
+    pub token: String,
+    pub device: DeviceToken,
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewInvite {
+    /// How many accounts the invite can create, unlimited when unset
+    pub max_uses: Option<i32>,
+    /// How long the invite can be used for, forever when unset
+    pub expires_in_hours: Option<u32>,
+}
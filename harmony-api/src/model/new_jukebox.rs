@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewJukebox {
+    /// How many votes it takes to skip a song, the server default when not given
+    pub skip_threshold: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewJukeboxEntry {
+    pub song: i32,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use super::playback_source::PlaybackSource;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewRoom {
+    pub source: PlaybackSource,
+    /// Start over from the first track once the last one has played
+    #[serde(default)]
+    pub repeat: bool,
+}
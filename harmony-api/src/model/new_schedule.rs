@@ -0,0 +1,47 @@
+use harmony_core::core::template::new_schedule::{ScheduleTarget, ScheduleTemplate};
+use serde::{Deserialize, Serialize};
+
+use crate::api_response::ApiResponse;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewSchedule {
+    pub name: String,
+    pub playlist: i32,
+    /// The station to play on, either this or `jukebox` must be given
+    pub station: Option<i32>,
+    /// The code of the jukebox to play on while nobody has queued anything
+    pub jukebox: Option<String>,
+    /// A cron expression for the start of each window, such as "0 20 * * Sat"
+    pub rule: String,
+    pub duration_minutes: i32,
+    /// The time zone the rule is read in, UTC when not given
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl NewSchedule {
+    pub fn target(&self) -> Result<ScheduleTarget, ApiResponse> {
+        match (self.station, &self.jukebox) {
+            (Some(station), None) => Ok(ScheduleTarget::Station(station)),
+            (None, Some(jukebox)) => Ok(ScheduleTarget::Jukebox(jukebox.clone())),
+            _ => Err(ApiResponse::BadRequest(
+                "Either a station or a jukebox must be given".to_string(),
+            )),
+        }
+    }
+
+    pub fn to_template(&self, created_by: i32) -> Result<ScheduleTemplate, ApiResponse> {
+        ScheduleTemplate::new(
+            &self.name,
+            self.playlist,
+            self.target()?,
+            &self.rule,
+            self.duration_minutes,
+            self.timezone.as_deref().unwrap_or("UTC"),
+            self.priority,
+            created_by,
+        )
+        .map_err(ApiResponse::from)
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use super::playback_source::PlaybackSource;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewSession {
+    pub source: PlaybackSource,
+    /// Renders the queue as one continuous stream with tracks fading into each other
+    pub crossfade_ms: Option<u64>,
+}
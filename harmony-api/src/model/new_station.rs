@@ -0,0 +1,20 @@
+use harmony_core::core::template::new_station::StationTemplate;
+use serde::{Deserialize, Serialize};
+
+use crate::api_response::ApiResponse;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewStation {
+    pub name: String,
+    /// The playlist to play, the whole library when not given
+    pub playlist: Option<i32>,
+    #[serde(default)]
+    pub shuffle: bool,
+}
+
+impl NewStation {
+    pub fn to_template(&self, created_by: i32) -> Result<StationTemplate, ApiResponse> {
+        StationTemplate::new(&self.name, self.playlist, self.shuffle, created_by)
+            .map_err(ApiResponse::from)
+    }
+}
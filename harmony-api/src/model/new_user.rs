@@ -7,10 +7,23 @@ use crate::api_response::ApiResponse;
 pub struct NewUser {
     name: String,
     password: String,
+    /// Lets the user register when registration is invite-only or needs approval
+    #[serde(default)]
+    invite: Option<String>,
 }
 
 impl NewUser {
     pub fn to_template(&self) -> Result<UserTemplate, ApiResponse> {
         UserTemplate::new(&self.name, &self.password).map_err(ApiResponse::from)
     }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn invite(&self) -> Option<&str> {
+        self.invite.as_deref()
+    }
 }
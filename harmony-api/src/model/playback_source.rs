@@ -0,0 +1,66 @@
+use harmony_core::core::model::{playlist::Playlist, song::Song};
+use harmony_core::core::playback::queue::PlaybackQueue;
+use serde::{Deserialize, Serialize};
+use tokio::task;
+
+use crate::api_response::ApiResponse;
+use crate::guards::stream_auth::StreamAuth;
+
+// Every song of an ad-hoc list is looked up on its own
+pub const MAX_SOURCE_SONGS: usize = 500;
+
+/// What a client wants to play, a single song, a playlist or an ad-hoc list of songs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackSource {
+    Song(i32),
+    Playlist(i32),
+    Songs(Vec<i32>),
+}
+
+impl PlaybackSource {
+    /// Resolves the source into a queue of songs.
+    /// Private playlists can only be played by their owner
+    pub fn to_queue(&self, calling_user: Option<i32>) -> Result<PlaybackQueue, ApiResponse> {
+        let tracks = match self {
+            PlaybackSource::Song(song_id) => vec![Song::get_by_id(*song_id)?],
+            PlaybackSource::Playlist(playlist_id) => {
+                let playlist = Playlist::get_by_id(*playlist_id)?;
+                if !playlist.public && Some(playlist.owner) != calling_user {
+                    return Err(ApiResponse::Unauthorized("Playlist is private".to_string()));
+                }
+
+                playlist.get_songs()?
+            }
+            PlaybackSource::Songs(song_ids) if song_ids.len() > MAX_SOURCE_SONGS => {
+                return Err(ApiResponse::BadRequest(format!(
+                    "At most {MAX_SOURCE_SONGS} songs can be played at once"
+                )));
+            }
+            PlaybackSource::Songs(song_ids) => song_ids
+                .iter()
+                .map(|song_id| Song::get_by_id(*song_id))
+                .collect::<Result<Vec<Song>, _>>()?,
+        };
+
+        Ok(PlaybackQueue::new(tracks))
+    }
+
+    /// Resolves the source for streaming on the blocking pool,
+    /// every track has to be covered by the ticket
+    pub async fn into_stream_queue(self, auth: &StreamAuth) -> Result<PlaybackQueue, ApiResponse> {
+        let user_id = auth.user_id();
+        let queue = task::spawn_blocking(move || self.to_queue(Some(user_id)))
+            .await
+            .map_err(ApiResponse::from)??;
+
+        let files: Vec<&str> = queue
+            .tracks()
+            .iter()
+            .map(|song| song.file_id.as_str())
+            .collect();
+        auth.check_all(&files).await?;
+
+        Ok(queue)
+    }
+}
@@ -0,0 +1,137 @@
+use harmony_core::utils::song_file::SongData;
+use rocket::http::{ContentType, Header, Status};
+use rocket::request::Request;
+use rocket::response::{Responder, Response};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::model::stream_chunk::StreamChunk;
+use crate::utils::range_util::{self, ByteRange, RangeRequest};
+
+/// A song file served over plain HTTP, honoring `Range` and conditional request headers
+#[derive(Debug)]
+pub struct RangedFile {
+    data: Arc<SongData>,
+    etag: String,
+}
+
+impl RangedFile {
+    #[must_use]
+    pub fn new(file_id: &str, data: Arc<SongData>) -> Self {
+        // Song files never change once converted, so the id and size identify the content
+        let etag = format!("\"{}-{:x}\"", file_id, data.len());
+        Self { data, etag }
+    }
+
+    fn not_modified(&self, request: &Request<'_>) -> bool {
+        if let Some(if_none_match) = request.headers().get_one("If-None-Match") {
+            return range_util::etag_matches(if_none_match, &self.etag);
+        }
+
+        match (
+            request.headers().get_one("If-Modified-Since"),
+            self.data.modified(),
+        ) {
+            (Some(since), Some(modified)) => httpdate::parse_http_date(since)
+                .is_ok_and(|since| truncate_to_seconds(modified) <= since),
+            _ => false,
+        }
+    }
+
+    // A range request is only honored when the validator in `If-Range` still matches
+    fn range_applies(&self, request: &Request<'_>, last_modified: Option<&str>) -> bool {
+        match request.headers().get_one("If-Range") {
+            None => true,
+            Some(if_range) if if_range.trim_start().starts_with('"') => if_range == self.etag,
+            Some(if_range) => last_modified == Some(if_range),
+        }
+    }
+
+    fn multipart_body(&self, ranges: &[ByteRange], boundary: &str) -> Vec<u8> {
+        let size = self.data.len();
+        let bytes = self.data.as_ref().as_ref();
+        let mut body = Vec::new();
+
+        for range in ranges {
+            body.extend_from_slice(
+                format!(
+                    "\r\n--{boundary}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+                    ContentType::Binary,
+                    range.content_range(size)
+                )
+                .as_bytes(),
+            );
+            body.extend_from_slice(&bytes[range.start..range.end]);
+        }
+
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        body
+    }
+}
+
+fn truncate_to_seconds(time: SystemTime) -> SystemTime {
+    httpdate::parse_http_date(&httpdate::fmt_http_date(time)).unwrap_or(time)
+}
+
+impl<'r> Responder<'r, 'static> for RangedFile {
+    fn respond_to(self, request: &'r Request<'_>) -> Result<Response<'static>, Status> {
+        let size = self.data.len();
+        let last_modified = self.data.modified().map(httpdate::fmt_http_date);
+
+        let mut response = Response::build();
+        response
+            .raw_header("Accept-Ranges", "bytes")
+            .raw_header("ETag", self.etag.clone());
+
+        if let Some(last_modified) = &last_modified {
+            response.raw_header("Last-Modified", last_modified.clone());
+        }
+
+        if self.not_modified(request) {
+            return response.status(Status::NotModified).ok();
+        }
+
+        let range = match request.headers().get_one("Range") {
+            Some(range) if self.range_applies(request, last_modified.as_deref()) => {
+                range_util::parse_range(range, size)
+            }
+            _ => RangeRequest::Full,
+        };
+
+        match range {
+            RangeRequest::Full => response
+                .header(ContentType::Binary)
+                .sized_body(size, Cursor::new(StreamChunk::new(self.data, 0, size)))
+                .ok(),
+            RangeRequest::Partial(ranges) if ranges.len() == 1 => {
+                let range = ranges[0];
+                response
+                    .status(Status::PartialContent)
+                    .header(ContentType::Binary)
+                    .raw_header("Content-Range", range.content_range(size))
+                    .sized_body(
+                        range.len(),
+                        Cursor::new(StreamChunk::new(self.data, range.start, range.end)),
+                    )
+                    .ok()
+            }
+            RangeRequest::Partial(ranges) => {
+                let boundary = nanoid::nanoid!();
+                let body = self.multipart_body(&ranges, &boundary);
+                response
+                    .status(Status::PartialContent)
+                    .header(Header::new(
+                        "Content-Type",
+                        format!("multipart/byteranges; boundary={boundary}"),
+                    ))
+                    .sized_body(body.len(), Cursor::new(body))
+                    .ok()
+            }
+            RangeRequest::Unsatisfiable => response
+                .status(Status::RangeNotSatisfiable)
+                .raw_header("Content-Range", format!("bytes */{size}"))
+                .ok(),
+        }
+    }
+}
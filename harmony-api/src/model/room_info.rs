@@ -0,0 +1,28 @@
+use harmony_core::core::playback::room::Room;
+use serde::{Deserialize, Serialize};
+
+use super::timeline_info::TimelineInfo;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoomInfo {
+    pub code: String,
+    pub host: i32,
+    pub members: usize,
+    #[serde(flatten)]
+    pub timeline: TimelineInfo,
+    /// A stream ticket covering every track of the room, only given to members
+    pub ticket: Option<String>,
+}
+
+impl RoomInfo {
+    #[must_use]
+    pub fn new(code: &str, room: &Room, now: i64) -> Self {
+        Self {
+            code: code.to_string(),
+            host: room.host(),
+            members: room.members().len(),
+            timeline: TimelineInfo::new(room.broadcast(), now),
+            ticket: None,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use harmony_core::core::model::schedule::{Schedule, ScheduleWindow};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduledWindow {
+    pub schedule: Schedule,
+    pub window: ScheduleWindow,
+}
+
+/// The schedules with a window at `time`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduledAt {
+    pub time: DateTime<Utc>,
+    /// The schedule that decides what plays, only given when asking about one station or jukebox
+    pub in_effect: Option<ScheduledWindow>,
+    /// Every schedule with a window at `time`, highest priority first
+    pub active: Vec<ScheduledWindow>,
+}
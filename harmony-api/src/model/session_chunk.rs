@@ -0,0 +1,39 @@
+use rocket::http::{ContentType, Status};
+use rocket::request::Request;
+use rocket::response::{Responder, Response};
+use std::io::Cursor;
+
+use super::session_info::PlaybackState;
+use super::stream_chunk::StreamChunk;
+
+/// The next piece of audio of a session, headers describe where it came from
+/// so clients can follow track changes without asking for the session info
+#[derive(Debug)]
+pub struct SessionChunk {
+    pub state: PlaybackState,
+    pub index: usize,
+    pub song: Option<i32>,
+    pub offset: usize,
+    /// Empty when the session is paused or finished
+    pub chunk: Option<StreamChunk>,
+}
+
+impl<'r> Responder<'r, 'static> for SessionChunk {
+    fn respond_to(self, _request: &Request<'_>) -> Result<Response<'static>, Status> {
+        let mut response = Response::build();
+        response
+            .header(ContentType::Binary)
+            .raw_header("X-Session-State", self.state.as_str())
+            .raw_header("X-Session-Index", self.index.to_string())
+            .raw_header("X-Session-Offset", self.offset.to_string());
+
+        if let Some(song) = self.song {
+            response.raw_header("X-Session-Track", song.to_string());
+        }
+
+        match self.chunk {
+            Some(chunk) => response.sized_body(chunk.len(), Cursor::new(chunk)).ok(),
+            None => response.sized_body(0, Cursor::new(Vec::new())).ok(),
+        }
+    }
+}
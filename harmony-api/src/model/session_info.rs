@@ -0,0 +1,78 @@
+use harmony_core::core::model::song::Song;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::player::Player;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackState {
+    Playing,
+    Paused,
+    Finished,
+}
+
+impl PlaybackState {
+    #[must_use]
+    pub fn of(player: &Player) -> Self {
+        if player.queue().is_finished() {
+            PlaybackState::Finished
+        } else if player.queue().is_paused() {
+            PlaybackState::Paused
+        } else {
+            PlaybackState::Playing
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PlaybackState::Playing => "playing",
+            PlaybackState::Paused => "paused",
+            PlaybackState::Finished => "finished",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub id: String,
+    pub state: PlaybackState,
+    /// The index of the current track in the queue
+    pub index: usize,
+    /// The number of tracks in the queue
+    pub tracks: usize,
+    pub track: Option<Song>,
+    pub next: Option<Song>,
+    /// The byte offset into the current track
+    pub offset: usize,
+    pub position_ms: u64,
+    /// The size of the current track, once it has been opened
+    pub size: Option<usize>,
+    pub duration_ms: Option<u64>,
+    /// Set when the session renders a continuous stream
+    pub crossfade_ms: Option<u64>,
+}
+
+impl SessionInfo {
+    #[must_use]
+    pub fn new(id: &str, player: &Player) -> Self {
+        let queue = player.queue();
+        let track = queue.current().cloned();
+        let encoding = track.as_ref().map(|song| song.encoding).unwrap_or_default();
+        let size = player.current().map(|stream| stream.data.len());
+
+        Self {
+            id: id.to_string(),
+            state: PlaybackState::of(player),
+            index: queue.index(),
+            tracks: queue.tracks().len(),
+            next: queue.next().cloned(),
+            offset: queue.offset(),
+            position_ms: encoding.duration_ms(queue.offset()),
+            size,
+            duration_ms: size.map(|size| encoding.duration_ms(size)),
+            crossfade_ms: player.crossfade_ms(),
+            track,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use harmony_core::core::model::{playlist::Playlist, song::Song};
+use serde::{Deserialize, Serialize};
+
+/// The song or playlist a share code points to
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedItem {
+    Song(Song),
+    Playlist(Playlist),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareInfo {
+    pub code: String,
+    pub created: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub item: SharedItem,
+}
@@ -0,0 +1,55 @@
+use harmony_core::core::model::song::Song;
+use serde::{Deserialize, Serialize};
+
+use super::playback_source::PlaybackSource;
+
+/// Control messages sent by a client over the stream socket
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Replaces the queue and starts playing it from the start
+    Play {
+        source: PlaybackSource,
+        /// How many chunks may be in flight before the client has to acknowledge one
+        window: Option<usize>,
+        chunk_size: Option<usize>,
+        /// Renders the queue as one continuous stream with tracks fading into each other
+        crossfade_ms: Option<u64>,
+    },
+    /// Sent when the client is ready for more audio, usually when its speaker buffer drained
+    Ack {
+        count: Option<usize>,
+    },
+    Pause,
+    Resume,
+    /// Moves to a byte offset or a time within the current track
+    Seek {
+        offset: Option<usize>,
+        position_ms: Option<u64>,
+    },
+    Skip,
+    Previous,
+    Stop,
+}
+
+/// Control messages sent by the server, audio is sent as binary messages in between
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A new track started, the following binary messages belong to it.
+    /// With a crossfade the previous message already faded into the track
+    Track {
+        index: usize,
+        song: Song,
+        size: usize,
+        offset: usize,
+        duration_ms: u64,
+    },
+    Paused,
+    Resumed,
+    /// The queue has no tracks left
+    End,
+    Error {
+        message: String,
+    },
+}
@@ -0,0 +1,15 @@
+use harmony_core::core::model::station::{Program, Station};
+use serde::{Deserialize, Serialize};
+
+use super::timeline_info::TimelineInfo;
+
+/// What a station is playing right now and what comes next
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StationInfo {
+    pub station: Station,
+    pub program: Program,
+    #[serde(flatten)]
+    pub timeline: TimelineInfo,
+    /// A stream ticket for the tracks of the station, when one was asked for
+    pub ticket: Option<String>,
+}
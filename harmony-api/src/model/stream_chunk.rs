@@ -0,0 +1,78 @@
+use harmony_core::utils::song_file::SongData;
+use rocket::http::{ContentType, Status};
+use rocket::request::Request;
+use rocket::response::{Responder, Response};
+use std::io::Cursor;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+enum ChunkData {
+    Song(Arc<SongData>),
+    Rendered(Arc<[u8]>),
+}
+
+impl AsRef<[u8]> for ChunkData {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            ChunkData::Song(data) => data.as_ref().as_ref(),
+            ChunkData::Rendered(data) => data,
+        }
+    }
+}
+
+/// A range of an open song, served straight from the mapped file without copying,
+/// or audio the server rendered itself
+#[derive(Debug, Clone)]
+pub struct StreamChunk {
+    data: ChunkData,
+    start: usize,
+    end: usize,
+}
+
+impl StreamChunk {
+    /// Creates a chunk for the range, clamping it to the song length
+    #[must_use]
+    pub fn new(data: Arc<SongData>, start: usize, end: usize) -> Self {
+        let end = end.min(data.len());
+        let start = start.min(end);
+        Self {
+            data: ChunkData::Song(data),
+            start,
+            end,
+        }
+    }
+
+    #[must_use]
+    pub fn rendered(data: Vec<u8>) -> Self {
+        Self {
+            end: data.len(),
+            data: ChunkData::Rendered(data.into()),
+            start: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl AsRef<[u8]> for StreamChunk {
+    fn as_ref(&self) -> &[u8] {
+        &self.data.as_ref()[self.start..self.end]
+    }
+}
+
+impl<'r> Responder<'r, 'static> for StreamChunk {
+    fn respond_to(self, _request: &Request<'_>) -> Result<Response<'static>, Status> {
+        Response::build()
+            .header(ContentType::Binary)
+            .sized_body(self.len(), Cursor::new(self))
+            .ok()
+    }
+}
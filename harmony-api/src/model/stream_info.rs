@@ -0,0 +1,13 @@
+use harmony_core::core::model::encoding::EncodingProfile;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreamInfo {
+    /// The size of the song in bytes
+    pub size: usize,
+    /// The playback time of the whole song
+    pub duration_ms: u64,
+    /// The byte offset of the requested position, 0 when none was given
+    pub offset: usize,
+    pub encoding: EncodingProfile,
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Suspension {
+    /// Why the account was suspended, shown to the roles that manage users
+    pub reason: Option<String>,
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+/// A password that replaced a forgotten one, it can't be retrieved again
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemporaryPassword {
+    pub password: String,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TicketResponse {
+    pub ticket: String,
+    /// The files the ticket grants access to now, it follows changes to its source
+    pub files: Vec<String>,
+    /// When the ticket expires, in seconds since the unix epoch
+    pub expires: usize,
+}
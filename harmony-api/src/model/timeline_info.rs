@@ -0,0 +1,58 @@
+use harmony_core::core::model::song::Song;
+use harmony_core::core::playback::broadcast::Broadcast;
+use serde::{Deserialize, Serialize};
+
+use super::session_info::PlaybackState;
+
+/// A shared timeline at `server_time`. Listeners play `track` from `offset` at
+/// that time, correcting for the time the response took to arrive
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimelineInfo {
+    pub state: PlaybackState,
+    /// The server time the timeline was read at, in milliseconds since the unix epoch
+    pub server_time: i64,
+    /// The index of the current track in the queue
+    pub index: usize,
+    /// The number of tracks in the queue
+    pub tracks: usize,
+    pub track: Option<Song>,
+    pub next: Option<Song>,
+    /// The byte offset into the current track
+    pub offset: usize,
+    pub position_ms: u64,
+    pub duration_ms: Option<u64>,
+    /// The server time the current track started at, shifted by any pauses
+    pub track_started_at: Option<i64>,
+}
+
+impl TimelineInfo {
+    #[must_use]
+    pub fn new(broadcast: &Broadcast, now: i64) -> Self {
+        let timeline = broadcast.timeline();
+        let position = broadcast.position(now);
+        let index = position.map_or(broadcast.tracks().len(), |position| position.index);
+        let track = broadcast.tracks().get(index).cloned();
+        let position_ms = position.map_or(0, |position| position.position_ms);
+
+        let state = match position {
+            None => PlaybackState::Finished,
+            Some(_) if timeline.is_paused() => PlaybackState::Paused,
+            Some(_) => PlaybackState::Playing,
+        };
+
+        Self {
+            state,
+            server_time: now,
+            index,
+            tracks: broadcast.tracks().len(),
+            next: broadcast.next(position).cloned(),
+            offset: track
+                .as_ref()
+                .map_or(0, |song| song.encoding.byte_offset(position_ms)),
+            position_ms,
+            duration_ms: position.map(|position| timeline.durations()[position.index]),
+            track_started_at: position.map(|position| position.track_started_at),
+            track,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserRole {
+    /// One of `listener`, `contributor`, `moderator` and `admin`
+    pub role: String,
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod login_throttle;
+pub mod playback_source;
+pub mod range;
+pub mod rate_limit;
@@ -0,0 +1,19 @@
+#[cfg(test)]
+use crate::utils::client::render_client;
+
+#[test]
+fn test_render_client() {
+    let script = render_client("http://music.example.com:8000/", "1.2.3");
+    assert!(script.contains("local BASE_URL = \"http://music.example.com:8000\""));
+    assert!(script.contains("local VERSION = \"1.2.3\""));
+    assert!(script.contains("wget http://music.example.com:8000/client/harmony.lua harmony"));
+    assert!(!script.contains("{{"));
+}
+
+#[test]
+fn test_render_client_escapes() {
+    let script = render_client("http://x\"..os.exit()..\"\\\nprint(1)", "1.2.3");
+    assert!(script.contains(r#"local BASE_URL = "http://x\"..os.exit()..\"\\\010print(1)""#));
+    assert!(script.contains(r#"wget http://x\"..os.exit()..\"\\\010print(1)/client/harmony.lua"#));
+    assert!(!script.contains("\nprint(1)"));
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+use crate::utils::login_throttle::{Lockout, Throttle, ThrottlePolicy};
+
+#[cfg(test)]
+const POLICY: ThrottlePolicy = ThrottlePolicy {
+    free_failures: 3,
+    base_delay: 1000,
+    max_delay: 8000,
+    lockout_failures: 10,
+    lockout: 900_000,
+};
+
+#[test]
+fn test_throttle_delay() {
+    assert_eq!(POLICY.delay(0), 0);
+    assert_eq!(POLICY.delay(3), 0);
+    assert_eq!(POLICY.delay(4), 1000);
+    assert_eq!(POLICY.delay(5), 2000);
+    assert_eq!(POLICY.delay(6), 4000);
+    assert_eq!(POLICY.delay(7), 8000);
+    assert_eq!(POLICY.delay(9), 8000);
+    assert_eq!(POLICY.delay(u32::MAX), 8000);
+}
+
+#[test]
+fn test_throttle_backoff() {
+    let mut throttle = Throttle::new(POLICY);
+
+    for _ in 0..3 {
+        assert_eq!(throttle.fail("bob", 0), None);
+        assert_eq!(throttle.wait(&"bob", 0), None);
+    }
+
+    assert_eq!(throttle.fail("bob", 0), None);
+    assert_eq!(throttle.wait(&"bob", 0), Some(1000));
+    assert_eq!(throttle.wait(&"bob", 400), Some(600));
+    assert_eq!(throttle.wait(&"bob", 1000), None);
+
+    assert_eq!(throttle.fail("bob", 1000), None);
+    assert_eq!(throttle.wait(&"bob", 1000), Some(2000));
+
+    // Others are not slowed down by it
+    assert_eq!(throttle.wait(&"alice", 1000), None);
+
+    throttle.succeed(&"bob");
+    assert_eq!(throttle.wait(&"bob", 1000), None);
+}
+
+#[test]
+fn test_throttle_lockout() {
+    let mut throttle = Throttle::new(POLICY);
+
+    for _ in 0..9 {
+        assert_eq!(throttle.fail("bob", 0), None);
+    }
+    assert_eq!(
+        throttle.fail("bob", 0),
+        Some(Lockout {
+            failures: 10,
+            until: 900_000
+        })
+    );
+    assert_eq!(throttle.wait(&"bob", 100_000), Some(800_000));
+
+    // Failures during a lockout don't start another one
+    assert_eq!(throttle.fail("bob", 100_000), None);
+
+    // Once it ends, counting starts over
+    assert_eq!(throttle.wait(&"bob", 900_000), None);
+    assert_eq!(throttle.fail("bob", 900_000), None);
+    assert_eq!(throttle.wait(&"bob", 900_000), None);
+}
+
+#[test]
+fn test_throttle_forgets() {
+    let mut throttle = Throttle::new(POLICY);
+
+    for _ in 0..5 {
+        throttle.fail("bob", 0);
+    }
+    throttle.prune(899_999);
+    assert_eq!(throttle.wait(&"bob", 0), Some(2000));
+
+    throttle.prune(900_000);
+    assert_eq!(throttle.wait(&"bob", 0), None);
+
+    // Old failures are forgotten when failing again too
+    for _ in 0..5 {
+        throttle.fail("alice", 0);
+    }
+    throttle.fail("alice", 900_000);
+    assert_eq!(throttle.wait(&"alice", 900_000), None);
+}
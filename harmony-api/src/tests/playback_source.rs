@@ -0,0 +1,9 @@
+#[cfg(test)]
+use crate::model::playback_source::{PlaybackSource, MAX_SOURCE_SONGS};
+
+#[test]
+fn test_too_many_songs() {
+    // Rejected before any song is looked up
+    let source = PlaybackSource::Songs(vec![1; MAX_SOURCE_SONGS + 1]);
+    assert!(source.to_queue(Some(1)).is_err());
+}
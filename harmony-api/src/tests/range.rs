@@ -0,0 +1,124 @@
+#[cfg(test)]
+use crate::utils::range_util::{etag_matches, parse_range, ByteRange, RangeRequest};
+
+#[test]
+fn test_single_ranges() {
+    let range = parse_range("bytes=0-99", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange { start: 0, end: 100 }])
+    );
+
+    let range = parse_range("bytes=900-", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange {
+            start: 900,
+            end: 1000
+        }])
+    );
+
+    let range = parse_range("bytes=-100", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange {
+            start: 900,
+            end: 1000
+        }])
+    );
+
+    let range = parse_range("bytes=500-5000", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange {
+            start: 500,
+            end: 1000
+        }])
+    );
+
+    let range = parse_range("bytes=-5000", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange {
+            start: 0,
+            end: 1000
+        }])
+    );
+}
+
+#[test]
+fn test_multiple_ranges() {
+    let range = parse_range("bytes=500-599, 0-99", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![
+            ByteRange { start: 0, end: 100 },
+            ByteRange {
+                start: 500,
+                end: 600
+            }
+        ])
+    );
+
+    // Overlapping and adjacent ranges are merged
+    let range = parse_range("bytes=0-99,50-149,150-199", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange { start: 0, end: 200 }])
+    );
+
+    // Unsatisfiable ranges are dropped when another range can be served
+    let range = parse_range("bytes=0-99,2000-2100", 1000);
+    assert_eq!(
+        range,
+        RangeRequest::Partial(vec![ByteRange { start: 0, end: 100 }])
+    );
+}
+
+#[test]
+fn test_unsatisfiable_ranges() {
+    let range = parse_range("bytes=1000-", 1000);
+    assert_eq!(range, RangeRequest::Unsatisfiable);
+
+    let range = parse_range("bytes=1000-1100,2000-2100", 1000);
+    assert_eq!(range, RangeRequest::Unsatisfiable);
+
+    let range = parse_range("bytes=-0", 1000);
+    assert_eq!(range, RangeRequest::Unsatisfiable);
+
+    let range = parse_range("bytes=0-", 0);
+    assert_eq!(range, RangeRequest::Unsatisfiable);
+}
+
+#[test]
+fn test_invalid_ranges() {
+    let range = parse_range("items=0-99", 1000);
+    assert_eq!(range, RangeRequest::Full);
+
+    let range = parse_range("bytes=99-0", 1000);
+    assert_eq!(range, RangeRequest::Full);
+
+    let range = parse_range("bytes=abc-def", 1000);
+    assert_eq!(range, RangeRequest::Full);
+
+    let range = parse_range("bytes=-", 1000);
+    assert_eq!(range, RangeRequest::Full);
+
+    let range = parse_range("bytes=", 1000);
+    assert_eq!(range, RangeRequest::Full);
+}
+
+#[test]
+fn test_content_range() {
+    let range = ByteRange { start: 0, end: 100 };
+    assert_eq!(range.content_range(1000), "bytes 0-99/1000");
+}
+
+#[test]
+fn test_etag_matches() {
+    assert!(etag_matches("\"abc-10\"", "\"abc-10\""));
+    assert!(etag_matches("\"other\", \"abc-10\"", "\"abc-10\""));
+    assert!(etag_matches("W/\"abc-10\"", "\"abc-10\""));
+    assert!(etag_matches("*", "\"abc-10\""));
+    assert!(!etag_matches("\"abc-11\"", "\"abc-10\""));
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+use crate::utils::rate_limit::RateLimiter;
+
+#[test]
+fn test_rate_limit_burst() {
+    let mut limiter = RateLimiter::new();
+
+    for _ in 0..60 {
+        assert_eq!(limiter.take("bob", 60, 0), Ok(()));
+    }
+    assert_eq!(limiter.take("bob", 60, 0), Err(1000));
+    assert_eq!(limiter.take("bob", 60, 400), Err(600));
+
+    // Others have their own allowance
+    assert_eq!(limiter.take("alice", 60, 0), Ok(()));
+}
+
+#[test]
+fn test_rate_limit_refills() {
+    let mut limiter = RateLimiter::new();
+
+    for _ in 0..10 {
+        limiter.take("bob", 10, 0).unwrap();
+    }
+
+    // One request comes back every six seconds
+    assert_eq!(limiter.take("bob", 10, 6000), Ok(()));
+    assert_eq!(limiter.take("bob", 10, 6000), Err(6000));
+    assert_eq!(limiter.take("bob", 10, 12_000), Ok(()));
+
+    // After a quiet minute the whole allowance is back
+    for _ in 0..10 {
+        assert_eq!(limiter.take("bob", 10, 72_000), Ok(()));
+    }
+    assert!(limiter.take("bob", 10, 72_000).is_err());
+}
+
+#[test]
+fn test_rate_limit_off() {
+    let mut limiter = RateLimiter::new();
+
+    for _ in 0..1000 {
+        assert_eq!(limiter.take("bob", 0, 0), Ok(()));
+    }
+}
+
+#[test]
+fn test_rate_limit_prune() {
+    let mut limiter = RateLimiter::new();
+
+    for _ in 0..10 {
+        limiter.take("bob", 10, 0).unwrap();
+    }
+    limiter.prune(59_999);
+    assert!(limiter.take("bob", 10, 0).is_err());
+
+    limiter.prune(60_000);
+    assert_eq!(limiter.take("bob", 10, 0), Ok(()));
+}
+
+#[test]
+fn test_rate_limit_all_keys() {
+    let mut limiter = RateLimiter::new();
+
+    for _ in 0..10 {
+        limiter.take("address", 10, 0).unwrap();
+    }
+
+    // A full key turns the request away without counting it against the others
+    assert_eq!(
+        limiter.take_all(vec![("token", 10), ("address", 10)], 0),
+        Err(6000)
+    );
+    for _ in 0..10 {
+        assert_eq!(limiter.take("token", 10, 0), Ok(()));
+    }
+
+    assert_eq!(
+        limiter.take_all(vec![("other", 10), ("other address", 100)], 0),
+        Ok(())
+    );
+    assert_eq!(limiter.take_all(vec![("address", 0)], 0), Ok(()));
+}
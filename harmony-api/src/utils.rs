@@ -1,3 +1,10 @@
 pub mod auth_util;
+pub mod client;
+pub mod clock;
 pub mod environment;
+pub mod login_throttle;
+pub mod player;
+pub mod position_util;
+pub mod range_util;
+pub mod rate_limit;
 pub mod states;
@@ -1,27 +1,58 @@
+use harmony_core::core::model::auth_session::AuthSession;
+use harmony_core::core::model::device_token::{DeviceToken, Scope, DEVICE_TOKEN_PREFIX};
 use harmony_core::core::model::user::User;
+use harmony_core::result::ServerError;
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::api_response::ApiResponse;
+use crate::model::playback_source::PlaybackSource;
 
 use super::environment;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
-    user: User, // User ID
-    exp: usize, // Expiration time
+    sub: i32,    // User ID
+    sid: String, // The login session the token belongs to
+    exp: usize,  // Expiration time
 }
 
-pub fn get_jwt_token(user: &User) -> Result<String, ApiResponse> {
+// Tickets are signed with the same secret as login tokens, the audience
+// keeps one from being accepted in place of the other
+const TICKET_AUDIENCE: &str = "stream";
+
+/// What a stream ticket grants access to. Only the source goes in the ticket, so it stays
+/// short for any size of playlist, its files are looked up when one is streamed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketScope {
+    Source(PlaybackSource),
+    Room(String),
+    Jukebox(String),
+    Station(i32),
+}
+
+/// Grants access to stream the songs of a source until it expires
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamTicket {
+    pub sub: i32,           // The user the ticket was issued to
+    pub scope: TicketScope, // Where the songs that may be streamed come from
+    pub aud: String,
+    pub exp: usize,
+}
+
+/// Creates a short-lived access token for a login session
+pub fn get_jwt_token(user_id: i32, session_id: &str) -> Result<String, ApiResponse> {
     let expiration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
         .as_secs()
-        + environment::get_token_time_valid(); // 1 week expiration
+        + environment::get_token_time_valid();
 
     let claims = Claims {
-        user: user.clone(),
+        sub: user_id,
+        sid: session_id.to_string(),
         exp: expiration as usize,
     };
 
@@ -37,7 +68,9 @@ pub fn get_jwt_token(user: &User) -> Result<String, ApiResponse> {
     }
 }
 
-pub fn get_user_from_jwt(token: &str) -> Result<User, ApiResponse> {
+/// Checks an access token and that its session has not been ended,
+/// returning the user with the id of the session
+pub fn get_user_from_jwt(token: &str) -> Result<(User, String), ApiResponse> {
     let validation = Validation::default();
     let token_data: TokenData<Claims> = decode(
         token,
@@ -51,8 +84,125 @@ pub fn get_user_from_jwt(token: &str) -> Result<User, ApiResponse> {
         _ => ApiResponse::Unauthorized("Invalid token".to_string()),
     })?;
 
-    // Extract the user from the token's claims
-    let user = token_data.claims.user;
+    let claims = token_data.claims;
+    let session = AuthSession::get_active(&claims.sid).map_err(|error| match error {
+        ServerError::NotFound => ApiResponse::Unauthorized("Session has ended".to_string()),
+        error => ApiResponse::from(error),
+    })?;
+
+    if session.user_id != claims.sub {
+        return Err(ApiResponse::Unauthorized("Invalid token".to_string()));
+    }
+
+    // The user is loaded fresh, so changes to it apply before the token expires
+    let user = User::get_by_id(claims.sub).map_err(|error| match error {
+        ServerError::NotFound => ApiResponse::Unauthorized("Invalid token".to_string()),
+        error => ApiResponse::from(error),
+    })?;
+
+    check_active(&user)?;
+    Ok((user, session.id))
+}
+
+/// Whether the server handed out the token and still accepts it, without loading the user.
+/// Enough for rate limiting, which only has to know the token is not made up
+#[must_use]
+pub fn is_issued_token(token: &str) -> bool {
+    if token.starts_with(DEVICE_TOKEN_PREFIX) {
+        return DeviceToken::find(token).is_ok();
+    }
+
+    let key = DecodingKey::from_secret(environment::get_jwt_secret().as_bytes());
+    if decode::<Claims>(token, &key, &Validation::default()).is_ok() {
+        return true;
+    }
+
+    let mut validation = Validation::default();
+    validation.set_audience(&[TICKET_AUDIENCE]);
+    decode::<StreamTicket>(token, &key, &validation).is_ok()
+}
+
+/// # Errors
+/// When the user is suspended
+pub fn check_active(user: &User) -> Result<(), ApiResponse> {
+    match user.suspended {
+        Some(_) => Err(ApiResponse::Unauthorized(
+            "Account is suspended".to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks a device token and its scope, returning the user it belongs to
+pub fn get_user_from_device_token(
+    token: &str,
+    scope: Scope,
+) -> Result<(User, DeviceToken), ApiResponse> {
+    let device = DeviceToken::authenticate(token).map_err(|error| match error {
+        ServerError::NotFound => ApiResponse::Unauthorized("Invalid token".to_string()),
+        error => ApiResponse::from(error),
+    })?;
+
+    if !device.allows(scope) {
+        return Err(ApiResponse::Unauthorized(format!(
+            "Device token lacks the {} scope",
+            scope.as_str()
+        )));
+    }
+
+    let user = User::get_by_id(device.user_id)?;
+    check_active(&user)?;
+    Ok((user, device))
+}
+
+pub fn get_stream_ticket(user: &User, scope: TicketScope) -> Result<StreamTicket, ApiResponse> {
+    let expiration = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+        + environment::get_stream_ticket_time_valid();
+
+    Ok(StreamTicket {
+        sub: user.id,
+        scope,
+        aud: TICKET_AUDIENCE.to_string(),
+        exp: expiration as usize,
+    })
+}
+
+pub fn encode_stream_ticket(ticket: &StreamTicket) -> Result<String, ApiResponse> {
+    encode(
+        &Header::default(),
+        ticket,
+        &EncodingKey::from_secret(environment::get_jwt_secret().as_bytes()),
+    )
+    .map_err(|_| ApiResponse::InternalServerError("Could not create ticket".to_string()))
+}
+
+/// Checks a stream ticket and that the user it was issued to may still stream
+pub fn decode_stream_ticket(token: &str) -> Result<StreamTicket, ApiResponse> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[TICKET_AUDIENCE]);
+
+    let token_data: TokenData<StreamTicket> = decode(
+        token,
+        &DecodingKey::from_secret(environment::get_jwt_secret().as_bytes()),
+        &validation,
+    )
+    .map_err(|err| match *err.kind() {
+        jsonwebtoken::errors::ErrorKind::ExpiredSignature => {
+            ApiResponse::Unauthorized("Ticket has expired".to_string())
+        }
+        _ => ApiResponse::Unauthorized("Invalid ticket".to_string()),
+    })?;
+
+    // Like login tokens, a ticket stops working once its user is suspended or deleted
+    let ticket = token_data.claims;
+    let user = User::get_by_id(ticket.sub).map_err(|error| match error {
+        ServerError::NotFound => ApiResponse::Unauthorized("Invalid ticket".to_string()),
+        error => ApiResponse::from(error),
+    })?;
 
-    Ok(user)
+    check_active(&user)?;
+    Ok(ticket)
 }
@@ -0,0 +1,47 @@
+use rocket::http::uri::Host;
+
+use crate::api_response::ApiResponse;
+
+use super::environment;
+
+/// The ComputerCraft client, with placeholders for the values of the serving server
+const CLIENT_TEMPLATE: &str = include_str!("../../client/harmony.lua");
+
+/// Fills in the client template, so the script talks to the server it was downloaded from
+#[must_use]
+pub fn render_client(base_url: &str, version: &str) -> String {
+    CLIENT_TEMPLATE
+        .replace("{{base_url}}", &lua_escape(base_url.trim_end_matches('/')))
+        .replace("{{version}}", &lua_escape(version))
+}
+
+// The base url comes from the Host header of whoever asks, so it must not be able to end
+// the string or comment it is put in and have the script run code of its own
+fn lua_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            c if c.is_control() => escaped.push_str(&format!("\\{:03}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The address clients should use, `PUBLIC_URL` when set and otherwise the host they connected to
+/// # Errors
+/// When `PUBLIC_URL` is not set and the request has no `Host` header
+pub fn base_url(host: Option<&Host<'_>>) -> Result<String, ApiResponse> {
+    if let Some(url) = environment::get_public_url() {
+        return Ok(url.trim_end_matches('/').to_string());
+    }
+
+    match host {
+        Some(host) => Ok(format!("http://{host}")),
+        None => Err(ApiResponse::BadRequest(
+            "Missing Host header, set PUBLIC_URL on the server".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,11 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The server time in milliseconds since the unix epoch, shared timelines are expressed in it
+#[must_use]
+pub fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| {
+            i64::try_from(time.as_millis()).unwrap_or(i64::MAX)
+        })
+}
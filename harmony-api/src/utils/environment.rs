@@ -1,4 +1,5 @@
 use dotenvy::dotenv;
+use harmony_core::core::model::registration_mode::RegistrationMode;
 use std::env;
 
 fn init_environment() {
@@ -22,6 +23,16 @@ pub fn get_token_time_valid() -> u64 {
         .expect("JWT_TIME_VALID must be a valid positive number")
 }
 
+/// How long a login session can go without being refreshed in seconds, defaults to 30 days
+#[must_use]
+pub fn get_refresh_token_time_valid() -> u64 {
+    init_environment();
+    env::var("REFRESH_TOKEN_TIME_VALID").map_or(2_592_000, |time| {
+        time.parse::<u64>()
+            .expect("REFRESH_TOKEN_TIME_VALID must be a valid positive number")
+    })
+}
+
 #[must_use]
 pub fn get_stream_lifetime() -> u64 {
     init_environment();
@@ -31,6 +42,190 @@ pub fn get_stream_lifetime() -> u64 {
         .expect("STREAM_LIFETIME must be a valid positive number")
 }
 
+/// How long a playback session lives in seconds after no requests have been made to it,
+/// defaults to 5 minutes
+#[must_use]
+pub fn get_session_lifetime() -> u64 {
+    init_environment();
+    env::var("SESSION_LIFETIME").map_or(300, |time| {
+        time.parse::<u64>()
+            .expect("SESSION_LIFETIME must be a valid positive number")
+    })
+}
+
+/// How many playback sessions a user can have open at once, defaults to 8.
+/// Opening another closes their oldest one
+#[must_use]
+pub fn get_max_sessions_per_user() -> usize {
+    init_environment();
+    env::var("MAX_SESSIONS_PER_USER").map_or(8, |max| {
+        max.parse::<usize>()
+            .expect("MAX_SESSIONS_PER_USER must be a valid positive number")
+    })
+}
+
+/// How long a room lives in seconds after no requests have been made to it,
+/// defaults to 1 hour
+#[must_use]
+pub fn get_room_lifetime() -> u64 {
+    init_environment();
+    env::var("ROOM_LIFETIME").map_or(3600, |time| {
+        time.parse::<u64>()
+            .expect("ROOM_LIFETIME must be a valid positive number")
+    })
+}
+
+/// How long a jukebox lives in seconds after no requests have been made to it,
+/// defaults to 1 hour
+#[must_use]
+pub fn get_jukebox_lifetime() -> u64 {
+    init_environment();
+    env::var("JUKEBOX_LIFETIME").map_or(3600, |time| {
+        time.parse::<u64>()
+            .expect("JUKEBOX_LIFETIME must be a valid positive number")
+    })
+}
+
+/// How many votes it takes to skip a jukebox song when its creator did not choose,
+/// defaults to 3
+#[must_use]
+pub fn get_jukebox_skip_threshold() -> usize {
+    init_environment();
+    env::var("JUKEBOX_SKIP_THRESHOLD").map_or(3, |votes| {
+        votes
+            .parse::<usize>()
+            .expect("JUKEBOX_SKIP_THRESHOLD must be a valid positive number")
+    })
+}
+
+/// How long a stream ticket is valid in seconds, defaults to 30 minutes
+#[must_use]
+pub fn get_stream_ticket_time_valid() -> u64 {
+    init_environment();
+    env::var("STREAM_TICKET_TIME_VALID").map_or(1800, |time| {
+        time.parse::<u64>()
+            .expect("STREAM_TICKET_TIME_VALID must be a valid positive number")
+    })
+}
+
+/// How many failed logins a name gets before it is locked out, defaults to 10.
+/// A third of them can be made right away, the ones after that have to wait longer each time
+#[must_use]
+pub fn get_login_max_failures() -> u32 {
+    init_environment();
+    env::var("LOGIN_MAX_FAILURES").map_or(10, |failures| {
+        failures
+            .parse::<u32>()
+            .expect("LOGIN_MAX_FAILURES must be a valid positive number")
+    })
+}
+
+/// How many failed logins an address gets before it is locked out, defaults to 50
+#[must_use]
+pub fn get_login_max_address_failures() -> u32 {
+    init_environment();
+    env::var("LOGIN_MAX_ADDRESS_FAILURES").map_or(50, |failures| {
+        failures
+            .parse::<u32>()
+            .expect("LOGIN_MAX_ADDRESS_FAILURES must be a valid positive number")
+    })
+}
+
+/// How long a login lockout lasts in seconds, defaults to 15 minutes
+#[must_use]
+pub fn get_login_lockout_time() -> u64 {
+    init_environment();
+    env::var("LOGIN_LOCKOUT_TIME").map_or(900, |time| {
+        time.parse::<u64>()
+            .expect("LOGIN_LOCKOUT_TIME must be a valid positive number")
+    })
+}
+
+/// How many stream and session requests a client may make a minute, defaults to 600.
+/// 0 turns the limit off
+#[must_use]
+pub fn get_stream_rate_limit() -> u32 {
+    init_environment();
+    env::var("STREAM_RATE_LIMIT").map_or(600, |limit| {
+        limit
+            .parse::<u32>()
+            .expect("STREAM_RATE_LIMIT must be a valid positive number")
+    })
+}
+
+/// How many list and search requests a client may make a minute, defaults to 120.
+/// 0 turns the limit off
+#[must_use]
+pub fn get_search_rate_limit() -> u32 {
+    init_environment();
+    env::var("SEARCH_RATE_LIMIT").map_or(120, |limit| {
+        limit
+            .parse::<u32>()
+            .expect("SEARCH_RATE_LIMIT must be a valid positive number")
+    })
+}
+
+/// How many songs a client may ask to add a minute, defaults to 5.
+/// 0 turns the limit off
+#[must_use]
+pub fn get_ingest_rate_limit() -> u32 {
+    init_environment();
+    env::var("INGEST_RATE_LIMIT").map_or(5, |limit| {
+        limit
+            .parse::<u32>()
+            .expect("INGEST_RATE_LIMIT must be a valid positive number")
+    })
+}
+
+/// How many mail sending requests a client may make a minute, defaults to 3.
+/// 0 turns the limit off
+#[must_use]
+pub fn get_mail_rate_limit() -> u32 {
+    init_environment();
+    env::var("MAIL_RATE_LIMIT").map_or(3, |limit| {
+        limit
+            .parse::<u32>()
+            .expect("MAIL_RATE_LIMIT must be a valid positive number")
+    })
+}
+
+/// Who may create an account, defaults to anyone
+#[must_use]
+pub fn get_registration_mode() -> RegistrationMode {
+    init_environment();
+    env::var("REGISTRATION_MODE").map_or(RegistrationMode::Open, |mode| {
+        RegistrationMode::parse(&mode)
+            .expect("REGISTRATION_MODE must be open, invite, approval or closed")
+    })
+}
+
+/// How many times the limit of a token the tokens behind one address may use together,
+/// defaults to 10. In-game computers all connect from the address of the game server
+#[must_use]
+pub fn get_address_rate_limit_factor() -> u32 {
+    init_environment();
+    env::var("ADDRESS_RATE_LIMIT_FACTOR").map_or(10, |factor| {
+        factor
+            .parse::<u32>()
+            .expect("ADDRESS_RATE_LIMIT_FACTOR must be a valid positive number")
+    })
+}
+
+/// The header a reverse proxy in front of the server puts the client address in.
+/// Unset means there is no proxy, the header could be set by anyone then
+#[must_use]
+pub fn get_proxy_ip_header() -> Option<String> {
+    init_environment();
+    env::var("PROXY_IP_HEADER").ok()
+}
+
+/// The address clients reach the server at, when it differs from the `Host` they connect with
+#[must_use]
+pub fn get_public_url() -> Option<String> {
+    init_environment();
+    env::var("PUBLIC_URL").ok()
+}
+
 #[must_use]
 pub fn get_version() -> String {
     init_environment();
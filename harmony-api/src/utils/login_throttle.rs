@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How failed logins are slowed down, times are in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottlePolicy {
+    /// Failures that can be made without having to wait
+    pub free_failures: u32,
+    /// The wait after the first failure past the free ones, doubling with each one after it
+    pub base_delay: i64,
+    pub max_delay: i64,
+    /// Failures after which logging in is locked entirely
+    pub lockout_failures: u32,
+    /// How long a lockout lasts, failures older than this are forgotten too
+    pub lockout: i64,
+}
+
+impl ThrottlePolicy {
+    /// How long to wait after the given number of failures
+    #[must_use]
+    pub fn delay(&self, failures: u32) -> i64 {
+        if failures <= self.free_failures {
+            return 0;
+        }
+
+        let doublings = (failures - self.free_failures - 1).min(32);
+        self.base_delay
+            .saturating_mul(1_i64 << doublings)
+            .min(self.max_delay)
+    }
+}
+
+/// A lockout that was just started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lockout {
+    pub failures: u32,
+    pub until: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Failures {
+    count: u32,
+    last: i64,
+    locked_until: Option<i64>,
+}
+
+/// Counts failed logins per key, such as a name or an address, and decides how long it has to wait
+#[derive(Debug)]
+pub struct Throttle<K> {
+    policy: ThrottlePolicy,
+    failures: HashMap<K, Failures>,
+}
+
+impl<K: Eq + Hash> Throttle<K> {
+    #[must_use]
+    pub fn new(policy: ThrottlePolicy) -> Self {
+        Self {
+            policy,
+            failures: HashMap::new(),
+        }
+    }
+
+    /// How long the key has to wait before it may try again, if it has to
+    #[must_use]
+    pub fn wait(&self, key: &K, now: i64) -> Option<i64> {
+        let failures = self.failures.get(key)?;
+        let until = failures
+            .locked_until
+            .unwrap_or(failures.last + self.policy.delay(failures.count));
+
+        (until > now).then_some(until - now)
+    }
+
+    /// Counts a failed login, returning the lockout when this failure started one
+    pub fn fail(&mut self, key: K, now: i64) -> Option<Lockout> {
+        let policy = self.policy;
+        let failures = self.failures.entry(key).or_insert(Failures {
+            count: 0,
+            last: now,
+            locked_until: None,
+        });
+
+        if failures.is_stale(&policy, now) {
+            *failures = Failures {
+                count: 0,
+                last: now,
+                locked_until: None,
+            };
+        }
+
+        failures.count += 1;
+        failures.last = now;
+
+        // Logins that were let through before the lockout began don't start another one
+        if failures.count < policy.lockout_failures || failures.locked_until.is_some() {
+            return None;
+        }
+
+        let until = now + policy.lockout;
+        failures.locked_until = Some(until);
+        Some(Lockout {
+            failures: failures.count,
+            until,
+        })
+    }
+
+    /// Forgets the failures of the key, after it logged in
+    pub fn succeed(&mut self, key: &K) {
+        self.failures.remove(key);
+    }
+
+    /// Forgets the keys whose lockout ended or that have not failed in a while
+    pub fn prune(&mut self, now: i64) {
+        let policy = self.policy;
+        self.failures
+            .retain(|_, failures| !failures.is_stale(&policy, now));
+    }
+}
+
+impl Failures {
+    fn is_stale(&self, policy: &ThrottlePolicy, now: i64) -> bool {
+        match self.locked_until {
+            Some(until) => until <= now,
+            None => now - self.last >= policy.lockout,
+        }
+    }
+}
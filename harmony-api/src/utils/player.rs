@@ -0,0 +1,229 @@
+use harmony_core::core::model::song::Song;
+use harmony_core::core::playback::mixer::Mixer;
+use harmony_core::core::playback::queue::PlaybackQueue;
+use harmony_core::result::ServerError;
+use harmony_core::utils::song_file::SongData;
+use std::sync::Arc;
+
+use super::states::{OpenStream, StreamState};
+use crate::model::stream_chunk::StreamChunk;
+
+#[derive(Debug)]
+pub enum Loaded {
+    /// A new track was opened
+    New,
+    /// The current track is open and has audio left
+    Current,
+    /// The queue has no tracks left
+    Finished,
+}
+
+/// The longest crossfade a player accepts
+pub const MAX_CROSSFADE_MS: u64 = 10_000;
+
+/// Plays a queue by keeping the stream of the current track open.
+/// With a crossfade the player renders one continuous stream instead,
+/// opening each next track ahead of time so it can be mixed in
+#[derive(Debug)]
+pub struct Player {
+    queue: PlaybackQueue,
+    current: Option<Arc<OpenStream>>,
+    crossfade_ms: Option<u64>,
+    next: Option<Arc<OpenStream>>,
+    mixer: Option<Mixer<Arc<SongData>>>,
+    track_changed: bool,
+}
+
+impl Player {
+    #[must_use]
+    pub fn new(queue: PlaybackQueue) -> Self {
+        Self {
+            queue,
+            current: None,
+            crossfade_ms: None,
+            next: None,
+            mixer: None,
+            track_changed: false,
+        }
+    }
+
+    /// Creates a player that renders the queue as one stream, fading tracks into each other.
+    /// A crossfade of zero joins the tracks without a gap
+    #[must_use]
+    pub fn with_crossfade(queue: PlaybackQueue, crossfade_ms: u64) -> Self {
+        Self {
+            crossfade_ms: Some(crossfade_ms.min(MAX_CROSSFADE_MS)),
+            ..Self::new(queue)
+        }
+    }
+
+    #[must_use]
+    pub fn queue(&self) -> &PlaybackQueue {
+        &self.queue
+    }
+
+    #[must_use]
+    pub fn crossfade_ms(&self) -> Option<u64> {
+        self.crossfade_ms
+    }
+
+    /// The stream of the current track, if it has been loaded
+    #[must_use]
+    pub fn current(&self) -> Option<&Arc<OpenStream>> {
+        self.current.as_ref()
+    }
+
+    /// Makes sure the current track is open, moving past tracks that have been played to the end.
+    /// # Errors
+    /// When the audio of the current track could not be opened, the track is skipped
+    /// and the song is returned so the caller can report it
+    pub async fn load(&mut self, streams: &StreamState) -> Result<Loaded, (Song, ServerError)> {
+        let mut opened = false;
+
+        loop {
+            let Some(song) = self.queue.current() else {
+                self.current = None;
+                return Ok(Loaded::Finished);
+            };
+
+            match &self.current {
+                Some(stream) if self.queue.offset() < stream.data.len() => {
+                    let changed = std::mem::take(&mut self.track_changed);
+                    if self.crossfade_ms.is_some() {
+                        self.prepare_mixer(streams).await;
+                    }
+
+                    return Ok(if opened || changed {
+                        Loaded::New
+                    } else {
+                        Loaded::Current
+                    });
+                }
+                Some(_) => {
+                    self.queue.skip();
+                    self.current = None;
+                    self.reset_mixer();
+                    opened = false;
+                }
+                None => match streams.open_song(song).await {
+                    Ok(stream) => {
+                        self.current = Some(stream);
+                        opened = true;
+                    }
+                    Err(error) => {
+                        let song = song.clone();
+                        self.queue.skip();
+                        return Err((song, error));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Starts the mixer at the cursor and opens the next track so it can be faded in.
+    /// Tracks in a different encoding can't be mixed, they start once the current one ended
+    async fn prepare_mixer(&mut self, streams: &StreamState) {
+        let (Some(crossfade_ms), Some(current)) = (self.crossfade_ms, &self.current) else {
+            return;
+        };
+
+        let mixer = self.mixer.get_or_insert_with(|| {
+            let mut mixer = Mixer::new(current.encoding.byte_offset(crossfade_ms));
+            mixer.push_from(current.data.clone(), self.queue.offset());
+            mixer
+        });
+
+        if !mixer.needs_track() || self.next.is_some() {
+            return;
+        }
+
+        let Some(song) = self.queue.next() else {
+            return;
+        };
+
+        if song.encoding != current.encoding {
+            return;
+        }
+
+        // A track that can't be opened is reported once it becomes the current one
+        if let Ok(stream) = streams.open_song(song).await {
+            mixer.push(stream.data.clone());
+            self.next = Some(stream);
+        }
+    }
+
+    fn reset_mixer(&mut self) {
+        self.mixer = None;
+        self.next = None;
+    }
+
+    /// Renders up to `max` bytes with the mixer, following it into the next track
+    fn render(&mut self, max: usize) -> Option<StreamChunk> {
+        let mixer = self.mixer.as_mut()?;
+        let rendered = mixer.read(max);
+        let finished = mixer.take_finished();
+        let offset = mixer.offset();
+        let done = mixer.is_finished();
+
+        if finished > 0 {
+            self.track_changed = true;
+            self.current = None;
+            for _ in 0..finished {
+                self.queue.skip();
+                self.current = self.next.take();
+            }
+        }
+
+        if done {
+            self.reset_mixer();
+        } else {
+            self.queue.seek(offset);
+        }
+
+        (!rendered.is_empty()).then(|| StreamChunk::rendered(rendered))
+    }
+
+    /// Takes up to `max` bytes from the loaded track and moves the cursor past them
+    pub fn take(&mut self, max: usize) -> Option<StreamChunk> {
+        if self.crossfade_ms.is_some() {
+            return self.render(max);
+        }
+
+        let stream = self.current.as_ref()?;
+        let start = self.queue.offset();
+        let end = start.saturating_add(max).min(stream.data.len());
+
+        if start >= end {
+            return None;
+        }
+
+        self.queue.advance(end - start);
+        Some(StreamChunk::new(stream.data.clone(), start, end))
+    }
+
+    pub fn pause(&mut self) {
+        self.queue.pause();
+    }
+
+    pub fn resume(&mut self) {
+        self.queue.resume();
+    }
+
+    /// Moves to a byte offset within the current track
+    pub fn seek(&mut self, offset: usize) {
+        self.queue.seek(offset);
+        self.reset_mixer();
+    }
+
+    pub fn skip(&mut self) {
+        self.queue.skip();
+        self.current = None;
+        self.reset_mixer();
+    }
+
+    pub fn previous(&mut self) {
+        self.queue.previous();
+        self.current = None;
+        self.reset_mixer();
+    }
+}
@@ -0,0 +1,33 @@
+use harmony_core::core::model::encoding::EncodingProfile;
+
+use crate::api_response::ApiResponse;
+
+/// Resolves a position given in bytes, milliseconds or seconds to a byte offset
+/// # Errors
+/// When more than one unit is given, or the seconds are not a positive number
+pub fn resolve_offset(
+    bytes: Option<usize>,
+    ms: Option<u64>,
+    seconds: Option<f64>,
+    encoding: &EncodingProfile,
+) -> Result<Option<usize>, ApiResponse> {
+    match (bytes, ms, seconds) {
+        (None, None, None) => Ok(None),
+        (Some(bytes), None, None) => Ok(Some(bytes)),
+        (None, Some(ms), None) => Ok(Some(encoding.byte_offset(ms))),
+        (None, None, Some(seconds)) => {
+            if !seconds.is_finite() || seconds < 0.0 {
+                return Err(ApiResponse::BadRequest(
+                    "Seconds must be a positive number".to_string(),
+                ));
+            }
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let ms = (seconds * 1000.0).round() as u64;
+            Ok(Some(encoding.byte_offset(ms)))
+        }
+        _ => Err(ApiResponse::BadRequest(
+            "Only one of bytes, milliseconds or seconds can be given".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,145 @@
+/// A satisfiable byte range, the end is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ByteRange {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Formats the range as the value of a `Content-Range` header
+    #[must_use]
+    pub fn content_range(&self, size: usize) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end - 1, size)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// No usable range was requested, the whole file should be sent
+    Full,
+    /// One or more ranges, sorted and with overlapping ranges merged
+    Partial(Vec<ByteRange>),
+    /// None of the requested ranges overlap the file
+    Unsatisfiable,
+}
+
+// Requests with more ranges than this are served in full rather than as
+// a multipart response, to keep clients from making us build huge bodies
+const MAX_RANGES: usize = 16;
+
+/// Parses the value of a `Range` header for a file of the given size.
+/// Malformed headers are ignored as described by RFC 9110
+#[must_use]
+pub fn parse_range(header: &str, size: usize) -> RangeRequest {
+    let Some(specs) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+
+    let mut ranges: Vec<ByteRange> = Vec::new();
+    let mut count = 0;
+
+    for spec in specs.split(',') {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            continue;
+        }
+
+        count += 1;
+        if count > MAX_RANGES {
+            return RangeRequest::Full;
+        }
+
+        let Some((first, last)) = spec.split_once('-') else {
+            return RangeRequest::Full;
+        };
+
+        let range = match (first.trim(), last.trim()) {
+            ("", "") => return RangeRequest::Full,
+            // A suffix range requests the last n bytes
+            ("", suffix) => {
+                let Ok(suffix) = suffix.parse::<usize>() else {
+                    return RangeRequest::Full;
+                };
+
+                if suffix == 0 || size == 0 {
+                    continue;
+                }
+
+                ByteRange {
+                    start: size.saturating_sub(suffix),
+                    end: size,
+                }
+            }
+            (first, last) => {
+                let Ok(start) = first.parse::<usize>() else {
+                    return RangeRequest::Full;
+                };
+
+                let end = if last.is_empty() {
+                    size
+                } else {
+                    let Ok(last) = last.parse::<usize>() else {
+                        return RangeRequest::Full;
+                    };
+
+                    if last < start {
+                        return RangeRequest::Full;
+                    }
+
+                    last.saturating_add(1).min(size)
+                };
+
+                if start >= size {
+                    continue;
+                }
+
+                ByteRange { start, end }
+            }
+        };
+
+        ranges.push(range);
+    }
+
+    if count == 0 {
+        return RangeRequest::Full;
+    }
+
+    if ranges.is_empty() {
+        return RangeRequest::Unsatisfiable;
+    }
+
+    RangeRequest::Partial(merge_ranges(ranges))
+}
+
+fn merge_ranges(mut ranges: Vec<ByteRange>) -> Vec<ByteRange> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// Checks an `If-None-Match` or `If-Match` header value against an entity tag
+#[must_use]
+pub fn etag_matches(header: &str, etag: &str) -> bool {
+    header.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.trim_start_matches("W/") == etag.trim_start_matches("W/")
+    })
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Limits are counted per minute, times are in milliseconds
+const WINDOW: i64 = 60_000;
+
+/// Routes that share a rate limit, see `RateLimit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    /// Reading audio, clients poll these the most
+    Stream,
+    /// Listing and searching songs, users and playlists
+    Search,
+    /// Adding songs, each one starts a download
+    Ingest,
+    /// Routes that send mail
+    Mail,
+}
+
+impl RouteGroup {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RouteGroup::Stream => "stream",
+            RouteGroup::Search => "search",
+            RouteGroup::Ingest => "ingest",
+            RouteGroup::Mail => "mail",
+        }
+    }
+}
+
+/// Lets each key make a number of requests a minute. A key that was quiet may use
+/// the whole minute at once, after that requests are spread out evenly
+#[derive(Debug, Default)]
+pub struct RateLimiter<K> {
+    // The moment each key has used up its allowance until, requests may run a minute ahead of it
+    used_until: HashMap<K, i64>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            used_until: HashMap::new(),
+        }
+    }
+
+    /// Counts a request against a limit a minute, 0 lets everything through.
+    /// # Errors
+    /// When the key is over its limit, with how long to wait before the next request fits
+    pub fn take(&mut self, key: K, limit: u32, now: i64) -> Result<(), i64> {
+        self.take_all(vec![(key, limit)], now)
+    }
+
+    /// Counts a request against several keys, such as a token and the address it came from.
+    /// It is only counted when every key has room, so one being full doesn't use up the others
+    /// # Errors
+    /// When a key is over its limit, with the longest wait before the request fits all of them
+    pub fn take_all(&mut self, keys: Vec<(K, u32)>, now: i64) -> Result<(), i64> {
+        let mut taken = Vec::with_capacity(keys.len());
+        let mut wait = 0;
+
+        for (key, limit) in keys {
+            if limit == 0 {
+                continue;
+            }
+
+            let interval = (WINDOW / i64::from(limit)).max(1);
+            let used_until = self
+                .used_until
+                .get(&key)
+                .map_or(now, |until| (*until).max(now));
+
+            let allowed_at = used_until + interval - WINDOW;
+            wait = wait.max(allowed_at - now);
+            taken.push((key, used_until + interval));
+        }
+
+        if wait > 0 {
+            return Err(wait);
+        }
+
+        self.used_until.extend(taken);
+        Ok(())
+    }
+
+    /// Forgets the keys that have their full allowance back
+    pub fn prune(&mut self, now: i64) {
+        self.used_until.retain(|_, until| *until > now);
+    }
+}
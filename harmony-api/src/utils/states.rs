@@ -1,24 +1,722 @@
-use rocket::tokio::sync::RwLock;
+use chrono::Utc;
+use harmony_core::core::model::schedule::Schedule;
+use harmony_core::core::model::station::{Program, Station};
+use harmony_core::core::model::{encoding::EncodingProfile, song::Song};
+use harmony_core::core::playback::broadcast::Broadcast;
+use harmony_core::core::playback::jukebox::Jukebox;
+use harmony_core::core::playback::room::Room;
+use harmony_core::result::ServerError;
+use harmony_core::utils::short_code;
+use harmony_core::utils::song_file::{self, SongData};
+use nanoid::nanoid;
+use rocket::tokio::sync::{Mutex as AsyncMutex, RwLock};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
-use tokio::time;
+use tokio::{task, time};
 
-// Since it is inefficient to read a file every time the user asks
-// for a part of a filestream, we will stream files using a loaded state
+use super::clock;
+use super::environment;
+use super::login_throttle::{Lockout, Throttle, ThrottlePolicy};
+use super::player::Player;
+use super::rate_limit::{RateLimiter, RouteGroup};
+
+/// Tracks when an idle entry should be dropped from its state
+#[derive(Debug)]
+pub struct Expiration {
+    at: Mutex<Instant>,
+    lifetime: Duration,
+}
+
+impl Expiration {
+    fn new(lifetime: Duration) -> Self {
+        Self {
+            at: Mutex::new(Instant::now() + lifetime),
+            lifetime,
+        }
+    }
+
+    /// Keeps the entry alive for another lifetime
+    pub fn touch(&self) {
+        *self.at.lock().expect("Expiration lock poisoned") = Instant::now() + self.lifetime;
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        *self.at.lock().expect("Expiration lock poisoned") <= now
+    }
+}
+
+/// A song file that is currently being streamed
+#[derive(Debug)]
+pub struct OpenStream {
+    pub data: Arc<SongData>,
+    pub encoding: EncodingProfile,
+    expiration: Expiration,
+}
+
+impl OpenStream {
+    fn new(data: SongData, encoding: EncodingProfile) -> Self {
+        Self {
+            data: Arc::new(data),
+            encoding,
+            expiration: Expiration::new(Duration::from_secs(environment::get_stream_lifetime())),
+        }
+    }
+
+    /// Keeps the stream alive for another stream lifetime
+    pub fn touch(&self) {
+        self.expiration.touch();
+    }
+}
+
+// Open streams map the song file into memory, the map lock is only held to look up
+// or insert a stream so listeners never wait on each other while reading
 #[derive(Debug, Clone, Default)]
 pub struct StreamState {
-    #[allow(clippy::type_complexity)]
-    pub file_data: Arc<RwLock<HashMap<String, (Vec<u8>, Instant)>>>,
+    pub streams: Arc<RwLock<HashMap<String, Arc<OpenStream>>>>,
 }
 
 impl StreamState {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            file_data: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(RwLock::new(HashMap::new())),
         }
     }
+
+    /// Returns the open stream for the file, opening it if needed
+    /// # Errors
+    /// When the song file does not exist or could not be mapped
+    pub async fn open(&self, file_id: &str) -> Result<Arc<OpenStream>, ServerError> {
+        if let Some(stream) = self.get(file_id).await {
+            return Ok(stream);
+        }
+
+        let file_id = file_id.to_string();
+        let song = task::spawn_blocking(move || Song::get_by_file_id(&file_id))
+            .await
+            .map_err(|_| ServerError::Database("Failed to look up song".to_string()))??;
+        self.open_song(&song).await
+    }
+
+    /// Returns the open stream for the song, opening it if needed
+    /// # Errors
+    /// When the song file does not exist or could not be mapped
+    pub async fn open_song(&self, song: &Song) -> Result<Arc<OpenStream>, ServerError> {
+        if let Some(stream) = self.get(&song.file_id).await {
+            return Ok(stream);
+        }
+
+        // Map the file without holding the lock, another request may beat us to it
+        let data = song_file::get_song_data(&song.file_id)?;
+
+        let mut streams = self.streams.write().await;
+        let stream = streams
+            .entry(song.file_id.clone())
+            .or_insert_with(|| Arc::new(OpenStream::new(data, song.encoding)));
+        stream.touch();
+
+        Ok(stream.clone())
+    }
+
+    /// Returns the stream if it is open, resetting its expiration time
+    pub async fn get(&self, file_id: &str) -> Option<Arc<OpenStream>> {
+        let stream = self.streams.read().await.get(file_id).cloned()?;
+        stream.touch();
+        Some(stream)
+    }
+}
+
+/// A playback session, the player is locked while a request moves its cursor
+#[derive(Debug)]
+pub struct PlaybackSession {
+    pub id: String,
+    pub owner: i32,
+    pub player: AsyncMutex<Player>,
+    created: Instant,
+    expiration: Expiration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionState {
+    pub sessions: Arc<RwLock<HashMap<String, Arc<PlaybackSession>>>>,
+}
+
+impl SessionState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Opens a session for the user, closing their oldest sessions when they have too many open
+    pub async fn create(&self, owner: i32, player: Player) -> Arc<PlaybackSession> {
+        let session = Arc::new(PlaybackSession {
+            id: nanoid!(),
+            owner,
+            player: AsyncMutex::new(player),
+            created: Instant::now(),
+            expiration: Expiration::new(Duration::from_secs(environment::get_session_lifetime())),
+        });
+
+        let max_sessions = environment::get_max_sessions_per_user().max(1);
+        let mut sessions = self.sessions.write().await;
+        let mut owned: Vec<(Instant, String)> = sessions
+            .values()
+            .filter(|session| session.owner == owner)
+            .map(|session| (session.created, session.id.clone()))
+            .collect();
+        owned.sort();
+
+        let excess = (owned.len() + 1).saturating_sub(max_sessions);
+        for (_, session_id) in owned.into_iter().take(excess) {
+            sessions.remove(&session_id);
+        }
+
+        sessions.insert(session.id.clone(), session.clone());
+        session
+    }
+
+    /// Returns the session if it exists, resetting its expiration time
+    pub async fn get(&self, session_id: &str) -> Option<Arc<PlaybackSession>> {
+        let session = self.sessions.read().await.get(session_id).cloned()?;
+        session.expiration.touch();
+        Some(session)
+    }
+
+    pub async fn remove(&self, session_id: &str) -> Option<Arc<PlaybackSession>> {
+        self.sessions.write().await.remove(session_id)
+    }
+}
+
+// Room and jukebox codes are read out to everyone at the event and typed on in-game computers
+const SHORT_CODE_LENGTH: usize = 6;
+
+/// A listening room, the lock is only held while the timeline is read or changed
+#[derive(Debug)]
+pub struct SharedRoom {
+    pub code: String,
+    pub room: Mutex<Room>,
+    expiration: Expiration,
+}
+
+impl SharedRoom {
+    /// Locks the room
+    /// # Panics
+    /// When another request panicked while holding the lock
+    pub fn lock(&self) -> MutexGuard<'_, Room> {
+        self.room.lock().expect("Room lock poisoned")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RoomState {
+    pub rooms: Arc<RwLock<HashMap<String, Arc<SharedRoom>>>>,
+}
+
+impl RoomState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rooms: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn create(&self, room: Room) -> Arc<SharedRoom> {
+        let mut rooms = self.rooms.write().await;
+
+        let mut code = short_code::generate(SHORT_CODE_LENGTH);
+        while rooms.contains_key(&code) {
+            code = short_code::generate(SHORT_CODE_LENGTH);
+        }
+
+        let room = Arc::new(SharedRoom {
+            code: code.clone(),
+            room: Mutex::new(room),
+            expiration: Expiration::new(Duration::from_secs(environment::get_room_lifetime())),
+        });
+        rooms.insert(code, room.clone());
+
+        room
+    }
+
+    /// Returns the room if it exists, resetting its expiration time
+    pub async fn get(&self, code: &str) -> Option<Arc<SharedRoom>> {
+        let room = self
+            .rooms
+            .read()
+            .await
+            .get(&short_code::normalize(code))
+            .cloned()?;
+        room.expiration.touch();
+        Some(room)
+    }
+
+    pub async fn remove(&self, code: &str) -> Option<Arc<SharedRoom>> {
+        self.rooms
+            .write()
+            .await
+            .remove(&short_code::normalize(code))
+    }
+}
+
+/// A jukebox, the lock is only held while the queue is read or changed
+#[derive(Debug)]
+pub struct SharedJukebox {
+    pub code: String,
+    pub owner: i32,
+    pub jukebox: Mutex<Jukebox>,
+    expiration: Expiration,
+}
+
+impl SharedJukebox {
+    /// Locks the jukebox
+    /// # Panics
+    /// When another request panicked while holding the lock
+    pub fn lock(&self) -> MutexGuard<'_, Jukebox> {
+        self.jukebox.lock().expect("Jukebox lock poisoned")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JukeboxState {
+    pub jukeboxes: Arc<RwLock<HashMap<String, Arc<SharedJukebox>>>>,
+}
+
+impl JukeboxState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            jukeboxes: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn create(
+        &self,
+        owner: i32,
+        jukebox: Jukebox,
+    ) -> Result<Arc<SharedJukebox>, ServerError> {
+        let jukebox = {
+            let mut jukeboxes = self.jukeboxes.write().await;
+
+            let mut code = short_code::generate(SHORT_CODE_LENGTH);
+            while jukeboxes.contains_key(&code) {
+                code = short_code::generate(SHORT_CODE_LENGTH);
+            }
+
+            let jukebox = Arc::new(SharedJukebox {
+                code: code.clone(),
+                owner,
+                jukebox: Mutex::new(jukebox),
+                expiration: Expiration::new(Duration::from_secs(
+                    environment::get_jukebox_lifetime(),
+                )),
+            });
+            jukeboxes.insert(code, jukebox.clone());
+            jukebox
+        };
+
+        // A jukebox from before a restart may have had the code and left its schedules behind
+        if let Err(error) = remove_schedules(vec![jukebox.code.clone()]).await {
+            self.jukeboxes.write().await.remove(&jukebox.code);
+            return Err(error);
+        }
+
+        Ok(jukebox)
+    }
+
+    /// Returns the jukebox if it exists, resetting its expiration time
+    pub async fn get(&self, code: &str) -> Option<Arc<SharedJukebox>> {
+        let jukebox = self
+            .jukeboxes
+            .read()
+            .await
+            .get(&short_code::normalize(code))
+            .cloned()?;
+        jukebox.expiration.touch();
+        Some(jukebox)
+    }
+
+    /// Removes the jukebox along with its schedules
+    pub async fn remove(&self, code: &str) -> Result<Option<Arc<SharedJukebox>>, ServerError> {
+        let jukebox = self
+            .jukeboxes
+            .write()
+            .await
+            .remove(&short_code::normalize(code));
+
+        if let Some(jukebox) = &jukebox {
+            remove_schedules(vec![jukebox.code.clone()]).await?;
+        }
+        Ok(jukebox)
+    }
+}
+
+// Jukebox codes are handed out again, the schedules of one that is gone must not carry over
+async fn remove_schedules(codes: Vec<String>) -> Result<(), ServerError> {
+    if codes.is_empty() {
+        return Ok(());
+    }
+
+    task::spawn_blocking(move || {
+        codes
+            .iter()
+            .try_for_each(|code| Schedule::remove_by_jukebox(code).map(|_| ()))
+    })
+    .await
+    .map_err(|_| ServerError::Database("Failed to remove schedules".to_string()))?
+}
+
+// How often a station picks up changes to its playlist or the library
+const STATION_REFRESH: Duration = Duration::from_secs(60);
+
+/// A station that is on air, stations never stop so they are not cleaned up
+#[derive(Debug)]
+pub struct LiveStation {
+    pub program: Program,
+    broadcast: Mutex<Broadcast>,
+    refreshed: Mutex<Instant>,
+}
+
+impl LiveStation {
+    // Starts the program where its clock says it should be
+    fn start(station: &Station, program: Program) -> Result<Self, ServerError> {
+        let broadcast = Broadcast::new(
+            song_file::with_durations(station.get_tracks(&program)?),
+            true,
+            program.started.timestamp_millis(),
+        );
+
+        Ok(Self {
+            program,
+            broadcast: Mutex::new(broadcast),
+            refreshed: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Locks the broadcast of the station
+    /// # Panics
+    /// When another request panicked while holding the lock
+    pub fn lock(&self) -> MutexGuard<'_, Broadcast> {
+        self.broadcast.lock().expect("Station lock poisoned")
+    }
+
+    // Claims the refresh for the caller when it is due, so only one request rebuilds the list
+    fn claim_refresh(&self) -> bool {
+        let mut refreshed = self.refreshed.lock().expect("Station lock poisoned");
+        if refreshed.elapsed() < STATION_REFRESH {
+            return false;
+        }
+
+        *refreshed = Instant::now();
+        true
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StationState {
+    pub stations: Arc<RwLock<HashMap<i32, Arc<LiveStation>>>>,
+}
+
+// Runs the blocking part of tuning in to a station
+async fn on_station_pool<T, F>(work: F) -> Result<T, ServerError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ServerError> + Send + 'static,
+{
+    task::spawn_blocking(work)
+        .await
+        .map_err(|_| ServerError::ExecutionFailed)?
+}
+
+impl StationState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            stations: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the broadcast of the station, putting it on air if needed.
+    /// A new program picks up where its clock says it should be,
+    /// a refreshed track list carries on with the song that was playing
+    /// # Errors
+    /// When the program or tracks of the station could not be loaded
+    pub async fn tune(&self, station: &Station) -> Result<Arc<LiveStation>, ServerError> {
+        // Programs and tracks come from the database and broadcasts read every song file
+        let blocking_station = station.clone();
+        let program = on_station_pool(move || blocking_station.program_at(Utc::now())).await?;
+        let live = self.stations.read().await.get(&station.id).cloned();
+
+        let live = match live {
+            Some(live) if live.program == program => live,
+            // Not on air yet, or a schedule started or ended
+            _ => {
+                let blocking_station = station.clone();
+                let live = Arc::new(
+                    on_station_pool(move || LiveStation::start(&blocking_station, program)).await?,
+                );
+                let mut stations = self.stations.write().await;
+
+                match stations.get(&station.id) {
+                    // Another request switched programs first
+                    Some(current) if current.program == program => return Ok(current.clone()),
+                    _ => {
+                        stations.insert(station.id, live.clone());
+                        return Ok(live);
+                    }
+                }
+            }
+        };
+
+        if live.claim_refresh() {
+            let now = clock::now_ms();
+            let playing = live
+                .lock()
+                .current(now)
+                .map(|(position, song)| (song.id, position.position_ms));
+
+            // Build the new broadcast without holding the lock
+            let blocking_station = station.clone();
+            let broadcast = on_station_pool(move || {
+                let tracks = song_file::with_durations(blocking_station.get_tracks(&program)?);
+                Ok(match playing {
+                    Some((song_id, position_ms)) => {
+                        Broadcast::starting_at(tracks, true, song_id, position_ms, now)
+                    }
+                    None => Broadcast::new(tracks, true, now),
+                })
+            })
+            .await?;
+            *live.lock() = broadcast;
+        }
+
+        Ok(live)
+    }
+
+    /// Returns the station if it is on air
+    pub async fn get(&self, station_id: i32) -> Option<Arc<LiveStation>> {
+        self.stations.read().await.get(&station_id).cloned()
+    }
+
+    pub async fn remove(&self, station_id: i32) -> Option<Arc<LiveStation>> {
+        self.stations.write().await.remove(&station_id)
+    }
+}
+
+// Failed logins start to wait a second, which doubles up to this
+const LOGIN_MAX_DELAY: i64 = 60_000;
+
+/// Failed logins, counted by the name that was tried and by the address they came from.
+/// Names are slowed down quickly, addresses get more room since people may share one
+#[derive(Debug, Clone)]
+pub struct LoginState {
+    accounts: Arc<Mutex<Throttle<String>>>,
+    addresses: Arc<Mutex<Throttle<IpAddr>>>,
+}
+
+impl Default for LoginState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoginState {
+    #[must_use]
+    pub fn new() -> Self {
+        let lockout = i64::try_from(environment::get_login_lockout_time())
+            .unwrap_or(i64::MAX)
+            .saturating_mul(1000);
+        let account_failures = environment::get_login_max_failures();
+        let address_failures = environment::get_login_max_address_failures();
+
+        Self {
+            accounts: Arc::new(Mutex::new(Throttle::new(ThrottlePolicy {
+                free_failures: account_failures / 3,
+                base_delay: 1000,
+                max_delay: LOGIN_MAX_DELAY,
+                lockout_failures: account_failures,
+                lockout,
+            }))),
+            addresses: Arc::new(Mutex::new(Throttle::new(ThrottlePolicy {
+                free_failures: address_failures / 3,
+                base_delay: 1000,
+                max_delay: LOGIN_MAX_DELAY,
+                lockout_failures: address_failures,
+                lockout,
+            }))),
+        }
+    }
+
+    /// How long in milliseconds the login has to wait before it may be tried, if it has to
+    /// # Panics
+    /// When another request panicked while holding the lock
+    #[must_use]
+    pub fn wait(&self, name: &str, address: Option<IpAddr>, now: i64) -> Option<i64> {
+        let account = self
+            .accounts
+            .lock()
+            .expect("Login lock poisoned")
+            .wait(&Self::account_key(name), now);
+        let address = address.and_then(|address| {
+            self.addresses
+                .lock()
+                .expect("Login lock poisoned")
+                .wait(&address, now)
+        });
+
+        account.max(address)
+    }
+
+    /// Counts a failed login, returning the lockouts of the name and the address it started
+    /// # Panics
+    /// When another request panicked while holding the lock
+    pub fn fail(
+        &self,
+        name: &str,
+        address: Option<IpAddr>,
+        now: i64,
+    ) -> (Option<Lockout>, Option<Lockout>) {
+        let account = self
+            .accounts
+            .lock()
+            .expect("Login lock poisoned")
+            .fail(Self::account_key(name), now);
+        let address = address.and_then(|address| {
+            self.addresses
+                .lock()
+                .expect("Login lock poisoned")
+                .fail(address, now)
+        });
+
+        (account, address)
+    }
+
+    /// Forgets the failures of the name after a login succeeded.
+    /// The address keeps its count, or logging into an own account would reset it
+    /// # Panics
+    /// When another request panicked while holding the lock
+    pub fn succeed(&self, name: &str) {
+        self.accounts
+            .lock()
+            .expect("Login lock poisoned")
+            .succeed(&Self::account_key(name));
+    }
+
+    fn prune(&self, now: i64) {
+        self.accounts
+            .lock()
+            .expect("Login lock poisoned")
+            .prune(now);
+        self.addresses
+            .lock()
+            .expect("Login lock poisoned")
+            .prune(now);
+    }
+
+    // Names differing in case are throttled together
+    fn account_key(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+}
+
+/// Requests made per route group, counted by the token they were made with,
+/// or by the address they came from when there is none
+#[derive(Debug, Clone)]
+pub struct RateLimitState {
+    limits: HashMap<RouteGroup, u32>,
+    address_factor: u32,
+    limiter: Arc<Mutex<RateLimiter<(RouteGroup, String)>>>,
+}
+
+impl Default for RateLimitState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimitState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            limits: HashMap::from([
+                (RouteGroup::Stream, environment::get_stream_rate_limit()),
+                (RouteGroup::Search, environment::get_search_rate_limit()),
+                (RouteGroup::Ingest, environment::get_ingest_rate_limit()),
+                (RouteGroup::Mail, environment::get_mail_rate_limit()),
+            ]),
+            address_factor: environment::get_address_rate_limit_factor(),
+            limiter: Arc::new(Mutex::new(RateLimiter::new())),
+        }
+    }
+
+    /// Counts a request to the group against the address it came from, and against its
+    /// token when it has one that checked out. Requests with a token share a larger
+    /// allowance of the address, so computers behind one game server don't crowd each other out
+    /// # Errors
+    /// When the client is over the limit of the group, with how long to wait in milliseconds
+    /// # Panics
+    /// When another request panicked while holding the lock
+    pub fn take(
+        &self,
+        group: RouteGroup,
+        address: Option<IpAddr>,
+        token: Option<String>,
+        now: i64,
+    ) -> Result<(), i64> {
+        let limit = self.limits.get(&group).copied().unwrap_or(0);
+        let mut keys = Vec::with_capacity(2);
+
+        match (token, address) {
+            (Some(token), address) => {
+                keys.push(((group, format!("token:{token}")), limit));
+                if let Some(address) = address {
+                    keys.push((
+                        (group, format!("address with tokens:{address}")),
+                        limit.saturating_mul(self.address_factor),
+                    ));
+                }
+            }
+            (None, Some(address)) => keys.push(((group, format!("address:{address}")), limit)),
+            (None, None) => {}
+        }
+
+        self.limiter
+            .lock()
+            .expect("Rate limit lock poisoned")
+            .take_all(keys, now)
+    }
+}
+
+/// Drops the entries that expired, returning their keys
+async fn remove_expired<T>(
+    entries: &RwLock<HashMap<String, Arc<T>>>,
+    expiration: impl Fn(&T) -> &Expiration,
+) -> Vec<String> {
+    let now = Instant::now();
+
+    // Only take the write lock when there is something to remove
+    let has_expired = entries
+        .read()
+        .await
+        .values()
+        .any(|entry| expiration(entry).expired(now));
+
+    if !has_expired {
+        return Vec::new();
+    }
+
+    let mut removed = Vec::new();
+    entries.write().await.retain(|key, entry| {
+        let expired = expiration(entry).expired(now);
+        if expired {
+            removed.push(key.clone());
+        }
+        !expired
+    });
+    removed
 }
 
 pub async fn stream_cleanup_task(state: StreamState) {
@@ -26,9 +724,57 @@ pub async fn stream_cleanup_task(state: StreamState) {
 
     loop {
         interval.tick().await;
+        remove_expired(&state.streams, |stream| &stream.expiration).await;
+    }
+}
+
+pub async fn session_cleanup_task(state: SessionState) {
+    let mut interval = time::interval(Duration::from_secs(1));
+
+    loop {
+        interval.tick().await;
+        remove_expired(&state.sessions, |session| &session.expiration).await;
+    }
+}
+
+pub async fn room_cleanup_task(state: RoomState) {
+    let mut interval = time::interval(Duration::from_secs(1));
+
+    loop {
+        interval.tick().await;
+        remove_expired(&state.rooms, |room| &room.expiration).await;
+    }
+}
+
+pub async fn jukebox_cleanup_task(state: JukeboxState) {
+    let mut interval = time::interval(Duration::from_secs(1));
 
-        let mut file_data_map = state.file_data.write().await;
-        let now = Instant::now();
-        file_data_map.retain(|_, (_, expiration)| *expiration > now);
+    loop {
+        interval.tick().await;
+        let expired = remove_expired(&state.jukeboxes, |jukebox| &jukebox.expiration).await;
+        // When this fails they are removed once the code is handed out again
+        remove_schedules(expired).await.ok();
+    }
+}
+
+pub async fn login_cleanup_task(state: LoginState) {
+    let mut interval = time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+        state.prune(clock::now_ms());
+    }
+}
+
+pub async fn rate_limit_cleanup_task(state: RateLimitState) {
+    let mut interval = time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+        state
+            .limiter
+            .lock()
+            .expect("Rate limit lock poisoned")
+            .prune(clock::now_ms());
     }
 }
@@ -1,3 +1,15 @@
+pub mod auth_session;
+pub mod device_token;
+pub mod email_token;
+pub mod encoding;
+pub mod invite;
+pub mod login_lockout;
 pub mod playlist;
+pub mod quota;
+pub mod registration_mode;
+pub mod role;
+pub mod schedule;
+pub mod share_code;
 pub mod song;
+pub mod station;
 pub mod user;
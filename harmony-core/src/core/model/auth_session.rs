@@ -0,0 +1,127 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use harmony_data::model::auth_session::{AuthSessionModel, NewAuthSessionModel};
+use harmony_data::result::Error;
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+
+use crate::result::ServerError;
+use crate::utils::secret;
+
+/// Refresh tokens start with this, so they can't be mistaken for device tokens
+pub const REFRESH_TOKEN_PREFIX: &str = "hrt_";
+
+/// A login, kept on the server so it can be ended before its tokens expire.
+/// Access tokens name the session, the refresh token renews it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuthSession {
+    pub id: String,
+    pub user_id: i32,
+    pub expires: DateTime<Utc>,
+    pub refreshed: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl AuthSession {
+    /// Starts a session for the user, returning it with its first refresh token
+    pub fn start(user_id: i32, lifetime: TimeDelta) -> Result<(Self, String), ServerError> {
+        // Logging in is a good moment to clear out the sessions that ran out
+        AuthSessionModel::remove_expired(user_id)
+            .map_err(|_| ServerError::Database("Failed to remove expired sessions".to_string()))?;
+
+        let token = secret::generate(REFRESH_TOKEN_PREFIX);
+        let new_session = NewAuthSessionModel {
+            id: nanoid!(),
+            user_id,
+            refresh_hash: secret::hash(&token),
+            expires: Utc::now() + lifetime,
+        };
+
+        match AuthSessionModel::create(&new_session) {
+            Ok(session) => Ok((Self::from_model(&session), token)),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to create session".to_string(),
+            )),
+        }
+    }
+
+    /// Trades a refresh token for a new one, extending the session.
+    /// A token that was already traded in means it leaked, so the session is ended
+    pub fn refresh(token: &str, lifetime: TimeDelta) -> Result<(Self, String), ServerError> {
+        let hash = secret::hash(token);
+        let session = match AuthSessionModel::get_by_refresh_hash(&hash) {
+            Ok(session) => session,
+            Err(Error::NotFound) => return Err(ServerError::NotFound),
+            Err(_) => return Err(ServerError::Database("Failed to get session".to_string())),
+        };
+
+        if session.refresh_hash != hash || session.expires <= Utc::now() {
+            Self::remove(&session.id)?;
+            return Err(ServerError::NotFound);
+        }
+
+        let new_token = secret::generate(REFRESH_TOKEN_PREFIX);
+        let result = AuthSessionModel::rotate(
+            &session.id,
+            &hash,
+            &secret::hash(&new_token),
+            Utc::now() + lifetime,
+        );
+
+        match result {
+            Ok(session) => Ok((Self::from_model(&session), new_token)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to refresh session".to_string(),
+            )),
+        }
+    }
+
+    /// Gets a session that has not expired or been ended
+    pub fn get_active(session_id: &str) -> Result<Self, ServerError> {
+        let session = match AuthSessionModel::get_by_id(session_id) {
+            Ok(session) => session,
+            Err(Error::NotFound) => return Err(ServerError::NotFound),
+            Err(_) => return Err(ServerError::Database("Failed to get session".to_string())),
+        };
+
+        if session.expires <= Utc::now() {
+            return Err(ServerError::NotFound);
+        }
+
+        Ok(Self::from_model(&session))
+    }
+
+    /// Ends a session, its tokens stop working right away
+    pub fn remove(session_id: &str) -> Result<(), ServerError> {
+        match AuthSessionModel::remove(session_id) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to remove session".to_string(),
+            )),
+        }
+    }
+
+    /// Ends every session of a user, returning how many there were
+    pub fn remove_all(user_id: i32) -> Result<usize, ServerError> {
+        AuthSessionModel::remove_by_user(user_id)
+            .map_err(|_| ServerError::Database("Failed to remove sessions".to_string()))
+    }
+
+    /// Ends every session of a user except the one they are using
+    pub fn remove_others(user_id: i32, keep: &str) -> Result<usize, ServerError> {
+        AuthSessionModel::remove_by_user_except(user_id, keep)
+            .map_err(|_| ServerError::Database("Failed to remove sessions".to_string()))
+    }
+
+    #[must_use]
+    pub fn from_model(session_model: &AuthSessionModel) -> Self {
+        Self {
+            id: session_model.id.clone(),
+            user_id: session_model.user_id,
+            expires: session_model.expires,
+            refreshed: session_model.refreshed,
+            created: session_model.created,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use harmony_data::{model::device_token::DeviceTokenModel, result::Error};
+use serde::{Deserialize, Serialize};
+
+use crate::core::validation::result::ValidationError;
+use crate::result::ServerError;
+use crate::utils::secret;
+
+/// Device tokens start with this, so they can be told apart from login tokens
+pub const DEVICE_TOKEN_PREFIX: &str = "hdt_";
+
+/// How stale `last_used` may get before it is written again
+pub const LAST_USED_PRECISION: TimeDelta = TimeDelta::minutes(1);
+
+/// What a device token may be used for
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Play audio through the stream, session and socket routes
+    Stream,
+    /// Look things up, such as songs and playlists
+    Read,
+    /// Make changes, such as adding songs or editing playlists
+    Write,
+}
+
+impl Scope {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::Stream => "stream",
+            Scope::Read => "read",
+            Scope::Write => "write",
+        }
+    }
+
+    pub fn parse(scope: &str) -> Result<Self, ValidationError> {
+        match scope.trim() {
+            "stream" => Ok(Scope::Stream),
+            "read" => Ok(Scope::Read),
+            "write" => Ok(Scope::Write),
+            _ => Err(ValidationError::InvalidScope(format!(
+                "Unknown scope \"{scope}\", expected stream, read or write"
+            ))),
+        }
+    }
+}
+
+/// A long-lived token a user created for a single device, such as an in-game computer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub last_used: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl DeviceToken {
+    pub fn get_by_user(user_id: i32) -> Result<Vec<Self>, ServerError> {
+        match DeviceTokenModel::get_by_user(user_id) {
+            Ok(tokens) => tokens.iter().map(Self::from_model).collect(),
+            Err(_) => Err(ServerError::Database(
+                "Failed to get device tokens".to_string(),
+            )),
+        }
+    }
+
+    /// Finds the device token with the given secret, without marking it as used
+    pub fn find(token: &str) -> Result<Self, ServerError> {
+        if !token.starts_with(DEVICE_TOKEN_PREFIX) {
+            return Err(ServerError::NotFound);
+        }
+
+        match DeviceTokenModel::get_by_hash(&secret::hash(token)) {
+            Ok(token) => Self::from_model(&token),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to get device token".to_string(),
+            )),
+        }
+    }
+
+    /// Finds the device token with the given secret and marks it as used
+    pub fn authenticate(token: &str) -> Result<Self, ServerError> {
+        let device = Self::find(token)?;
+        let now = Utc::now();
+
+        if device.needs_touch(now) {
+            DeviceTokenModel::touch(device.id, now - LAST_USED_PRECISION)
+                .map_err(|_| ServerError::Database("Failed to update device token".to_string()))?;
+        }
+
+        Ok(device)
+    }
+
+    /// Whether `last_used` is too old to still be shown, streams use their token
+    /// for every chunk and it is not worth a write each time
+    #[must_use]
+    pub fn needs_touch(&self, now: DateTime<Utc>) -> bool {
+        self.last_used
+            .is_none_or(|last_used| now - last_used >= LAST_USED_PRECISION)
+    }
+
+    /// Revokes a device token of the user
+    pub fn remove(user_id: i32, token_id: i32) -> Result<(), ServerError> {
+        match DeviceTokenModel::remove(user_id, token_id) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to remove device token".to_string(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    pub fn from_model(token_model: &DeviceTokenModel) -> Result<Self, ServerError> {
+        let scopes = token_model
+            .scopes
+            .split(',')
+            .filter(|scope| !scope.is_empty())
+            .map(Scope::parse)
+            .collect::<Result<Vec<Scope>, ValidationError>>()
+            .map_err(|_| ServerError::Database("Device token has an unknown scope".to_string()))?;
+
+        Ok(Self {
+            id: token_model.id,
+            user_id: token_model.user_id,
+            name: token_model.name.clone(),
+            scopes,
+            last_used: token_model.last_used,
+            created: token_model.created,
+        })
+    }
+}
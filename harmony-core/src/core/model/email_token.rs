@@ -0,0 +1,84 @@
+use chrono::{TimeDelta, Utc};
+use harmony_data::model::email_token::{EmailTokenModel, NewEmailTokenModel};
+use harmony_data::result::Error;
+
+use crate::result::ServerError;
+use crate::utils::secret;
+
+/// Mailed tokens start with this, so they can't be mistaken for other tokens
+pub const EMAIL_TOKEN_PREFIX: &str = "het_";
+
+/// What a mailed token lets its holder do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailPurpose {
+    /// Prove the address belongs to the user
+    Verify,
+    /// Set a new password without knowing the old one
+    Reset,
+}
+
+impl EmailPurpose {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EmailPurpose::Verify => "verify",
+            EmailPurpose::Reset => "reset",
+        }
+    }
+
+    /// How long a token works, resets stay short since they hand over the account
+    #[must_use]
+    pub fn lifetime(self) -> TimeDelta {
+        match self {
+            EmailPurpose::Verify => TimeDelta::days(1),
+            EmailPurpose::Reset => TimeDelta::hours(1),
+        }
+    }
+}
+
+/// A mailed token that was used up, telling who it was for
+#[derive(Debug, Clone)]
+pub struct EmailToken {
+    pub user_id: i32,
+    /// The address the token was sent to
+    pub email: String,
+}
+
+impl EmailToken {
+    /// Creates a token to mail to the address, the one the user had for the purpose stops working
+    pub fn issue(user_id: i32, purpose: EmailPurpose, email: &str) -> Result<String, ServerError> {
+        let token = secret::generate(EMAIL_TOKEN_PREFIX);
+        let new_token = NewEmailTokenModel {
+            hash: secret::hash(&token),
+            user_id,
+            purpose: purpose.as_str().to_string(),
+            email: email.to_string(),
+            expires: Utc::now() + purpose.lifetime(),
+        };
+
+        match EmailTokenModel::create(&new_token) {
+            Ok(_) => Ok(token),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to create token".to_string(),
+            )),
+        }
+    }
+
+    /// Uses up a token, returns `ServerError::NotFound` when it is unknown, used or expired
+    pub fn consume(token: &str, purpose: EmailPurpose) -> Result<Self, ServerError> {
+        match EmailTokenModel::consume(&secret::hash(token.trim()), purpose.as_str()) {
+            Ok(token) => Ok(Self {
+                user_id: token.user_id,
+                email: token.email,
+            }),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to use token".to_string())),
+        }
+    }
+
+    /// Removes every token of the user, such as after their password was reset
+    pub fn remove_all(user_id: i32) -> Result<usize, ServerError> {
+        EmailTokenModel::remove_by_user(user_id)
+            .map_err(|_| ServerError::Database("Failed to remove tokens".to_string()))
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::result::ServerError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Codec {
+    Dfpwm,
+}
+
+impl Codec {
+    #[must_use]
+    pub fn bits_per_sample(self) -> u64 {
+        match self {
+            Codec::Dfpwm => 1,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Codec::Dfpwm => "dfpwm",
+        }
+    }
+
+    pub fn parse(codec: &str) -> Result<Self, ServerError> {
+        match codec {
+            "dfpwm" => Ok(Codec::Dfpwm),
+            _ => Err(ServerError::Database(format!("Unknown codec {codec}"))),
+        }
+    }
+}
+
+/// Describes how a song file was encoded, so byte offsets can be translated to time and back
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EncodingProfile {
+    pub codec: Codec,
+    pub sample_rate: u32,
+    pub channels: u32,
+}
+
+impl Default for EncodingProfile {
+    /// The profile new songs are converted to, what CC:Tweaked speakers play natively
+    fn default() -> Self {
+        Self {
+            codec: Codec::Dfpwm,
+            sample_rate: 48_000,
+            channels: 1,
+        }
+    }
+}
+
+impl EncodingProfile {
+    pub fn new(codec: &str, sample_rate: i32, channels: i32) -> Result<Self, ServerError> {
+        let (Ok(sample_rate), Ok(channels)) = (u32::try_from(sample_rate), u32::try_from(channels))
+        else {
+            return Err(ServerError::Database(
+                "Invalid encoding profile".to_string(),
+            ));
+        };
+
+        Ok(Self {
+            codec: Codec::parse(codec)?,
+            sample_rate,
+            channels,
+        })
+    }
+
+    #[must_use]
+    pub fn bytes_per_second(&self) -> u64 {
+        u64::from(self.sample_rate) * u64::from(self.channels) * self.codec.bits_per_sample() / 8
+    }
+
+    /// The playback time of the given number of bytes, in milliseconds
+    #[must_use]
+    pub fn duration_ms(&self, size: usize) -> u64 {
+        match self.bytes_per_second() {
+            0 => 0,
+            bytes_per_second => (size as u64).saturating_mul(1000) / bytes_per_second,
+        }
+    }
+
+    /// The byte offset at which the given playback time starts
+    #[must_use]
+    pub fn byte_offset(&self, position_ms: u64) -> usize {
+        let offset = position_ms.saturating_mul(self.bytes_per_second()) / 1000;
+        usize::try_from(offset).unwrap_or(usize::MAX)
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use harmony_data::model::invite::{InviteModel, NewInviteModel};
+use harmony_data::result::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::result::ServerError;
+use crate::utils::short_code;
+
+/// The length of new invite codes, longer than share codes since they let people in
+pub const INVITE_CODE_LENGTH: usize = 8;
+
+// Taken codes are retried with a new one, running out of tries means the space is nearly full
+const MAX_ATTEMPTS: usize = 8;
+
+/// A code that lets people register when registration is invite-only or needs approval
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Invite {
+    pub code: String,
+    /// Unset once the user is deleted
+    pub created_by: Option<i32>,
+    /// Unset for invites that can be used any number of times
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    pub expires: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl Invite {
+    pub fn get_list() -> Result<Vec<Self>, ServerError> {
+        match InviteModel::get_list() {
+            Ok(invites) => Ok(invites.iter().map(Self::from_model).collect()),
+            Err(_) => Err(ServerError::Database("Failed to get invites".to_string())),
+        }
+    }
+
+    /// Creates an invite with a fresh code
+    pub fn generate(
+        created_by: i32,
+        max_uses: Option<i32>,
+        expires: Option<DateTime<Utc>>,
+    ) -> Result<Self, ServerError> {
+        for _ in 0..MAX_ATTEMPTS {
+            let code = short_code::generate(INVITE_CODE_LENGTH);
+
+            match InviteModel::exists(&code) {
+                Ok(()) => continue,
+                Err(Error::NotFound) => {}
+                Err(_) => {
+                    return Err(ServerError::Database(
+                        "Failed to check if invite exists".to_string(),
+                    ))
+                }
+            }
+
+            return match InviteModel::create(&NewInviteModel {
+                code,
+                created_by,
+                max_uses,
+                expires,
+            }) {
+                Ok(invite) => Ok(Self::from_model(&invite)),
+                Err(_) => Err(ServerError::CouldNotCreate(
+                    "Failed to create invite".to_string(),
+                )),
+            };
+        }
+
+        Err(ServerError::CouldNotCreate(
+            "Could not find a free invite code".to_string(),
+        ))
+    }
+
+    /// Revokes an invite as typed by a user, ignoring case, spaces and dashes
+    pub fn remove(code: &str) -> Result<(), ServerError> {
+        match InviteModel::remove(&short_code::normalize(code)) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to remove invite".to_string())),
+        }
+    }
+
+    #[must_use]
+    pub fn from_model(invite_model: &InviteModel) -> Self {
+        Self {
+            code: invite_model.code.clone(),
+            created_by: invite_model.created_by,
+            max_uses: invite_model.max_uses,
+            uses: invite_model.uses,
+            expires: invite_model.expires,
+            created: invite_model.created,
+        }
+    }
+}
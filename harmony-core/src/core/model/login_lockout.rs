@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use harmony_data::model::login_lockout::{LoginLockoutModel, NewLoginLockoutModel};
+use harmony_data::model::user::UserModel;
+use serde::{Deserialize, Serialize};
+
+use crate::result::ServerError;
+
+/// What was locked out after too many failed logins
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LockoutScope {
+    /// Logging in as one name, from anywhere
+    Account,
+    /// Logging in from one address, as anyone
+    Address,
+}
+
+impl LockoutScope {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LockoutScope::Account => "account",
+            LockoutScope::Address => "address",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "account" => Some(LockoutScope::Account),
+            "address" => Some(LockoutScope::Address),
+            _ => None,
+        }
+    }
+}
+
+/// A record of logins being locked after too many failures, kept for admins to look through
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoginLockout {
+    pub id: i32,
+    pub scope: LockoutScope,
+    /// The account the name belongs to, unset when nobody has the name
+    pub user_id: Option<i32>,
+    /// The name that was tried, unset for address lockouts
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub failures: i32,
+    pub locked_until: DateTime<Utc>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl LoginLockout {
+    /// Records a lockout, linking it to the account when the name belongs to one
+    pub fn record(
+        scope: LockoutScope,
+        name: Option<&str>,
+        address: Option<String>,
+        failures: u32,
+        locked_until: DateTime<Utc>,
+    ) -> Result<Self, ServerError> {
+        let user_id = name
+            .and_then(|name| UserModel::get_by_name(name).ok())
+            .map(|user| user.id);
+
+        let new_lockout = NewLoginLockoutModel {
+            scope: scope.as_str().to_string(),
+            user_id,
+            name: name.map(str::to_string),
+            address,
+            failures: i32::try_from(failures).unwrap_or(i32::MAX),
+            locked_until,
+        };
+
+        match LoginLockoutModel::create(&new_lockout) {
+            Ok(lockout) => Ok(Self::from_model(&lockout)),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to record lockout".to_string(),
+            )),
+        }
+    }
+
+    /// Gets the most recent lockouts, newest first
+    pub fn get_recent(count: i64) -> Result<Vec<Self>, ServerError> {
+        match LoginLockoutModel::get_recent(count) {
+            Ok(lockouts) => Ok(lockouts.iter().map(Self::from_model).collect()),
+            Err(_) => Err(ServerError::Database("Failed to get lockouts".to_string())),
+        }
+    }
+
+    #[must_use]
+    pub fn from_model(lockout_model: &LoginLockoutModel) -> Self {
+        Self {
+            id: lockout_model.id,
+            // Rows are limited to known scopes by a check
+            scope: LockoutScope::parse(&lockout_model.scope).unwrap_or(LockoutScope::Account),
+            user_id: lockout_model.user_id,
+            name: lockout_model.name.clone(),
+            address: lockout_model.address.clone(),
+            failures: lockout_model.failures,
+            locked_until: lockout_model.locked_until,
+            created: lockout_model.created,
+        }
+    }
+}
@@ -41,6 +41,16 @@ impl Playlist {
         Ok(Self::from_model(&user))
     }
 
+    /// Gets a playlist the user may see, public ones and their own. Private playlists of
+    /// others are `ServerError::NotFound`, so their ids can't be probed
+    pub fn get_visible(playlist_id: i32, calling_user: Option<i32>) -> Result<Self, ServerError> {
+        match PlaylistModel::get_visible(playlist_id, calling_user) {
+            Ok(playlist) => Ok(Self::from_model(&playlist)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to get playlist".to_string())),
+        }
+    }
+
     pub fn find_by_name(playlist_name: &str, calling_user: i32) -> Result<Vec<Self>, ServerError> {
         let playlists = match PlaylistModel::find_by_name(playlist_name, calling_user) {
             Ok(playlists) => playlists,
@@ -0,0 +1,99 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use harmony_data::model::song::SongModel;
+use serde::{Deserialize, Serialize};
+
+use crate::core::model::role::Permission;
+use crate::core::model::user::User;
+use crate::result::ServerError;
+use crate::utils::{environment, song_file};
+
+/// Limits on how much audio a user may add, unset limits don't apply
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Quota {
+    pub songs_per_day: Option<u32>,
+    /// In bytes of converted audio
+    pub storage: Option<u64>,
+}
+
+/// How much of their quota a user has used
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    #[serde(flatten)]
+    pub quota: Quota,
+    /// Songs added in the last day
+    pub songs_today: u32,
+    /// When the oldest song of the last day stops counting, unset when none were added
+    pub next_song_freed: Option<DateTime<Utc>>,
+    pub storage_used: u64,
+}
+
+impl Quota {
+    /// The quota set for the server
+    #[must_use]
+    pub fn from_environment() -> Self {
+        Self {
+            songs_per_day: environment::get_song_daily_quota(),
+            storage: environment::get_storage_quota(),
+        }
+    }
+
+    /// The quota that applies to the user, admins run the server so they have none
+    #[must_use]
+    pub fn for_user(user: &User) -> Self {
+        if user.can(Permission::ManageUsers) {
+            return Self::default();
+        }
+
+        Self::from_environment()
+    }
+
+    /// Counts what the user added, songs whose file is gone take no space
+    pub fn usage(self, user_id: i32) -> Result<QuotaUsage, ServerError> {
+        let today = SongModel::get_added_since(user_id, Utc::now() - TimeDelta::days(1))
+            .map_err(|_| ServerError::Database("Failed to get songs".to_string()))?;
+        let file_ids = SongModel::get_file_ids_by_user(user_id)
+            .map_err(|_| ServerError::Database("Failed to get songs".to_string()))?;
+
+        let storage_used = file_ids
+            .iter()
+            .filter_map(|file_id| song_file::get_song_size(file_id).ok())
+            .map(|size| size as u64)
+            .sum();
+
+        Ok(QuotaUsage {
+            quota: self,
+            songs_today: u32::try_from(today.len()).unwrap_or(u32::MAX),
+            next_song_freed: today
+                .first()
+                .and_then(|song| song.created)
+                .map(|created| created + TimeDelta::days(1)),
+            storage_used,
+        })
+    }
+}
+
+impl QuotaUsage {
+    /// Whether another song may be added today
+    #[must_use]
+    pub fn songs_left(&self) -> bool {
+        self.quota
+            .songs_per_day
+            .is_none_or(|limit| self.songs_today < limit)
+    }
+
+    /// Whether there is room to store another song
+    #[must_use]
+    pub fn storage_left(&self) -> bool {
+        self.quota
+            .storage
+            .is_none_or(|limit| self.storage_used < limit)
+    }
+
+    /// Whether more audio is stored than the quota allows
+    #[must_use]
+    pub fn storage_exceeded(&self) -> bool {
+        self.quota
+            .storage
+            .is_some_and(|limit| self.storage_used > limit)
+    }
+}
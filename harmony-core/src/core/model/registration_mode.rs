@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Who may create an account
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationMode {
+    /// Anyone can register
+    #[default]
+    Open,
+    /// Only with an invite code
+    Invite,
+    /// Anyone can register, but an admin approves the account before it can log in.
+    /// An invite code skips the approval
+    Approval,
+    /// Nobody can register
+    Closed,
+}
+
+impl RegistrationMode {
+    #[must_use]
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.trim() {
+            "open" => Some(RegistrationMode::Open),
+            "invite" => Some(RegistrationMode::Invite),
+            "approval" => Some(RegistrationMode::Approval),
+            "closed" => Some(RegistrationMode::Closed),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::validation::result::ValidationError;
+
+/// Something a user may be allowed to do, beyond listening and managing their own things
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Download new songs onto the server
+    AddSongs,
+    /// Remove songs, including ones added by others
+    DeleteAnySong,
+    /// Change and remove playlists and share codes of others
+    ManageAnyPlaylist,
+    /// Create and remove stations, and schedule what they play
+    ManageStations,
+    /// Change the role of other users
+    ManageUsers,
+}
+
+/// What a user is trusted with, each role can do everything the one before it can
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Only listens, can still keep playlists of their own
+    Listener,
+    /// Adds songs, the role new users get
+    #[default]
+    Contributor,
+    /// Cleans up songs and playlists of others
+    Moderator,
+    /// Runs the server
+    Admin,
+}
+
+impl Role {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Listener => "listener",
+            Role::Contributor => "contributor",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn parse(role: &str) -> Result<Self, ValidationError> {
+        match role.trim() {
+            "listener" => Ok(Role::Listener),
+            "contributor" => Ok(Role::Contributor),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(ValidationError::InvalidRole(format!(
+                "Unknown role \"{role}\", expected listener, contributor, moderator or admin"
+            ))),
+        }
+    }
+
+    #[must_use]
+    pub fn permissions(self) -> &'static [Permission] {
+        match self {
+            Role::Listener => &[],
+            Role::Contributor => &[Permission::AddSongs],
+            Role::Moderator => &[
+                Permission::AddSongs,
+                Permission::DeleteAnySong,
+                Permission::ManageAnyPlaylist,
+            ],
+            Role::Admin => &[
+                Permission::AddSongs,
+                Permission::DeleteAnySong,
+                Permission::ManageAnyPlaylist,
+                Permission::ManageStations,
+                Permission::ManageUsers,
+            ],
+        }
+    }
+
+    #[must_use]
+    pub fn allows(self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Duration, Utc};
+use harmony_data::{model::schedule::ScheduleModel, result::Error};
+use serde::{Deserialize, Serialize};
+
+use crate::core::validation::validated_types::{CronRule, TimeZone};
+use crate::result::ServerError;
+
+/// Plays a playlist on a station or jukebox during the windows that start
+/// whenever the rule matches in the schedule's time zone
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Schedule {
+    pub id: i32,
+    pub name: String,
+    pub playlist: i32,
+    pub station: Option<i32>,
+    pub jukebox: Option<String>,
+    /// A cron expression for the start of each window
+    pub rule: String,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    /// Decides between overlapping windows, the highest wins
+    pub priority: i32,
+    /// Unset once the user is deleted
+    pub created_by: Option<i32>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Schedule {
+    pub fn get_list() -> Result<Vec<Self>, ServerError> {
+        match ScheduleModel::get_list() {
+            Ok(schedules) => Ok(Self::from_models(&schedules)),
+            Err(_) => Err(ServerError::Database("Failed to get schedules".to_string())),
+        }
+    }
+
+    pub fn get_by_id(schedule_id: i32) -> Result<Self, ServerError> {
+        let schedule = match ScheduleModel::get_by_id(schedule_id) {
+            Ok(schedule) => schedule,
+            Err(error) => match error {
+                Error::NotFound => return Err(ServerError::NotFound),
+                _ => return Err(ServerError::Database("Failed to get schedule".to_string())),
+            },
+        };
+
+        Ok(Self::from_model(&schedule))
+    }
+
+    pub fn get_by_station(station_id: i32) -> Result<Vec<Self>, ServerError> {
+        match ScheduleModel::get_by_station(station_id) {
+            Ok(schedules) => Ok(Self::from_models(&schedules)),
+            Err(_) => Err(ServerError::Database("Failed to get schedules".to_string())),
+        }
+    }
+
+    pub fn get_by_jukebox(jukebox_code: &str) -> Result<Vec<Self>, ServerError> {
+        match ScheduleModel::get_by_jukebox(jukebox_code) {
+            Ok(schedules) => Ok(Self::from_models(&schedules)),
+            Err(_) => Err(ServerError::Database("Failed to get schedules".to_string())),
+        }
+    }
+
+    /// Removes the schedules of a jukebox. Jukebox codes are handed out again once a
+    /// jukebox is gone, its schedules must not carry over to the next one
+    pub fn remove_by_jukebox(jukebox_code: &str) -> Result<usize, ServerError> {
+        ScheduleModel::remove_by_jukebox(jukebox_code)
+            .map_err(|_| ServerError::Database("Failed to remove schedules".to_string()))
+    }
+
+    pub fn remove(&self) -> Result<(), ServerError> {
+        match ScheduleModel::remove(self.id) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to remove schedule".to_string(),
+            )),
+        }
+    }
+
+    /// The window of this schedule that covers the given time, the latest one when windows overlap
+    #[must_use]
+    pub fn window_at(&self, time: DateTime<Utc>) -> Option<ScheduleWindow> {
+        // Stored schedules were validated when they were created
+        let rule = CronRule::parse(&self.rule).ok()?;
+        let timezone = TimeZone::parse(&self.timezone).ok()?;
+        let duration = Duration::minutes(i64::from(self.duration_minutes));
+
+        // Only windows that started less than a duration ago can still be running
+        let from = (time - duration).with_timezone(&timezone);
+        let start = rule
+            .after(&from)
+            .map(|start| start.with_timezone(&Utc))
+            .take_while(|start| *start <= time)
+            .last()?;
+
+        Some(ScheduleWindow {
+            start,
+            end: start + duration,
+        })
+    }
+
+    /// The schedule that decides what plays at the given time, if any
+    #[must_use]
+    pub fn in_effect(schedules: &[Self], time: DateTime<Utc>) -> Option<(&Self, ScheduleWindow)> {
+        Self::active(schedules, time).into_iter().next()
+    }
+
+    /// The schedules with a window at the given time, the one in effect first
+    #[must_use]
+    pub fn active(schedules: &[Self], time: DateTime<Utc>) -> Vec<(&Self, ScheduleWindow)> {
+        let mut active: Vec<(&Self, ScheduleWindow)> = schedules
+            .iter()
+            .filter_map(|schedule| Some((schedule, schedule.window_at(time)?)))
+            .collect();
+
+        // Between equal priorities the window that started last wins
+        active.sort_by_key(|(schedule, window)| {
+            (
+                std::cmp::Reverse(schedule.priority),
+                std::cmp::Reverse(window.start),
+            )
+        });
+        active
+    }
+
+    pub fn from_model(schedule_model: &ScheduleModel) -> Self {
+        Self {
+            id: schedule_model.id,
+            name: schedule_model.name.clone(),
+            playlist: schedule_model.playlist,
+            station: schedule_model.station,
+            jukebox: schedule_model.jukebox.clone(),
+            rule: schedule_model.rule.clone(),
+            duration_minutes: schedule_model.duration_minutes,
+            timezone: schedule_model.timezone.clone(),
+            priority: schedule_model.priority,
+            created_by: schedule_model.created_by,
+            created: schedule_model.created,
+        }
+    }
+
+    pub fn from_models(schedule_models: &Vec<ScheduleModel>) -> Vec<Self> {
+        let mut result: Vec<Self> = Vec::new();
+
+        for model in schedule_models {
+            result.push(Self::from_model(model));
+        }
+
+        result
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use harmony_data::model::share_code::{NewShareCodeModel, ShareCodeModel};
+use harmony_data::result::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::result::ServerError;
+use crate::utils::short_code;
+
+/// The length of new share codes, 25^6 codes is plenty for a music library
+pub const SHARE_CODE_LENGTH: usize = 6;
+
+// Taken codes are retried with a new one, running out of tries means the space is nearly full
+const MAX_ATTEMPTS: usize = 8;
+
+/// What a share code points to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareTarget {
+    Song(i32),
+    Playlist(i32),
+}
+
+/// A short code that is easy to type on an in-game computer, resolving to a song or playlist
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShareCode {
+    pub code: String,
+    pub target: ShareTarget,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl ShareCode {
+    /// Looks up a code as typed by a user, ignoring case, spaces and dashes
+    pub fn get_by_code(code: &str) -> Result<Self, ServerError> {
+        match ShareCodeModel::get_by_code(&short_code::normalize(code)) {
+            Ok(share_code) => Self::from_model(&share_code),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to get share code".to_string(),
+            )),
+        }
+    }
+
+    /// The code of a song or playlist, `ServerError::NotFound` when it was revoked
+    pub fn get_by_target(target: ShareTarget) -> Result<Self, ServerError> {
+        let result = match target {
+            ShareTarget::Song(song) => ShareCodeModel::get_by_song(song),
+            ShareTarget::Playlist(playlist) => ShareCodeModel::get_by_playlist(playlist),
+        };
+
+        match result {
+            Ok(share_code) => Self::from_model(&share_code),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to get share code".to_string(),
+            )),
+        }
+    }
+
+    /// Gives a song or playlist a new code, the old code stops working
+    pub fn generate(target: ShareTarget) -> Result<Self, ServerError> {
+        let (song, playlist) = match target {
+            ShareTarget::Song(song) => (Some(song), None),
+            ShareTarget::Playlist(playlist) => (None, Some(playlist)),
+        };
+
+        for _ in 0..MAX_ATTEMPTS {
+            let result = ShareCodeModel::replace(&NewShareCodeModel {
+                code: short_code::generate(SHARE_CODE_LENGTH),
+                song,
+                playlist,
+            });
+
+            match result {
+                Ok(share_code) => return Self::from_model(&share_code),
+                Err(Error::UniqueViolation(_)) => {}
+                Err(_) => {
+                    return Err(ServerError::CouldNotCreate(
+                        "Failed to create share code".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Err(ServerError::CouldNotCreate(
+            "Could not find a free share code".to_string(),
+        ))
+    }
+
+    /// Gives a song or playlist that was just created its code. Failing does not fail
+    /// the creation, the owner can generate a code for it later
+    pub fn assign(target: ShareTarget) {
+        Self::generate(target).ok();
+    }
+
+    /// Removes the code of a song or playlist, until a new one is generated
+    pub fn revoke(target: ShareTarget) -> Result<(), ServerError> {
+        let result = match target {
+            ShareTarget::Song(song) => ShareCodeModel::remove_by_song(song),
+            ShareTarget::Playlist(playlist) => ShareCodeModel::remove_by_playlist(playlist),
+        };
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to remove share code".to_string(),
+            )),
+        }
+    }
+
+    pub fn from_model(share_code_model: &ShareCodeModel) -> Result<Self, ServerError> {
+        let target = match (share_code_model.song, share_code_model.playlist) {
+            (Some(song), None) => ShareTarget::Song(song),
+            (None, Some(playlist)) => ShareTarget::Playlist(playlist),
+            _ => {
+                return Err(ServerError::Database(
+                    "Share code has no single target".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            code: share_code_model.code.clone(),
+            target,
+            created: share_code_model.created,
+        })
+    }
+}
@@ -3,16 +3,21 @@ use harmony_data::{model::song::SongModel, result::Error};
 use serde::{Deserialize, Serialize};
 
 use crate::result::ServerError;
+use crate::utils::song_file;
+
+use super::encoding::EncodingProfile;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Song {
     pub id: i32,
     pub name: String,
     pub author: Option<String>,
-    pub added_by: i32,
+    /// Unset once the user is deleted
+    pub added_by: Option<i32>,
     pub youtube_url: String,
     pub file_id: String,
     pub created: Option<DateTime<Utc>>,
+    pub encoding: EncodingProfile,
 }
 
 impl Song {
@@ -44,6 +49,33 @@ impl Song {
         Ok(Self::from_model(&song))
     }
 
+    pub fn get_by_file_id(file_id: &str) -> Result<Self, ServerError> {
+        let song = match SongModel::get_by_file_id(file_id) {
+            Ok(song) => song,
+            Err(error) => match error {
+                Error::NotFound => return Err(ServerError::NotFound),
+                _ => return Err(ServerError::Database("Failed to get song".to_string())),
+            },
+        };
+
+        Ok(Self::from_model(&song))
+    }
+
+    /// Removes the song from the server, along with its file and its place in playlists
+    pub fn remove(&self) -> Result<(), ServerError> {
+        match SongModel::remove(self.id) {
+            Ok(()) => {}
+            Err(Error::NotFound) => return Err(ServerError::NotFound),
+            Err(_) => return Err(ServerError::Database("Failed to remove song".to_string())),
+        }
+
+        // The row is what matters, a file that is already gone is fine
+        match song_file::remove_song(&self.file_id) {
+            Err(ServerError::FileNotFound) => Ok(()),
+            result => result,
+        }
+    }
+
     pub fn from_model(song_model: &SongModel) -> Self {
         Self {
             id: song_model.id,
@@ -53,6 +85,13 @@ impl Song {
             youtube_url: song_model.youtube_url.clone(),
             file_id: song_model.file_id.clone(),
             created: song_model.created,
+            // Rows only ever hold profiles the converter wrote, fall back to it otherwise
+            encoding: EncodingProfile::new(
+                &song_model.codec,
+                song_model.sample_rate,
+                song_model.channels,
+            )
+            .unwrap_or_default(),
         }
     }
 
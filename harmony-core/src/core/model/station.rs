@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use harmony_data::{model::station::StationModel, result::Error};
+use serde::{Deserialize, Serialize};
+
+use crate::core::playback::shuffle::shuffle;
+use crate::result::ServerError;
+
+use super::{playlist::Playlist, schedule::Schedule, song::Song};
+
+/// What a station plays during a stretch of time, its own playlist or a scheduled one
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Program {
+    pub playlist: Option<i32>,
+    /// The schedule that put the program on air, `None` for the station's own playlist
+    pub schedule: Option<i32>,
+    /// The program's timeline counts from here
+    pub started: DateTime<Utc>,
+    /// Who picked the playlist, it only plays while it is public or still theirs
+    #[serde(skip)]
+    pub chosen_by: Option<i32>,
+}
+
+/// A radio station that plays a playlist, or the whole library, around the clock
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Station {
+    pub id: i32,
+    pub name: String,
+    /// The playlist the station plays, the whole library when not set
+    pub playlist: Option<i32>,
+    pub shuffle: bool,
+    /// Unset once the user is deleted
+    pub created_by: Option<i32>,
+    /// When the station went on air, its timeline counts from here
+    pub started: DateTime<Utc>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl Station {
+    pub fn get_list() -> Result<Vec<Self>, ServerError> {
+        match StationModel::get_list() {
+            Ok(stations) => Ok(Self::from_models(&stations)),
+            Err(_) => Err(ServerError::Database("Failed to get stations".to_string())),
+        }
+    }
+
+    pub fn get_by_id(station_id: i32) -> Result<Self, ServerError> {
+        let station = match StationModel::get_by_id(station_id) {
+            Ok(station) => station,
+            Err(error) => match error {
+                Error::NotFound => return Err(ServerError::NotFound),
+                _ => return Err(ServerError::Database("Failed to get station".to_string())),
+            },
+        };
+
+        Ok(Self::from_model(&station))
+    }
+
+    pub fn remove(&self) -> Result<(), ServerError> {
+        match StationModel::remove(self.id) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to remove station".to_string(),
+            )),
+        }
+    }
+
+    /// The program on air at the given time
+    pub fn program_at(&self, time: DateTime<Utc>) -> Result<Program, ServerError> {
+        let schedules = Schedule::get_by_station(self.id)?;
+
+        Ok(match Schedule::in_effect(&schedules, time) {
+            Some((schedule, window)) => Program {
+                playlist: Some(schedule.playlist),
+                schedule: Some(schedule.id),
+                started: window.start,
+                chosen_by: schedule.created_by,
+            },
+            None => Program {
+                playlist: self.playlist,
+                schedule: None,
+                started: self.started,
+                chosen_by: self.created_by,
+            },
+        })
+    }
+
+    /// The songs the station plays during the program, in the order it plays them
+    pub fn get_tracks(&self, program: &Program) -> Result<Vec<Song>, ServerError> {
+        let mut tracks = match program.playlist {
+            Some(playlist_id) => {
+                Playlist::get_visible(playlist_id, program.chosen_by)?.get_songs()?
+            }
+            None => {
+                let mut songs = Song::get_list()?;
+                songs.sort_by_key(|song| song.id);
+                songs
+            }
+        };
+
+        if self.shuffle {
+            // Seeded by the station and program so the order survives restarts
+            let seed = (u64::from(self.id.unsigned_abs()) << 32)
+                ^ program.started.timestamp_millis().unsigned_abs();
+            shuffle(&mut tracks, seed);
+        }
+
+        Ok(tracks)
+    }
+
+    pub fn from_model(station_model: &StationModel) -> Self {
+        Self {
+            id: station_model.id,
+            name: station_model.name.clone(),
+            playlist: station_model.playlist,
+            shuffle: station_model.shuffle,
+            created_by: station_model.created_by,
+            started: station_model.started,
+            created: station_model.created,
+        }
+    }
+
+    pub fn from_models(station_models: &Vec<StationModel>) -> Vec<Self> {
+        let mut result: Vec<Self> = Vec::new();
+
+        for model in station_models {
+            result.push(Self::from_model(model));
+        }
+
+        result
+    }
+}
@@ -1,15 +1,45 @@
 use chrono::{DateTime, Utc};
+use harmony_data::model::{playlist::PlaylistModel, song::SongModel};
 use harmony_data::{model::user::UserModel, result::Error};
 use serde::{Deserialize, Serialize};
 
+use crate::core::validation::validated_types::{Email, Password, Username};
 use crate::result::ServerError;
+use crate::utils::password;
+
+use super::auth_session::AuthSession;
+use super::role::{Permission, Role};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct User {
     pub id: i32,
     pub name: String,
-    pub admin: bool,
+    pub role: Role,
     pub created: Option<DateTime<Utc>>,
+    /// Only shown to the roles that manage users, through `UserDetails`
+    #[serde(skip)]
+    pub suspended: Option<DateTime<Utc>>,
+    /// Set while the account waits for an admin to approve it
+    pub pending: bool,
+    /// Only shown to the user themselves and the roles that manage users
+    #[serde(skip)]
+    pub email: Option<String>,
+    /// When the address was proven to be theirs, unset until then
+    #[serde(skip)]
+    pub email_verified: Option<DateTime<Utc>>,
+}
+
+/// A user as the roles that manage users see them
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserDetails {
+    #[serde(flatten)]
+    pub user: User,
+    pub suspended: Option<DateTime<Utc>>,
+    pub suspension_reason: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: Option<DateTime<Utc>>,
+    pub songs: i64,
+    pub playlists: i64,
 }
 
 impl User {
@@ -44,12 +74,234 @@ impl User {
         Ok(Self::from_models(&users))
     }
 
+    /// Gives the user another role, their permissions change on their next request
+    pub fn set_role(user_id: i32, role: Role) -> Result<Self, ServerError> {
+        match UserModel::set_role(user_id, role.as_str()) {
+            Ok(user) => Ok(Self::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to set role".to_string())),
+        }
+    }
+
+    pub fn get_details_list() -> Result<Vec<UserDetails>, ServerError> {
+        let Ok(users) = UserModel::get_list() else {
+            return Err(ServerError::Database("Failed to get users".to_string()));
+        };
+
+        users
+            .iter()
+            .map(|user| {
+                let songs = SongModel::count_by_user(user.id);
+                let playlists = PlaylistModel::count_by_user(user.id);
+                match (songs, playlists) {
+                    (Ok(songs), Ok(playlists)) => Ok(UserDetails {
+                        user: Self::from_model(user),
+                        suspended: user.suspended,
+                        suspension_reason: user.suspension_reason.clone(),
+                        email: user.email.clone(),
+                        email_verified: user.email_verified,
+                        songs,
+                        playlists,
+                    }),
+                    _ => Err(ServerError::Database(
+                        "Failed to get user details".to_string(),
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    /// The accounts waiting for approval, oldest first
+    pub fn get_pending() -> Result<Vec<Self>, ServerError> {
+        match UserModel::get_pending() {
+            Ok(users) => Ok(Self::from_models(&users)),
+            Err(_) => Err(ServerError::Database(
+                "Failed to get pending users".to_string(),
+            )),
+        }
+    }
+
+    pub fn approve(user_id: i32) -> Result<Self, ServerError> {
+        match UserModel::approve(user_id) {
+            Ok(user) => Ok(Self::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to approve user".to_string())),
+        }
+    }
+
+    /// Removes an account that was waiting for approval
+    pub fn reject(user_id: i32) -> Result<(), ServerError> {
+        match UserModel::reject(user_id) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to reject user".to_string())),
+        }
+    }
+
+    /// Suspends a user and ends their sessions, device tokens stop working until it is lifted
+    pub fn suspend(user_id: i32, reason: Option<&str>) -> Result<(), ServerError> {
+        match UserModel::set_suspended(user_id, Some(Utc::now()), reason) {
+            Ok(_) => {}
+            Err(Error::NotFound) => return Err(ServerError::NotFound),
+            Err(_) => return Err(ServerError::Database("Failed to suspend user".to_string())),
+        }
+
+        AuthSession::remove_all(user_id)?;
+        Ok(())
+    }
+
+    pub fn unsuspend(user_id: i32) -> Result<(), ServerError> {
+        match UserModel::set_suspended(user_id, None, None) {
+            Ok(_) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database(
+                "Failed to unsuspend user".to_string(),
+            )),
+        }
+    }
+
+    /// Replaces the password of a user with a temporary one and ends their sessions,
+    /// returning the temporary password so it can be handed to them
+    pub fn reset_password(user_id: i32) -> Result<String, ServerError> {
+        let temporary = password::generate_temporary();
+
+        match UserModel::set_hash(user_id, &password::hash(&temporary)?) {
+            Ok(()) => {}
+            Err(Error::NotFound) => return Err(ServerError::NotFound),
+            Err(_) => {
+                return Err(ServerError::Database(
+                    "Failed to reset password".to_string(),
+                ))
+            }
+        }
+
+        AuthSession::remove_all(user_id)?;
+        Ok(temporary)
+    }
+
+    /// Sets a new password, the caller checks the old one first
+    pub fn set_password(user_id: i32, new_password: &Password) -> Result<(), ServerError> {
+        match UserModel::set_hash(user_id, &password::hash(new_password.value())?) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to set password".to_string())),
+        }
+    }
+
+    /// Renames a user, returns `ServerError::AlreadyExists` when someone else has the name.
+    /// Users may change how their own name is capitalised
+    pub fn rename(user_id: i32, new_name: &Username) -> Result<Self, ServerError> {
+        match UserModel::name_taken(user_id, new_name.value()) {
+            Ok(false) => {}
+            Ok(true) => return Err(ServerError::AlreadyExists),
+            Err(_) => return Err(ServerError::Database("Failed to check name".to_string())),
+        }
+
+        match UserModel::set_name(user_id, new_name.value()) {
+            Ok(user) => Ok(Self::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(Error::UniqueViolation(_)) => Err(ServerError::AlreadyExists),
+            Err(_) => Err(ServerError::Database("Failed to rename user".to_string())),
+        }
+    }
+
+    /// Gives the user another email address, or takes it away.
+    /// A new address has to be verified before it can be used
+    pub fn set_email(user_id: i32, email: Option<&Email>) -> Result<(), ServerError> {
+        match UserModel::set_email(user_id, email.map(|email| email.value().as_str())) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to set email".to_string())),
+        }
+    }
+
+    /// Marks the address as verified as long as the user still has it,
+    /// returns `ServerError::AlreadyExists` when another account verified it first
+    pub fn verify_email(user_id: i32, email: &str) -> Result<Self, ServerError> {
+        match UserModel::get_by_verified_email(email) {
+            Ok(user) if user.id != user_id => return Err(ServerError::AlreadyExists),
+            Ok(_) | Err(Error::NotFound) => {}
+            Err(_) => return Err(ServerError::Database("Failed to check email".to_string())),
+        }
+
+        match UserModel::verify_email(user_id, email) {
+            Ok(user) => Ok(Self::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(Error::UniqueViolation(_)) => Err(ServerError::AlreadyExists),
+            Err(_) => Err(ServerError::Database("Failed to verify email".to_string())),
+        }
+    }
+
+    /// Finds the user that verified the address, however it is written
+    pub fn get_by_verified_email(email: &str) -> Result<Self, ServerError> {
+        match UserModel::get_by_verified_email(email) {
+            Ok(user) => Ok(Self::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to get user".to_string())),
+        }
+    }
+
+    /// Finds a user by their name, however it is capitalised
+    pub fn get_by_name(name: &str) -> Result<Self, ServerError> {
+        match UserModel::get_by_name(name) {
+            Ok(user) => Ok(Self::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to get user".to_string())),
+        }
+    }
+
+    /// The address mail can be sent to, only once it is verified
+    #[must_use]
+    pub fn verified_email(&self) -> Option<&str> {
+        self.email_verified.and(self.email.as_deref())
+    }
+
+    /// Returns whether the user is the only admin left, who must not disappear
+    pub fn is_last_admin(&self) -> Result<bool, ServerError> {
+        if self.role != Role::Admin {
+            return Ok(false);
+        }
+
+        match UserModel::count_by_role(Role::Admin.as_str()) {
+            Ok(count) => Ok(count <= 1),
+            Err(_) => Err(ServerError::Database("Failed to count admins".to_string())),
+        }
+    }
+
+    /// Removes a user with their playlists, songs they added stay on the server
+    pub fn remove(user_id: i32) -> Result<(), ServerError> {
+        match UserModel::remove(user_id) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to remove user".to_string())),
+        }
+    }
+
+    /// Removes a user like `remove`, but hands their playlists to another user first
+    pub fn remove_transferring(user_id: i32, new_owner: i32) -> Result<(), ServerError> {
+        match UserModel::remove_transferring(user_id, new_owner) {
+            Ok(()) => Ok(()),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(_) => Err(ServerError::Database("Failed to remove user".to_string())),
+        }
+    }
+
+    #[must_use]
+    pub fn can(&self, permission: Permission) -> bool {
+        self.role.allows(permission)
+    }
+
     pub fn from_model(user_model: &UserModel) -> Self {
         Self {
             id: user_model.id,
             name: user_model.name.clone(),
-            admin: user_model.admin,
+            // Rows are limited to known roles by a check, fall back to the default otherwise
+            role: Role::parse(&user_model.role).unwrap_or_default(),
             created: user_model.created,
+            suspended: user_model.suspended,
+            pending: user_model.pending,
+            email: user_model.email.clone(),
+            email_verified: user_model.email_verified,
         }
     }
 
@@ -0,0 +1,8 @@
+pub mod broadcast;
+pub mod dfpwm;
+pub mod jukebox;
+pub mod mixer;
+pub mod queue;
+pub mod room;
+pub mod shuffle;
+pub mod timeline;
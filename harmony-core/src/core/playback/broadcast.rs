@@ -0,0 +1,93 @@
+use crate::core::model::song::Song;
+
+use super::timeline::{Timeline, TimelinePosition};
+
+/// A list of tracks played on a shared timeline, listeners only ever read the position
+/// instead of moving it. Tracks come with their duration, those without audio get no time
+#[derive(Debug)]
+pub struct Broadcast {
+    tracks: Vec<Song>,
+    timeline: Timeline,
+}
+
+impl Broadcast {
+    /// Starts playing the first track at the given time
+    #[must_use]
+    pub fn new(tracks: Vec<(Song, u64)>, repeat: bool, start: i64) -> Self {
+        let (tracks, durations) = tracks.into_iter().unzip();
+        let timeline = Timeline::new(durations, repeat, start);
+        Self { tracks, timeline }
+    }
+
+    /// Starts playing the given song part way in, or the first track when it is not in the list
+    #[must_use]
+    pub fn starting_at(
+        tracks: Vec<(Song, u64)>,
+        repeat: bool,
+        song_id: i32,
+        position_ms: u64,
+        now: i64,
+    ) -> Self {
+        let (tracks, durations): (Vec<Song>, Vec<u64>) = tracks.into_iter().unzip();
+        let (index, position_ms) = tracks
+            .iter()
+            .position(|song| song.id == song_id)
+            .map_or((0, 0), |index| (index, position_ms));
+
+        let timeline = Timeline::starting_at(durations, repeat, index, position_ms, now);
+        Self { tracks, timeline }
+    }
+
+    #[must_use]
+    pub fn tracks(&self) -> &[Song] {
+        &self.tracks
+    }
+
+    #[must_use]
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    #[must_use]
+    pub fn position(&self, now: i64) -> Option<TimelinePosition> {
+        self.timeline.position(now)
+    }
+
+    /// The song playing at the given time and how far into it playback is
+    #[must_use]
+    pub fn current(&self, now: i64) -> Option<(TimelinePosition, &Song)> {
+        let position = self.position(now)?;
+        Some((position, self.tracks.get(position.index)?))
+    }
+
+    /// The song after the one at the given position, wrapping around when repeating
+    #[must_use]
+    pub fn next(&self, position: Option<TimelinePosition>) -> Option<&Song> {
+        let position = position?;
+
+        match self.tracks.get(position.index + 1) {
+            None if self.timeline.repeats() => self.tracks.first(),
+            next => next,
+        }
+    }
+
+    pub fn pause(&mut self, now: i64) {
+        self.timeline.pause(now);
+    }
+
+    pub fn resume(&mut self, now: i64) {
+        self.timeline.resume(now);
+    }
+
+    pub fn seek(&mut self, now: i64, position_ms: u64) {
+        self.timeline.seek(now, position_ms);
+    }
+
+    pub fn skip(&mut self, now: i64) {
+        self.timeline.skip(now);
+    }
+
+    pub fn previous(&mut self, now: i64) {
+        self.timeline.previous(now);
+    }
+}
@@ -0,0 +1,129 @@
+// DFPWM1a as implemented by CC:Tweaked, so audio we encode decodes
+// the same way on a speaker as audio converted by ffmpeg
+const PREC: i32 = 10;
+const PREC_POW: i32 = 1 << PREC;
+const PREC_POW_HALF: i32 = 1 << (PREC - 1);
+const STRENGTH_MIN: i32 = 2 << (PREC - 8);
+const LPF_STRENGTH: i32 = 140;
+
+/// The number of samples stored in one byte of DFPWM
+pub const SAMPLES_PER_BYTE: usize = 8;
+
+/// The adaptive predictor shared by the encoder and the decoder
+#[derive(Debug, Clone, Default)]
+struct Predictor {
+    charge: i32,
+    strength: i32,
+    previous_bit: bool,
+}
+
+impl Predictor {
+    fn predict(&mut self, bit: bool) -> i32 {
+        let target = if bit { 127 } else { -128 };
+        let mut charge =
+            self.charge + ((self.strength * (target - self.charge) + PREC_POW_HALF) >> PREC);
+        if charge == self.charge && charge != target {
+            charge += if bit { 1 } else { -1 };
+        }
+
+        let same = bit == self.previous_bit;
+        let z = if same { PREC_POW - 1 } else { 0 };
+        let mut strength = self.strength;
+        if strength != z {
+            strength += if same { 1 } else { -1 };
+        }
+
+        self.charge = charge;
+        self.strength = strength.max(STRENGTH_MIN);
+        self.previous_bit = bit;
+        charge
+    }
+}
+
+/// Turns signed 8-bit PCM into DFPWM, keeping its state between calls
+/// so a stream can be encoded a piece at a time
+#[derive(Debug, Clone, Default)]
+pub struct DfpwmEncoder {
+    predictor: Predictor,
+    previous_charge: i32,
+}
+
+impl DfpwmEncoder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encodes the samples, a trailing partial byte is padded with silence
+    pub fn encode(&mut self, samples: &[i8]) -> Vec<u8> {
+        samples
+            .chunks(SAMPLES_PER_BYTE)
+            .map(|chunk| {
+                let mut byte = 0u8;
+                for i in 0..SAMPLES_PER_BYTE {
+                    let level = i32::from(chunk.get(i).copied().unwrap_or(0));
+                    let bit = level > self.previous_charge
+                        || (level == self.previous_charge && level == 127);
+                    byte = (byte >> 1) | if bit { 0x80 } else { 0 };
+                    self.previous_charge = self.predictor.predict(bit);
+                }
+                byte
+            })
+            .collect()
+    }
+}
+
+/// Turns DFPWM into signed 8-bit PCM, keeping its state between calls
+#[derive(Debug, Clone, Default)]
+pub struct DfpwmDecoder {
+    predictor: Predictor,
+    low_pass_charge: i32,
+    previous_charge: i32,
+    previous_bit: bool,
+}
+
+impl DfpwmDecoder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes a single byte into its eight samples
+    pub fn decode_byte(&mut self, byte: u8) -> [i8; SAMPLES_PER_BYTE] {
+        let mut samples = [0; SAMPLES_PER_BYTE];
+        let mut byte = byte;
+
+        for sample in &mut samples {
+            let bit = byte & 1 != 0;
+            let charge = self.predictor.predict(bit);
+
+            // Smooth out the step when the signal changes direction
+            let antijerk = if bit == self.previous_bit {
+                charge
+            } else {
+                (charge + self.previous_charge + 1) >> 1
+            };
+
+            self.previous_charge = charge;
+            self.previous_bit = bit;
+            self.low_pass_charge +=
+                ((antijerk - self.low_pass_charge) * LPF_STRENGTH + PREC_POW_HALF) >> PREC;
+            *sample = clamp_sample(self.low_pass_charge);
+            byte >>= 1;
+        }
+
+        samples
+    }
+
+    pub fn decode(&mut self, data: &[u8]) -> Vec<i8> {
+        data.iter()
+            .flat_map(|&byte| self.decode_byte(byte))
+            .collect()
+    }
+}
+
+/// Clamps a mixed level to the range of a sample
+#[must_use]
+pub fn clamp_sample(level: i32) -> i8 {
+    i8::try_from(level.clamp(-128, 127)).unwrap_or_default()
+}
@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::core::model::song::Song;
+use crate::result::ServerError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Vote {
+    Up,
+    Down,
+    /// Takes back an earlier vote
+    None,
+}
+
+/// A song waiting in the jukebox queue
+#[derive(Debug, Clone)]
+pub struct JukeboxEntry {
+    pub id: u64,
+    pub song: Song,
+    pub duration_ms: u64,
+    pub added_by: i32,
+    votes: HashMap<i32, Vote>,
+}
+
+impl JukeboxEntry {
+    /// Upvotes minus downvotes
+    #[must_use]
+    pub fn score(&self) -> i64 {
+        self.votes
+            .values()
+            .map(|vote| match vote {
+                Vote::Up => 1,
+                Vote::Down => -1,
+                Vote::None => 0,
+            })
+            .sum()
+    }
+
+    #[must_use]
+    pub fn vote_of(&self, user_id: i32) -> Vote {
+        self.votes.get(&user_id).copied().unwrap_or(Vote::None)
+    }
+}
+
+/// The song the jukebox is playing
+#[derive(Debug, Clone)]
+pub struct NowPlaying {
+    pub song: Song,
+    pub duration_ms: u64,
+    /// The user who queued the song, `None` when it came from the fallback playlist
+    pub added_by: Option<i32>,
+    /// The server time the song started at, in milliseconds since the unix epoch
+    pub started_at: i64,
+    skip_votes: HashSet<i32>,
+}
+
+impl NowPlaying {
+    #[must_use]
+    pub fn skip_votes(&self) -> usize {
+        self.skip_votes.len()
+    }
+
+    #[must_use]
+    pub fn position_ms(&self, now: i64) -> u64 {
+        u64::try_from(now.saturating_sub(self.started_at))
+            .unwrap_or(0)
+            .min(self.duration_ms)
+    }
+
+    fn ends_at(&self) -> i64 {
+        self.started_at
+            .saturating_add(i64::try_from(self.duration_ms).unwrap_or(i64::MAX))
+    }
+}
+
+// Songs that play in a loop while nobody has queued anything
+#[derive(Debug, Clone)]
+struct Fallback {
+    id: i32,
+    tracks: Vec<(Song, u64)>,
+    next: usize,
+}
+
+/// A queue anyone can add songs to, played in order of votes on the server clock.
+/// Songs with the same score play in the order they were added
+#[derive(Debug, Clone)]
+pub struct Jukebox {
+    queue: Vec<JukeboxEntry>,
+    playing: Option<NowPlaying>,
+    fallback: Option<Fallback>,
+    skip_threshold: usize,
+    next_id: u64,
+}
+
+impl Jukebox {
+    /// Creates an empty jukebox, the current song is skipped once `skip_threshold` users
+    /// voted to skip it and queued songs are dropped once they are that many votes down
+    #[must_use]
+    pub fn new(skip_threshold: usize) -> Self {
+        Self {
+            queue: Vec::new(),
+            playing: None,
+            fallback: None,
+            skip_threshold: skip_threshold.max(1),
+            next_id: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn skip_threshold(&self) -> usize {
+        self.skip_threshold
+    }
+
+    /// The queued songs in the order they will play
+    #[must_use]
+    pub fn queue(&self) -> &[JukeboxEntry] {
+        &self.queue
+    }
+
+    #[must_use]
+    pub fn playing(&self) -> Option<&NowPlaying> {
+        self.playing.as_ref()
+    }
+
+    /// Moves on to the queued songs whose turn has come by the given time.
+    /// Every change calls this first, readers should too
+    pub fn update(&mut self, now: i64) {
+        loop {
+            let start = match &self.playing {
+                Some(playing) if playing.ends_at() > now => return,
+                // The next song starts right when the last one ended, even if nobody asked since
+                Some(playing) => playing.ends_at(),
+                None => now,
+            };
+
+            self.playing = None;
+
+            let (song, duration_ms, added_by) = if self.queue.is_empty() {
+                let Some((song, duration_ms)) = self.next_fallback() else {
+                    return;
+                };
+
+                (song, duration_ms, None)
+            } else {
+                let entry = self.queue.remove(0);
+                (entry.song, entry.duration_ms, Some(entry.added_by))
+            };
+
+            self.playing = Some(NowPlaying {
+                song,
+                duration_ms,
+                added_by,
+                started_at: start,
+                skip_votes: HashSet::new(),
+            });
+        }
+    }
+
+    fn next_fallback(&mut self) -> Option<(Song, u64)> {
+        let fallback = self.fallback.as_mut()?;
+        let track = fallback.tracks.get(fallback.next)?.clone();
+        fallback.next = (fallback.next + 1) % fallback.tracks.len();
+        Some(track)
+    }
+
+    /// The id the current fallback playlist was set with
+    #[must_use]
+    pub fn fallback_id(&self) -> Option<i32> {
+        self.fallback.as_ref().map(|fallback| fallback.id)
+    }
+
+    /// Sets the songs to play in a loop while the queue is empty, with their durations.
+    /// A fallback song that is playing makes way for the new fallback right away
+    pub fn set_fallback(&mut self, fallback: Option<(i32, Vec<(Song, u64)>)>, now: i64) {
+        self.update(now);
+
+        self.fallback = fallback.map(|(id, tracks)| Fallback {
+            id,
+            // Songs without audio would make the loop spin without ever playing anything
+            tracks: tracks
+                .into_iter()
+                .filter(|(_, duration_ms)| *duration_ms > 0)
+                .collect(),
+            next: 0,
+        });
+
+        if self
+            .playing
+            .as_ref()
+            .is_some_and(|playing| playing.added_by.is_none())
+        {
+            self.playing = None;
+        }
+
+        self.update(now);
+    }
+
+    /// Adds a song to the end of its score group and returns the id of the entry
+    /// # Errors
+    /// `ServerError::AlreadyExists` when the song is already playing or queued
+    pub fn enqueue(
+        &mut self,
+        song: Song,
+        duration_ms: u64,
+        user_id: i32,
+        now: i64,
+    ) -> Result<u64, ServerError> {
+        self.update(now);
+
+        let playing = self
+            .playing
+            .as_ref()
+            .is_some_and(|playing| playing.song.id == song.id);
+        if playing || self.queue.iter().any(|entry| entry.song.id == song.id) {
+            return Err(ServerError::AlreadyExists);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queue.push(JukeboxEntry {
+            id,
+            song,
+            duration_ms,
+            added_by: user_id,
+            votes: HashMap::new(),
+        });
+        self.sort();
+        self.update(now);
+
+        Ok(id)
+    }
+
+    /// Records the vote of a user on a queued song
+    /// # Errors
+    /// `ServerError::NotFound` when the entry is not in the queue
+    pub fn vote(
+        &mut self,
+        entry_id: u64,
+        user_id: i32,
+        vote: Vote,
+        now: i64,
+    ) -> Result<(), ServerError> {
+        self.update(now);
+
+        let Some(entry) = self.queue.iter_mut().find(|entry| entry.id == entry_id) else {
+            return Err(ServerError::NotFound);
+        };
+
+        match vote {
+            Vote::None => entry.votes.remove(&user_id),
+            vote => entry.votes.insert(user_id, vote),
+        };
+
+        let threshold = i64::try_from(self.skip_threshold).unwrap_or(i64::MAX);
+        self.queue.retain(|entry| entry.score() > -threshold);
+        self.sort();
+
+        Ok(())
+    }
+
+    /// Takes a song out of the queue
+    /// # Errors
+    /// `ServerError::NotFound` when the entry is not in the queue
+    pub fn remove(&mut self, entry_id: u64, now: i64) -> Result<JukeboxEntry, ServerError> {
+        self.update(now);
+
+        let Some(index) = self.queue.iter().position(|entry| entry.id == entry_id) else {
+            return Err(ServerError::NotFound);
+        };
+
+        Ok(self.queue.remove(index))
+    }
+
+    /// Votes to skip the current song and returns whether it was skipped
+    /// # Errors
+    /// `ServerError::NotFound` when nothing is playing
+    pub fn vote_skip(&mut self, user_id: i32, now: i64) -> Result<bool, ServerError> {
+        self.update(now);
+
+        let Some(playing) = &mut self.playing else {
+            return Err(ServerError::NotFound);
+        };
+
+        playing.skip_votes.insert(user_id);
+        if playing.skip_votes.len() < self.skip_threshold {
+            return Ok(false);
+        }
+
+        self.skip(now);
+        Ok(true)
+    }
+
+    /// Stops the current song and starts the next one right away
+    pub fn skip(&mut self, now: i64) {
+        self.update(now);
+        self.playing = None;
+        self.update(now);
+    }
+
+    fn sort(&mut self) {
+        // Entry ids increase as songs are added, so they break ties between equal scores
+        self.queue
+            .sort_by_key(|entry| (std::cmp::Reverse(entry.score()), entry.id));
+    }
+}
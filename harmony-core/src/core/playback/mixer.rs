@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::ops::Deref;
+
+use super::dfpwm::{clamp_sample, DfpwmDecoder, DfpwmEncoder, SAMPLES_PER_BYTE};
+
+/// A track being decoded by the mixer
+#[derive(Debug)]
+struct Deck<T> {
+    data: T,
+    decoder: DfpwmDecoder,
+    /// The next byte to decode
+    position: usize,
+    buffer: [i8; SAMPLES_PER_BYTE],
+    /// The next sample to take from the buffer, the buffer is empty when it is full length
+    buffered: usize,
+}
+
+impl<T> Deck<T>
+where
+    T: Deref,
+    T::Target: AsRef<[u8]>,
+{
+    fn new(data: T, offset: usize) -> Self {
+        let position = offset.min(data.as_ref().len());
+        Self {
+            data,
+            decoder: DfpwmDecoder::new(),
+            position,
+            buffer: [0; SAMPLES_PER_BYTE],
+            buffered: SAMPLES_PER_BYTE,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.as_ref().len()
+    }
+
+    /// The samples left to play
+    fn remaining(&self) -> usize {
+        (self.len() - self.position) * SAMPLES_PER_BYTE + (SAMPLES_PER_BYTE - self.buffered)
+    }
+
+    /// The byte offset of the next sample, reads always stop at byte boundaries
+    fn offset(&self) -> usize {
+        if self.buffered < SAMPLES_PER_BYTE {
+            self.position - 1
+        } else {
+            self.position
+        }
+    }
+
+    fn sample(&mut self) -> i8 {
+        if self.buffered == SAMPLES_PER_BYTE {
+            let Some(&byte) = self.data.as_ref().get(self.position) else {
+                return 0;
+            };
+            self.buffer = self.decoder.decode_byte(byte);
+            self.buffered = 0;
+            self.position += 1;
+        }
+
+        let sample = self.buffer[self.buffered];
+        self.buffered += 1;
+        sample
+    }
+}
+
+/// Renders a sequence of DFPWM tracks into one continuous stream.
+/// The tail of each track is faded into the head of the next one and the
+/// result is encoded again, so a client can play it without gaps or clicks.
+/// Callers push the next track before reading so the mixer can start the
+/// crossfade in time, a track with nothing queued after it just plays out
+#[derive(Debug)]
+pub struct Mixer<T> {
+    decks: VecDeque<Deck<T>>,
+    encoder: DfpwmEncoder,
+    /// The length of a crossfade in bytes
+    crossfade: usize,
+    finished: usize,
+}
+
+impl<T> Mixer<T>
+where
+    T: Deref,
+    T::Target: AsRef<[u8]>,
+{
+    /// Creates a mixer that overlaps tracks by the given number of bytes,
+    /// with no crossfade tracks are simply joined without a gap
+    #[must_use]
+    pub fn new(crossfade: usize) -> Self {
+        Self {
+            decks: VecDeque::new(),
+            encoder: DfpwmEncoder::new(),
+            crossfade,
+            finished: 0,
+        }
+    }
+
+    /// Adds a track to play after the ones already queued
+    pub fn push(&mut self, data: T) {
+        self.push_from(data, 0);
+    }
+
+    /// Adds a track that starts playing at a byte offset
+    pub fn push_from(&mut self, data: T, offset: usize) {
+        self.decks.push_back(Deck::new(data, offset));
+    }
+
+    /// Whether the mixer is waiting for the track after the current one
+    #[must_use]
+    pub fn needs_track(&self) -> bool {
+        self.decks.len() < 2
+    }
+
+    /// The number of queued tracks, including the one playing
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.decks.len()
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.decks.is_empty()
+    }
+
+    /// The byte offset into the track that is playing
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.decks.front().map_or(0, Deck::offset)
+    }
+
+    /// Returns how many tracks ended since the last call
+    pub fn take_finished(&mut self) -> usize {
+        std::mem::take(&mut self.finished)
+    }
+
+    // The overlap of the current and the next track, never longer than either of them
+    fn fade_length(&self) -> usize {
+        match (self.decks.front(), self.decks.get(1)) {
+            (Some(current), Some(next)) => {
+                self.crossfade.min(current.len()).min(next.len()) * SAMPLES_PER_BYTE
+            }
+            _ => 0,
+        }
+    }
+
+    fn sample(&mut self) -> Option<i8> {
+        loop {
+            let remaining = self.decks.front()?.remaining();
+            if remaining == 0 {
+                self.decks.pop_front();
+                self.finished += 1;
+                continue;
+            }
+
+            let fade = self.fade_length();
+            if remaining > fade {
+                return self.decks.front_mut().map(Deck::sample);
+            }
+
+            // Linear fade, the current track goes from full volume to silence as the next one comes in
+            let outgoing = i32::from(self.decks[0].sample());
+            let incoming = i32::from(self.decks[1].sample());
+            let (remaining, fade) = (remaining as i64, fade as i64);
+            let mixed =
+                (i64::from(outgoing) * remaining + i64::from(incoming) * (fade - remaining)) / fade;
+            return Some(clamp_sample(i32::try_from(mixed).unwrap_or_default()));
+        }
+    }
+
+    /// Renders up to `max` bytes of the stream
+    pub fn read(&mut self, max: usize) -> Vec<u8> {
+        let mut samples = Vec::with_capacity(max.saturating_mul(SAMPLES_PER_BYTE).min(1 << 20));
+
+        while samples.len() < max.saturating_mul(SAMPLES_PER_BYTE) {
+            match self.sample() {
+                Some(sample) => samples.push(sample),
+                None => break,
+            }
+        }
+
+        // Move past a track that ended exactly at the end of this read
+        while self.decks.front().is_some_and(|deck| deck.remaining() == 0) {
+            self.decks.pop_front();
+            self.finished += 1;
+        }
+
+        self.encoder.encode(&samples)
+    }
+}
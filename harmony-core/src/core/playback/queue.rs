@@ -0,0 +1,97 @@
+use crate::core::model::song::Song;
+
+/// An ordered list of tracks with a cursor into the current one.
+/// The queue knows nothing about the audio itself, callers report
+/// how far they got and move on when a track runs out of data
+#[derive(Debug, Clone)]
+pub struct PlaybackQueue {
+    tracks: Vec<Song>,
+    index: usize,
+    offset: usize,
+    paused: bool,
+}
+
+impl PlaybackQueue {
+    #[must_use]
+    pub fn new(tracks: Vec<Song>) -> Self {
+        Self {
+            tracks,
+            index: 0,
+            offset: 0,
+            paused: false,
+        }
+    }
+
+    #[must_use]
+    pub fn tracks(&self) -> &[Song] {
+        &self.tracks
+    }
+
+    /// The track under the cursor, `None` once the queue is finished
+    #[must_use]
+    pub fn current(&self) -> Option<&Song> {
+        self.tracks.get(self.index)
+    }
+
+    /// The track after the current one
+    #[must_use]
+    pub fn next(&self) -> Option<&Song> {
+        self.tracks.get(self.index + 1)
+    }
+
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The byte offset into the current track
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.index >= self.tracks.len()
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Moves the cursor forward within the current track
+    pub fn advance(&mut self, bytes: usize) {
+        self.offset = self.offset.saturating_add(bytes);
+    }
+
+    /// Moves the cursor to a byte offset within the current track
+    pub fn seek(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    /// Starts the next track, returns `false` when there is none left
+    pub fn skip(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        self.index += 1;
+        self.offset = 0;
+        !self.is_finished()
+    }
+
+    /// Restarts the previous track, or the first one when already there
+    pub fn previous(&mut self) {
+        self.index = self.index.saturating_sub(1);
+        self.offset = 0;
+    }
+}
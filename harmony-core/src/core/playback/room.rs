@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+
+use crate::core::model::song::Song;
+
+use super::broadcast::Broadcast;
+
+/// A group of listeners playing the same queue at the same moment, controlled by its host
+#[derive(Debug)]
+pub struct Room {
+    host: i32,
+    members: HashSet<i32>,
+    broadcast: Broadcast,
+}
+
+impl Room {
+    /// Starts playing the tracks right away
+    #[must_use]
+    pub fn new(host: i32, tracks: Vec<(Song, u64)>, repeat: bool, now: i64) -> Self {
+        Self {
+            host,
+            members: HashSet::from([host]),
+            broadcast: Broadcast::new(tracks, repeat, now),
+        }
+    }
+
+    #[must_use]
+    pub fn host(&self) -> i32 {
+        self.host
+    }
+
+    #[must_use]
+    pub fn members(&self) -> &HashSet<i32> {
+        &self.members
+    }
+
+    #[must_use]
+    pub fn is_member(&self, user_id: i32) -> bool {
+        self.members.contains(&user_id)
+    }
+
+    #[must_use]
+    pub fn broadcast(&self) -> &Broadcast {
+        &self.broadcast
+    }
+
+    /// The shared timeline, only the host should move it
+    pub fn broadcast_mut(&mut self) -> &mut Broadcast {
+        &mut self.broadcast
+    }
+
+    pub fn join(&mut self, user_id: i32) {
+        self.members.insert(user_id);
+    }
+
+    pub fn leave(&mut self, user_id: i32) {
+        self.members.remove(&user_id);
+    }
+}
@@ -0,0 +1,17 @@
+/// Shuffles the items in an order that only depends on the seed,
+/// so every server computes the same order for the same seed
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed;
+
+    // Fisher-Yates driven by splitmix64, which is plenty for picking an order of songs
+    for i in (1..items.len()).rev() {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        let j = usize::try_from(z % (i as u64 + 1)).unwrap_or(0);
+        items.swap(i, j);
+    }
+}
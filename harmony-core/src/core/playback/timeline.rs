@@ -0,0 +1,164 @@
+/// Where a timeline is at a given moment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelinePosition {
+    /// The index of the track that is playing
+    pub index: usize,
+    /// How far into the track playback is
+    pub position_ms: u64,
+    /// The server time at which the track started, or would have if it was not paused
+    pub track_started_at: i64,
+}
+
+/// A playback timeline driven by the server clock instead of by reads, so every listener
+/// that asks at the same moment is told to play the same part of the same track.
+/// Times are milliseconds since the unix epoch
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    durations: Vec<u64>,
+    repeat: bool,
+    paused: bool,
+    // The position at the anchor time, playback moves forward from there
+    index: usize,
+    position_ms: u64,
+    anchor: i64,
+}
+
+impl Timeline {
+    /// Starts a timeline at the first track
+    #[must_use]
+    pub fn new(durations: Vec<u64>, repeat: bool, now: i64) -> Self {
+        Self::starting_at(durations, repeat, 0, 0, now)
+    }
+
+    /// Starts a timeline part way into one of its tracks
+    #[must_use]
+    pub fn starting_at(
+        durations: Vec<u64>,
+        repeat: bool,
+        index: usize,
+        position_ms: u64,
+        now: i64,
+    ) -> Self {
+        Self {
+            durations,
+            repeat,
+            paused: false,
+            index,
+            position_ms,
+            anchor: now,
+        }
+    }
+
+    #[must_use]
+    pub fn durations(&self) -> &[u64] {
+        &self.durations
+    }
+
+    #[must_use]
+    pub fn repeats(&self) -> bool {
+        self.repeat
+    }
+
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    #[must_use]
+    pub fn total_ms(&self) -> u64 {
+        self.durations.iter().sum()
+    }
+
+    /// The position at the given time, `None` once the last track has ended
+    #[must_use]
+    pub fn position(&self, now: i64) -> Option<TimelinePosition> {
+        let total = self.total_ms();
+        if self.index >= self.durations.len() || total == 0 {
+            return None;
+        }
+
+        let elapsed = if self.paused {
+            0
+        } else {
+            u64::try_from(now.saturating_sub(self.anchor)).unwrap_or(0)
+        };
+
+        let mut index = self.index;
+        let mut position_ms = self.position_ms.saturating_add(elapsed);
+
+        loop {
+            if index >= self.durations.len() {
+                if !self.repeat {
+                    return None;
+                }
+
+                // Whole passes over the queue do not change where we end up
+                index = 0;
+                position_ms %= total;
+            }
+
+            if position_ms < self.durations[index] {
+                break;
+            }
+
+            position_ms -= self.durations[index];
+            index += 1;
+        }
+
+        Some(TimelinePosition {
+            index,
+            position_ms,
+            track_started_at: now.saturating_sub(i64::try_from(position_ms).unwrap_or(i64::MAX)),
+        })
+    }
+
+    // Moves the anchor to the given time, keeping the current position
+    fn settle(&mut self, now: i64) {
+        match self.position(now) {
+            Some(position) => {
+                self.index = position.index;
+                self.position_ms = position.position_ms;
+            }
+            None => {
+                self.index = self.durations.len();
+                self.position_ms = 0;
+            }
+        }
+
+        self.anchor = now;
+    }
+
+    pub fn pause(&mut self, now: i64) {
+        self.settle(now);
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self, now: i64) {
+        self.settle(now);
+        self.paused = false;
+    }
+
+    /// Moves to a time within the current track
+    pub fn seek(&mut self, now: i64, position_ms: u64) {
+        self.settle(now);
+        self.position_ms = position_ms;
+    }
+
+    /// Starts the next track, after the last one the timeline finishes unless it repeats
+    pub fn skip(&mut self, now: i64) {
+        self.settle(now);
+        self.index += 1;
+        self.position_ms = 0;
+
+        if self.repeat && self.index >= self.durations.len() {
+            self.index = 0;
+        }
+    }
+
+    /// Restarts the previous track, or the first one when already there
+    pub fn previous(&mut self, now: i64) {
+        self.settle(now);
+        self.index = self.index.saturating_sub(1);
+        self.position_ms = 0;
+    }
+}
@@ -1,3 +1,6 @@
+pub mod new_device_token;
 pub mod new_playlist;
+pub mod new_schedule;
 pub mod new_song;
+pub mod new_station;
 pub mod new_user;
@@ -0,0 +1,67 @@
+use harmony_data::model::device_token::{DeviceTokenModel, NewDeviceTokenModel};
+
+use crate::{
+    core::{
+        model::device_token::{DeviceToken, Scope, DEVICE_TOKEN_PREFIX},
+        validation::{result::ValidationError, validated_types::Name},
+    },
+    result::ServerError,
+    utils::secret,
+};
+
+pub struct DeviceTokenTemplate {
+    pub name: Name,
+    pub scopes: Vec<Scope>,
+    pub user_id: i32,
+}
+
+impl DeviceTokenTemplate {
+    pub fn new(name: &str, scopes: &[String], user_id: i32) -> Result<Self, ValidationError> {
+        let mut scopes = scopes
+            .iter()
+            .map(|scope| Scope::parse(scope))
+            .collect::<Result<Vec<Scope>, ValidationError>>()?;
+        scopes.sort();
+        scopes.dedup();
+
+        if scopes.is_empty() {
+            return Err(ValidationError::InvalidScope(
+                "A device token needs at least one scope".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            name: Name::new(name)?,
+            scopes,
+            user_id,
+        })
+    }
+
+    /// Creates the token, the secret is returned once and only its hash is stored
+    pub fn create(&self) -> Result<(DeviceToken, String), ServerError> {
+        let token = secret::generate(DEVICE_TOKEN_PREFIX);
+        let new_token = self.to_model(&token);
+
+        match DeviceTokenModel::create(&new_token) {
+            Ok(device) => Ok((DeviceToken::from_model(&device)?, token)),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to create device token".to_string(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn to_model(&self, token: &str) -> NewDeviceTokenModel {
+        NewDeviceTokenModel {
+            user_id: self.user_id,
+            name: self.name.value().to_string(),
+            hash: secret::hash(token),
+            scopes: self
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str())
+                .collect::<Vec<&str>>()
+                .join(","),
+        }
+    }
+}
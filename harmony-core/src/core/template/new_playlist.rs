@@ -3,6 +3,7 @@ use harmony_data::model::playlist::{NewPlaylistModel, PlaylistModel};
 use crate::{
     core::{
         model::playlist::Playlist,
+        model::share_code::{ShareCode, ShareTarget},
         validation::{result::ValidationError, validated_types::Name},
     },
     result::ServerError,
@@ -25,7 +26,10 @@ impl PlaylistTemplate {
         let new_playlist = self.to_model()?;
 
         match PlaylistModel::create(&new_playlist) {
-            Ok(song) => Ok(Playlist::from_model(&song)),
+            Ok(playlist) => {
+                ShareCode::assign(ShareTarget::Playlist(playlist.id));
+                Ok(Playlist::from_model(&playlist))
+            }
             Err(_) => Err(ServerError::CouldNotCreate(
                 "Failed to create playlist".to_string(),
             )),
@@ -0,0 +1,103 @@
+use harmony_data::model::schedule::{NewScheduleModel, ScheduleModel};
+
+use crate::{
+    core::{
+        model::{playlist::Playlist, schedule::Schedule, station::Station},
+        validation::{
+            result::ValidationError,
+            validated_types::{CronRule, Name, TimeZone},
+        },
+    },
+    result::ServerError,
+    utils::short_code,
+};
+
+/// Where a schedule plays
+pub enum ScheduleTarget {
+    Station(i32),
+    Jukebox(String),
+}
+
+pub struct ScheduleTemplate {
+    pub name: Name,
+    pub playlist: i32,
+    pub target: ScheduleTarget,
+    pub rule: CronRule,
+    pub duration_minutes: i32,
+    pub timezone: TimeZone,
+    pub priority: i32,
+    pub created_by: i32,
+}
+
+impl ScheduleTemplate {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: &str,
+        playlist: i32,
+        target: ScheduleTarget,
+        rule: &str,
+        duration_minutes: i32,
+        timezone: &str,
+        priority: i32,
+        created_by: i32,
+    ) -> Result<Self, ValidationError> {
+        // A window can last at most a week, longer ones are better served by the station itself
+        if !(1..=7 * 24 * 60).contains(&duration_minutes) {
+            return Err(ValidationError::InvalidSchedule(
+                "Duration must be between 1 minute and 1 week".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            name: Name::new(name)?,
+            playlist,
+            target: match target {
+                ScheduleTarget::Jukebox(code) => {
+                    ScheduleTarget::Jukebox(short_code::normalize(&code))
+                }
+                target => target,
+            },
+            rule: CronRule::new(rule)?,
+            duration_minutes,
+            timezone: TimeZone::new(timezone)?,
+            priority,
+            created_by,
+        })
+    }
+
+    pub fn create(&self) -> Result<Schedule, ServerError> {
+        // Whatever is scheduled plays to everyone listening, so it can't be someone's private playlist
+        Playlist::get_visible(self.playlist, Some(self.created_by))?;
+        if let ScheduleTarget::Station(station_id) = self.target {
+            Station::get_by_id(station_id)?; // Check if the station exists
+        }
+
+        let new_schedule = self.to_model()?;
+
+        match ScheduleModel::create(&new_schedule) {
+            Ok(schedule) => Ok(Schedule::from_model(&schedule)),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to create schedule".to_string(),
+            )),
+        }
+    }
+
+    pub fn to_model(&self) -> Result<NewScheduleModel, ServerError> {
+        let (station, jukebox) = match &self.target {
+            ScheduleTarget::Station(station_id) => (Some(*station_id), None),
+            ScheduleTarget::Jukebox(code) => (None, Some(code.clone())),
+        };
+
+        Ok(NewScheduleModel {
+            name: self.name.value().to_string(),
+            playlist: self.playlist,
+            station,
+            jukebox,
+            rule: self.rule.value().to_string(),
+            duration_minutes: self.duration_minutes,
+            timezone: self.timezone.value().to_string(),
+            priority: self.priority,
+            created_by: self.created_by,
+        })
+    }
+}
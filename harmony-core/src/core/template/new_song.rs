@@ -1,11 +1,19 @@
+use chrono::{TimeDelta, Utc};
 use harmony_data::model::song::{NewSongModel, SongModel};
 use harmony_data::result::Error;
+use std::sync::OnceLock;
 
+use crate::core::model::encoding::EncodingProfile;
+use crate::core::model::share_code::{ShareCode, ShareTarget};
 use crate::core::model::song::Song;
 use crate::core::validation::result::ValidationError;
 use crate::core::validation::validated_types::{Name, YouTubeUrl};
 use crate::result::ServerError;
 use crate::utils;
+use crate::utils::single_flight::SingleFlight;
+
+// Songs being added right now by url, a second request for one waits for the first
+static INGESTING: OnceLock<SingleFlight<String, Result<Song, ServerError>>> = OnceLock::new();
 
 pub struct SongTemplate {
     pub name: Name,
@@ -45,25 +53,70 @@ impl SongTemplate {
         }
     }
 
-    pub fn create(&self) -> Result<Song, ServerError> {
+    /// Downloads and adds the song. When the url is already being added, waits for that
+    /// instead and returns the same song, so it is only downloaded once.
+    /// With a daily limit the song is only added while the user has songs left for the day,
+    /// otherwise `ServerError::LimitReached` is returned
+    pub fn create(&self, songs_per_day: Option<u32>) -> Result<Song, ServerError> {
+        INGESTING
+            .get_or_init(SingleFlight::new)
+            .run(self.youtube_url.value().clone(), || {
+                self.create_now(songs_per_day)
+            })
+            .unwrap_or_else(|| {
+                Err(ServerError::CouldNotCreate(
+                    "Failed to create song".to_string(),
+                ))
+            })
+    }
+
+    fn create_now(&self, songs_per_day: Option<u32>) -> Result<Song, ServerError> {
         self.song_free()?;
         let new_song = self.to_model()?;
 
-        match SongModel::create(&new_song) {
-            Ok(song) => Ok(Song::from_model(&song)),
-            Err(_) => Err(ServerError::CouldNotCreate(
-                "Failed to create song".to_string(),
-            )),
+        let result = match songs_per_day {
+            None => SongModel::create(&new_song).map(Some),
+            Some(limit) => SongModel::create_limited(
+                &new_song,
+                Utc::now() - TimeDelta::days(1),
+                i64::from(limit),
+            ),
+        };
+
+        match result {
+            Ok(Some(song)) => {
+                ShareCode::assign(ShareTarget::Song(song.id));
+                Ok(Song::from_model(&song))
+            }
+            Ok(None) => {
+                utils::song_file::remove_song(&new_song.file_id).ok();
+                Err(ServerError::LimitReached)
+            }
+            Err(error) => {
+                // Nothing points at the download anymore
+                utils::song_file::remove_song(&new_song.file_id).ok();
+                match error {
+                    Error::UniqueViolation(_) => Err(ServerError::AlreadyExists),
+                    _ => Err(ServerError::CouldNotCreate(
+                        "Failed to create song".to_string(),
+                    )),
+                }
+            }
         }
     }
 
     pub fn to_model(&self) -> Result<NewSongModel, ServerError> {
+        let profile = EncodingProfile::default();
+
         Ok(NewSongModel {
             name: self.name.value().to_string(),
             author: self.author.as_ref().map(|a| a.value().clone()),
             added_by: self.added_by,
             youtube_url: self.youtube_url.value().clone(),
-            file_id: utils::song_file::pull_song(self.youtube_url.value())?,
+            file_id: utils::song_file::pull_song(self.youtube_url.value(), &profile)?,
+            codec: profile.codec.as_str().to_string(),
+            sample_rate: profile.sample_rate.cast_signed(),
+            channels: profile.channels.cast_signed(),
         })
     }
 }
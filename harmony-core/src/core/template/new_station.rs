@@ -0,0 +1,73 @@
+use harmony_data::model::station::{NewStationModel, StationModel};
+use harmony_data::result::Error;
+
+use crate::{
+    core::{
+        model::{playlist::Playlist, station::Station},
+        validation::{result::ValidationError, validated_types::Name},
+    },
+    result::ServerError,
+};
+
+pub struct StationTemplate {
+    pub name: Name,
+    pub playlist: Option<i32>,
+    pub shuffle: bool,
+    pub created_by: i32,
+}
+
+impl StationTemplate {
+    pub fn new(
+        name: &str,
+        playlist: Option<i32>,
+        shuffle: bool,
+        created_by: i32,
+    ) -> Result<Self, ValidationError> {
+        Ok(Self {
+            name: Name::new(name)?,
+            playlist,
+            shuffle,
+            created_by,
+        })
+    }
+
+    /// Returns `ServerError::AlreadyExists` if a station has the name
+    pub fn station_free(&self) -> Result<(), ServerError> {
+        match StationModel::exists(self.name.value()) {
+            Ok(()) => Err(ServerError::AlreadyExists),
+            Err(err) => match err {
+                Error::NotFound => Ok(()),
+                _ => Err(ServerError::Database(
+                    "Failed to check if station exists".to_owned(),
+                )),
+            },
+        }
+    }
+
+    pub fn create(&self) -> Result<Station, ServerError> {
+        self.station_free()?;
+        if let Some(playlist_id) = self.playlist {
+            // A station plays to everyone, so it can't be someone's private playlist
+            Playlist::get_visible(playlist_id, Some(self.created_by))?;
+        }
+
+        let new_station = self.to_model()?;
+
+        match StationModel::create(&new_station) {
+            Ok(station) => Ok(Station::from_model(&station)),
+            Err(Error::UniqueViolation(_)) => Err(ServerError::AlreadyExists),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to create station".to_string(),
+            )),
+        }
+    }
+
+    pub fn to_model(&self) -> Result<NewStationModel, ServerError> {
+        Ok(NewStationModel {
+            name: self.name.value().to_string(),
+            playlist: self.playlist,
+            shuffle: self.shuffle,
+            created_by: self.created_by,
+        })
+    }
+}
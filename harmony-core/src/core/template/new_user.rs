@@ -2,7 +2,7 @@ use crate::core::model::user::User;
 use crate::core::validation::result::ValidationError;
 use crate::core::validation::validated_types::{Password, Username};
 use crate::result::{AuthError, ServerError};
-use bcrypt::{hash, verify, DEFAULT_COST};
+use crate::utils::{password, short_code};
 use harmony_data::model::user::{NewUserModel, UserModel};
 use harmony_data::result::Error;
 
@@ -23,14 +23,12 @@ impl UserTemplate {
         Ok(NewUserModel {
             name: self.name.value().to_string(),
             hash: self.get_hash()?,
+            pending: false,
         })
     }
 
     pub fn get_hash(&self) -> Result<String, ServerError> {
-        match hash(self.password.value(), DEFAULT_COST) {
-            Ok(hash) => Ok(hash),
-            Err(_) => Err(ServerError::HashFailed),
-        }
+        password::hash(self.password.value())
     }
 
     /// Returns `ServerError::AlreadyExists` if the user exists
@@ -58,14 +56,15 @@ impl UserTemplate {
         }
     }
 
-    /// Checks if the user provided valid login creddentials
+    /// Checks if the user provided valid login creddentials.
+    /// Unknown users take as long as a wrong password, so the two can't be told apart by timing
     pub fn verify_auth(&self) -> Result<i32, AuthError> {
-        if self.exists().is_err() {
-            return Err(AuthError::UserNotFound);
-        };
-
         let user_model: UserModel = match UserModel::get_by_name(self.name.value()) {
             Ok(user_model) => user_model,
+            Err(Error::NotFound) => {
+                password::verify_dummy(self.password.value());
+                return Err(AuthError::UserNotFound);
+            }
             Err(_) => {
                 return Err(AuthError::Database(
                     "Something went wrong during authentication".to_string(),
@@ -74,8 +73,20 @@ impl UserTemplate {
         };
 
         // Verify the provided password against the stored hash
-        match verify(self.password.value(), &user_model.hash) {
-            Ok(true) => Ok(user_model.id),                // Password matches
+        match password::verify(self.password.value(), &user_model.hash) {
+            Ok(true) if user_model.suspended.is_some() => Err(AuthError::Suspended),
+            Ok(true) if user_model.pending => Err(AuthError::Pending),
+            Ok(true) => {
+                // Password matches, the moment to bring an outdated hash up to date
+                let params = password::HashParams::from_environment();
+                if password::needs_rehash(&user_model.hash, params) {
+                    if let Ok(hash) = password::hash_with(self.password.value(), params) {
+                        // The login is fine either way, the hash is redone on the next one
+                        UserModel::set_hash(user_model.id, &hash).ok();
+                    }
+                }
+                Ok(user_model.id)
+            }
             Ok(false) => Err(AuthError::InvalidPassword), // Password does not match
             Err(_) => Err(AuthError::Database(
                 "Something went wrong during authentication".to_string(),
@@ -84,11 +95,38 @@ impl UserTemplate {
     }
 
     pub fn create(&self) -> Result<User, ServerError> {
+        self.create_as(false)
+    }
+
+    /// Creates an account that can't log in until an admin approves it
+    pub fn create_pending(&self) -> Result<User, ServerError> {
+        self.create_as(true)
+    }
+
+    /// Creates an account with an invite as typed by the user,
+    /// returns `ServerError::NotFound` when the invite is unknown, used up or expired
+    pub fn create_with_invite(&self, code: &str) -> Result<User, ServerError> {
         self.name_free()?; // The user cannot exist
         let new_user = self.to_model()?;
 
+        match UserModel::create_with_invite(new_user, &short_code::normalize(code)) {
+            Ok(user) => Ok(User::from_model(&user)),
+            Err(Error::NotFound) => Err(ServerError::NotFound),
+            Err(Error::UniqueViolation(_)) => Err(ServerError::AlreadyExists),
+            Err(_) => Err(ServerError::CouldNotCreate(
+                "Failed to create user".to_string(),
+            )),
+        }
+    }
+
+    fn create_as(&self, pending: bool) -> Result<User, ServerError> {
+        self.name_free()?; // The user cannot exist
+        let mut new_user = self.to_model()?;
+        new_user.pending = pending;
+
         match UserModel::create(new_user) {
             Ok(user) => Ok(User::from_model(&user)),
+            Err(Error::UniqueViolation(_)) => Err(ServerError::AlreadyExists),
             Err(_) => Err(ServerError::CouldNotCreate(
                 "Failed to create user".to_string(),
             )),
@@ -8,4 +8,7 @@ pub enum ValidationError {
     InvalidFilePath(String),
     InvalidYouTubeUrl(String),
     InvalidName(String),
+    InvalidSchedule(String),
+    InvalidScope(String),
+    InvalidRole(String),
 }
@@ -1,5 +1,7 @@
 use crate::core::validation::result::ValidationError;
+use chrono_tz::Tz;
 use regex::Regex;
+use std::str::FromStr;
 
 #[derive(Debug)]
 pub struct Username(String);
@@ -66,7 +68,8 @@ impl Email {
     pub fn new(email: &str) -> Result<Self, ValidationError> {
         // Improved regex pattern for more accurate email validation
         let re = Regex::new(r"(^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+$)").unwrap();
-        if !re.is_match(email) {
+        // Addresses can't be longer than the database column
+        if email.len() > 255 || !re.is_match(email) {
             Err(ValidationError::InvalidEmail(
                 "Invalid email format".to_string(),
             ))
@@ -155,3 +158,58 @@ impl YouTubeUrl {
         &self.0
     }
 }
+
+#[derive(Debug)]
+pub struct CronRule(String);
+
+impl CronRule {
+    pub fn new(rule: &str) -> Result<Self, ValidationError> {
+        let rule = rule.trim();
+        Self::parse(rule)?;
+        Ok(Self(rule.to_string()))
+    }
+
+    /// Parses a rule in the usual five field form, or with seconds and years like the `cron` crate
+    pub fn parse(rule: &str) -> Result<cron::Schedule, ValidationError> {
+        let rule = if rule.split_whitespace().count() == 5 {
+            format!("0 {rule}")
+        } else {
+            rule.to_string()
+        };
+
+        cron::Schedule::from_str(&rule).map_err(|_| {
+            ValidationError::InvalidSchedule(
+                "Rule must be a cron expression such as \"0 20 * * Sat\"".to_string(),
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+pub struct TimeZone(String);
+
+impl TimeZone {
+    pub fn new(timezone: &str) -> Result<Self, ValidationError> {
+        Self::parse(timezone)?;
+        Ok(Self(timezone.to_string()))
+    }
+
+    /// Parses an IANA time zone name such as `Europe/Amsterdam`
+    pub fn parse(timezone: &str) -> Result<Tz, ValidationError> {
+        Tz::from_str(timezone).map_err(|_| {
+            ValidationError::InvalidSchedule(
+                "Time zone must be a name such as \"Europe/Amsterdam\" or \"UTC\"".to_string(),
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
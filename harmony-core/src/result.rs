@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[allow(clippy::enum_variant_names)]
 #[non_exhaustive]
 pub enum ServerError {
@@ -9,6 +9,9 @@ pub enum ServerError {
     NotFound,
     ExecutionFailed,
     FileNotFound,
+    MailFailed(String),
+    /// The user used up a limit, like the songs they may add in a day
+    LimitReached,
 }
 
 #[derive(Debug)]
@@ -17,6 +20,8 @@ pub enum ServerError {
 pub enum AuthError {
     InvalidPassword,
     UserNotFound,
+    Suspended,
+    Pending,
     HashFailed,
     Database(String),
 }
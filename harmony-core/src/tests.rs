@@ -1,5 +1,22 @@
+pub mod broadcast;
+pub mod byte_size;
+pub mod device_token;
+pub mod dfpwm;
 pub mod email;
+pub mod encoding_profile;
+pub mod jukebox;
+pub mod mail;
+pub mod mixer;
 pub mod name;
 pub mod password;
+pub mod playback_queue;
+pub mod role;
+pub mod room;
+pub mod schedule;
+pub mod share_code;
+pub mod short_code;
+pub mod shuffle;
+pub mod single_flight;
+pub mod timeline;
 pub mod username;
 pub mod youtube_url;
@@ -0,0 +1,82 @@
+#[cfg(test)]
+use crate::core::{
+    model::{encoding::EncodingProfile, song::Song},
+    playback::broadcast::Broadcast,
+};
+
+#[cfg(test)]
+fn song(id: i32) -> Song {
+    Song {
+        id,
+        name: format!("Song {id}"),
+        author: None,
+        added_by: Some(1),
+        youtube_url: format!("https://youtu.be/{id}"),
+        file_id: format!("file{id}"),
+        created: None,
+        encoding: EncodingProfile::default(),
+    }
+}
+
+#[cfg(test)]
+fn playing(broadcast: &Broadcast, now: i64) -> Option<(i32, u64)> {
+    broadcast
+        .current(now)
+        .map(|(position, song)| (song.id, position.position_ms))
+}
+
+#[test]
+fn test_broadcast_skips_songs_without_audio() {
+    let tracks = vec![(song(1), 1000), (song(2), 0), (song(3), 2000)];
+    let broadcast = Broadcast::new(tracks, false, 10_000);
+
+    assert_eq!(broadcast.tracks().len(), 3);
+    assert_eq!(playing(&broadcast, 10_500), Some((1, 500)));
+    assert_eq!(playing(&broadcast, 11_000), Some((3, 0)));
+    assert_eq!(playing(&broadcast, 13_000), None);
+}
+
+#[test]
+fn test_broadcast_next_wraps_when_repeating() {
+    let tracks = vec![(song(1), 1000), (song(2), 1000)];
+
+    let once = Broadcast::new(tracks.clone(), false, 0);
+    assert!(once.next(once.position(1500)).is_none());
+
+    let repeating = Broadcast::new(tracks, true, 0);
+    assert_eq!(repeating.next(repeating.position(500)).unwrap().id, 2);
+    assert_eq!(repeating.next(repeating.position(1500)).unwrap().id, 1);
+    assert_eq!(playing(&repeating, 2500), Some((1, 500)));
+}
+
+#[test]
+fn test_broadcast_starting_at() {
+    let tracks = vec![(song(1), 1000), (song(2), 1000)];
+
+    // Picks up the song where it was when the list changed
+    let broadcast = Broadcast::starting_at(tracks.clone(), true, 2, 400, 5000);
+    assert_eq!(playing(&broadcast, 5000), Some((2, 400)));
+
+    // A song that is no longer in the list starts the list over
+    let broadcast = Broadcast::starting_at(tracks, true, 3, 400, 5000);
+    assert_eq!(playing(&broadcast, 5000), Some((1, 0)));
+}
+
+#[test]
+fn test_broadcast_controls() {
+    let tracks = vec![(song(1), 1000), (song(2), 1000)];
+    let mut broadcast = Broadcast::new(tracks, false, 0);
+
+    broadcast.pause(300);
+    assert_eq!(playing(&broadcast, 5000), Some((1, 300)));
+
+    broadcast.resume(5000);
+    broadcast.skip(5100);
+    assert_eq!(playing(&broadcast, 5100), Some((2, 0)));
+
+    broadcast.seek(5200, 700);
+    assert_eq!(playing(&broadcast, 5300), Some((2, 800)));
+
+    broadcast.previous(5300);
+    assert_eq!(playing(&broadcast, 5300), Some((1, 0)));
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+use crate::utils::byte_size;
+
+#[test]
+fn test_parse_byte_sizes() {
+    assert_eq!(byte_size::parse("512"), Some(512));
+    assert_eq!(byte_size::parse("10K"), Some(10 * 1024));
+    assert_eq!(byte_size::parse("10M"), Some(10 * 1024 * 1024));
+    assert_eq!(byte_size::parse("2g"), Some(2 * 1024 * 1024 * 1024));
+    assert_eq!(byte_size::parse(" 1 T "), Some(1024 * 1024 * 1024 * 1024));
+    assert_eq!(byte_size::parse("0"), Some(0));
+}
+
+#[test]
+fn test_parse_invalid_byte_sizes() {
+    assert_eq!(byte_size::parse(""), None);
+    assert_eq!(byte_size::parse("M"), None);
+    assert_eq!(byte_size::parse("10X"), None);
+    assert_eq!(byte_size::parse("-10M"), None);
+    assert_eq!(byte_size::parse("1.5G"), None);
+    assert_eq!(byte_size::parse("99999999999T"), None);
+}
+
+#[test]
+fn test_format_byte_sizes() {
+    assert_eq!(byte_size::format(0), "0 B");
+    assert_eq!(byte_size::format(1023), "1023 B");
+    assert_eq!(byte_size::format(1024), "1.0 KiB");
+    assert_eq!(byte_size::format(1536), "1.5 KiB");
+    assert_eq!(byte_size::format(500 * 1024 * 1024), "500.0 MiB");
+    assert_eq!(byte_size::format(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    assert_eq!(byte_size::format(u64::MAX), "16777215.9 TiB");
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+use crate::core::model::device_token::{DeviceToken, Scope, LAST_USED_PRECISION};
+#[cfg(test)]
+use crate::core::template::new_device_token::DeviceTokenTemplate;
+#[cfg(test)]
+use crate::utils::secret;
+#[cfg(test)]
+use chrono::{TimeDelta, Utc};
+
+#[test]
+fn test_device_token_scopes() {
+    let scopes = vec![
+        "write".to_string(),
+        "stream".to_string(),
+        "write".to_string(),
+    ];
+    let template = DeviceTokenTemplate::new("Kitchen", &scopes, 1).unwrap();
+    assert_eq!(template.scopes, vec![Scope::Stream, Scope::Write]);
+    assert_eq!(template.to_model("hdt_secret").scopes, "stream,write");
+
+    assert!(DeviceTokenTemplate::new("Kitchen", &[], 1).is_err());
+    assert!(DeviceTokenTemplate::new("Kitchen", &["admin".to_string()], 1).is_err());
+    assert!(DeviceTokenTemplate::new("", &["read".to_string()], 1).is_err());
+}
+
+#[test]
+fn test_secret_hash() {
+    assert_eq!(
+        secret::hash("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    // The secret itself is never stored
+    let template = DeviceTokenTemplate::new("Kitchen", &["read".to_string()], 1).unwrap();
+    let model = template.to_model("hdt_secret");
+    assert_eq!(model.hash, secret::hash("hdt_secret"));
+    assert!(!model.hash.contains("secret"));
+}
+
+#[test]
+fn test_device_token_touch() {
+    let now = Utc::now();
+    let mut device = DeviceToken {
+        id: 1,
+        user_id: 1,
+        name: "Kitchen".to_string(),
+        scopes: vec![Scope::Stream],
+        last_used: None,
+        created: None,
+    };
+    assert!(device.needs_touch(now));
+
+    // Busy devices are only written once in a while
+    device.last_used = Some(now - TimeDelta::seconds(10));
+    assert!(!device.needs_touch(now));
+
+    device.last_used = Some(now - LAST_USED_PRECISION);
+    assert!(device.needs_touch(now));
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+use crate::core::playback::dfpwm::{DfpwmDecoder, DfpwmEncoder};
+
+#[cfg(test)]
+fn sine(samples: usize) -> Vec<i8> {
+    (0..samples)
+        .map(|i| {
+            let level = (i as f64 * 2.0 * std::f64::consts::PI * 110.0 / 48_000.0).sin() * 100.0;
+            level as i8
+        })
+        .collect()
+}
+
+#[test]
+fn test_dfpwm_silence() {
+    let encoded = DfpwmEncoder::new().encode(&[0; 64]);
+    assert_eq!(encoded, vec![0xAA; 8]);
+
+    let decoded = DfpwmDecoder::new().decode(&encoded);
+    assert_eq!(decoded.len(), 64);
+    assert!(decoded.iter().all(|sample| sample.abs() < 8));
+}
+
+#[test]
+fn test_dfpwm_round_trip() {
+    let samples = sine(48_000);
+    let encoded = DfpwmEncoder::new().encode(&samples);
+    assert_eq!(encoded.len(), 6000);
+
+    let decoded = DfpwmDecoder::new().decode(&encoded);
+    assert_eq!(decoded.len(), samples.len());
+
+    // Lossy, but after the predictor settled it should follow the signal closely
+    let error: f64 = samples[4800..]
+        .iter()
+        .zip(&decoded[4800..])
+        .map(|(a, b)| (f64::from(*a) - f64::from(*b)).abs())
+        .sum::<f64>()
+        / (samples.len() - 4800) as f64;
+    assert!(error < 20.0, "average error {error}");
+}
+
+#[test]
+fn test_dfpwm_streaming() {
+    let samples = sine(4096);
+
+    let whole = DfpwmEncoder::new().encode(&samples);
+    let mut encoder = DfpwmEncoder::new();
+    let mut pieces = encoder.encode(&samples[..1000]);
+    pieces.extend(encoder.encode(&samples[1000..]));
+    assert_eq!(whole, pieces);
+
+    let mut decoder = DfpwmDecoder::new();
+    let mut decoded = decoder.decode(&whole[..100]);
+    decoded.extend(decoder.decode(&whole[100..]));
+    assert_eq!(decoded, DfpwmDecoder::new().decode(&whole));
+
+    // A partial byte is padded
+    assert_eq!(DfpwmEncoder::new().encode(&samples[..9]).len(), 2);
+}
@@ -41,4 +41,7 @@ fn test_invalid_emails() {
 
     let email = Email::new("user@domain.c ");
     assert!(email.is_err());
+
+    let email = Email::new(&format!("{}@example.com", "a".repeat(250)));
+    assert!(email.is_err());
 }
@@ -0,0 +1,42 @@
+#[cfg(test)]
+use crate::core::model::encoding::{Codec, EncodingProfile};
+
+#[test]
+fn test_default_profile() {
+    let profile = EncodingProfile::default();
+    assert_eq!(profile.codec, Codec::Dfpwm);
+    assert_eq!(profile.bytes_per_second(), 6000);
+}
+
+#[test]
+fn test_time_to_bytes() {
+    let profile = EncodingProfile::default();
+    assert_eq!(profile.byte_offset(0), 0);
+    assert_eq!(profile.byte_offset(1000), 6000);
+    assert_eq!(profile.byte_offset(1500), 9000);
+    assert_eq!(profile.byte_offset(1), 6);
+
+    let stereo = EncodingProfile {
+        channels: 2,
+        ..EncodingProfile::default()
+    };
+    assert_eq!(stereo.byte_offset(1000), 12000);
+}
+
+#[test]
+fn test_bytes_to_duration() {
+    let profile = EncodingProfile::default();
+    assert_eq!(profile.duration_ms(0), 0);
+    assert_eq!(profile.duration_ms(6000), 1000);
+    assert_eq!(profile.duration_ms(60_000), 10_000);
+    assert_eq!(profile.duration_ms(6001), 1000);
+}
+
+#[test]
+fn test_profile_from_columns() {
+    let profile = EncodingProfile::new("dfpwm", 48_000, 1).unwrap();
+    assert_eq!(profile, EncodingProfile::default());
+
+    assert!(EncodingProfile::new("mp3", 48_000, 1).is_err());
+    assert!(EncodingProfile::new("dfpwm", -1, 1).is_err());
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+use crate::core::{
+    model::{encoding::EncodingProfile, song::Song},
+    playback::jukebox::{Jukebox, Vote},
+};
+#[cfg(test)]
+use crate::result::ServerError;
+
+#[cfg(test)]
+fn song(id: i32) -> Song {
+    Song {
+        id,
+        name: format!("Song {id}"),
+        author: None,
+        added_by: Some(1),
+        youtube_url: format!("https://youtu.be/{id}"),
+        file_id: format!("file{id}"),
+        created: None,
+        encoding: EncodingProfile::default(),
+    }
+}
+
+#[cfg(test)]
+fn queued_songs(jukebox: &Jukebox) -> Vec<i32> {
+    jukebox.queue().iter().map(|entry| entry.song.id).collect()
+}
+
+#[test]
+fn test_jukebox_plays_in_order_of_votes() {
+    let mut jukebox = Jukebox::new(2);
+
+    // The first song starts right away
+    jukebox.enqueue(song(1), 1000, 1, 0).unwrap();
+    assert_eq!(jukebox.playing().unwrap().song.id, 1);
+
+    let second = jukebox.enqueue(song(2), 1000, 1, 10).unwrap();
+    let third = jukebox.enqueue(song(3), 1000, 2, 20).unwrap();
+    assert_eq!(queued_songs(&jukebox), vec![2, 3]);
+
+    jukebox.vote(third, 1, Vote::Up, 30).unwrap();
+    assert_eq!(queued_songs(&jukebox), vec![3, 2]);
+    assert_eq!(jukebox.queue()[0].score(), 1);
+    assert_eq!(jukebox.queue()[0].vote_of(1), Vote::Up);
+
+    // Taking the vote back restores the order songs were added in
+    jukebox.vote(third, 1, Vote::None, 40).unwrap();
+    assert_eq!(queued_songs(&jukebox), vec![2, 3]);
+
+    jukebox.vote(second, 1, Vote::Down, 50).unwrap();
+    assert_eq!(queued_songs(&jukebox), vec![3, 2]);
+
+    // Enough downvotes drop a song from the queue
+    jukebox.vote(second, 2, Vote::Down, 60).unwrap();
+    assert_eq!(queued_songs(&jukebox), vec![3]);
+
+    assert!(matches!(
+        jukebox.vote(second, 1, Vote::Up, 70),
+        Err(ServerError::NotFound)
+    ));
+}
+
+#[test]
+fn test_jukebox_follows_the_clock() {
+    let mut jukebox = Jukebox::new(2);
+    jukebox.enqueue(song(1), 1000, 1, 0).unwrap();
+    jukebox.enqueue(song(2), 1000, 1, 0).unwrap();
+
+    // The second song started when the first ended, not when we asked
+    jukebox.update(1500);
+    let playing = jukebox.playing().unwrap();
+    assert_eq!(playing.song.id, 2);
+    assert_eq!(playing.started_at, 1000);
+    assert_eq!(playing.position_ms(1500), 500);
+
+    jukebox.update(5000);
+    assert!(jukebox.playing().is_none());
+
+    // A song added to an idle jukebox starts when it is added
+    jukebox.enqueue(song(3), 1000, 1, 6000).unwrap();
+    assert_eq!(jukebox.playing().unwrap().started_at, 6000);
+}
+
+#[test]
+fn test_jukebox_vote_skip() {
+    let mut jukebox = Jukebox::new(2);
+    jukebox.enqueue(song(1), 1000, 1, 0).unwrap();
+    jukebox.enqueue(song(2), 1000, 1, 0).unwrap();
+
+    assert!(!jukebox.vote_skip(1, 100).unwrap());
+    // Voting twice does not count twice
+    assert!(!jukebox.vote_skip(1, 200).unwrap());
+    assert_eq!(jukebox.playing().unwrap().skip_votes(), 1);
+
+    assert!(jukebox.vote_skip(2, 300).unwrap());
+    let playing = jukebox.playing().unwrap();
+    assert_eq!((playing.song.id, playing.started_at), (2, 300));
+    assert_eq!(playing.skip_votes(), 0);
+
+    jukebox.skip(400);
+    assert!(jukebox.playing().is_none());
+    assert!(matches!(
+        jukebox.vote_skip(1, 500),
+        Err(ServerError::NotFound)
+    ));
+}
+
+#[test]
+fn test_jukebox_rejects_duplicates() {
+    let mut jukebox = Jukebox::new(2);
+    jukebox.enqueue(song(1), 1000, 1, 0).unwrap();
+    let second = jukebox.enqueue(song(2), 1000, 1, 0).unwrap();
+
+    assert!(matches!(
+        jukebox.enqueue(song(1), 1000, 2, 0),
+        Err(ServerError::AlreadyExists)
+    ));
+    assert!(matches!(
+        jukebox.enqueue(song(2), 1000, 2, 0),
+        Err(ServerError::AlreadyExists)
+    ));
+
+    jukebox.remove(second, 0).unwrap();
+    assert!(jukebox.queue().is_empty());
+    jukebox.enqueue(song(2), 1000, 2, 0).unwrap();
+}
+
+#[test]
+fn test_jukebox_fallback() {
+    let mut jukebox = Jukebox::new(2);
+
+    jukebox.set_fallback(
+        Some((7, vec![(song(10), 1000), (song(11), 0), (song(12), 1000)])),
+        0,
+    );
+    assert_eq!(jukebox.fallback_id(), Some(7));
+
+    // The fallback plays in a loop, skipping songs without audio
+    let playing = jukebox.playing().unwrap();
+    assert_eq!((playing.song.id, playing.added_by), (10, None));
+    jukebox.update(1500);
+    assert_eq!(jukebox.playing().unwrap().song.id, 12);
+    jukebox.update(2500);
+    assert_eq!(jukebox.playing().unwrap().song.id, 10);
+
+    // Queued songs go first once the current song ends
+    jukebox.enqueue(song(1), 1000, 3, 2600).unwrap();
+    assert_eq!(jukebox.playing().unwrap().song.id, 10);
+    jukebox.update(3000);
+    let playing = jukebox.playing().unwrap();
+    assert_eq!((playing.song.id, playing.added_by), (1, Some(3)));
+
+    // Switching the fallback leaves queued songs alone
+    jukebox.set_fallback(None, 3100);
+    assert_eq!(jukebox.playing().unwrap().song.id, 1);
+    jukebox.update(4000);
+    assert!(jukebox.playing().is_none());
+
+    // But cuts a fallback song short
+    jukebox.set_fallback(Some((8, vec![(song(20), 1000)])), 5000);
+    jukebox.set_fallback(Some((9, vec![(song(30), 1000)])), 5100);
+    let playing = jukebox.playing().unwrap();
+    assert_eq!((playing.song.id, playing.started_at), (30, 5100));
+}
@@ -0,0 +1,19 @@
+#[cfg(test)]
+use crate::utils::mail::SmtpSecurity;
+
+#[test]
+fn test_parse_smtp_security() {
+    assert_eq!(
+        SmtpSecurity::parse("starttls"),
+        Some(SmtpSecurity::StartTls)
+    );
+    assert_eq!(SmtpSecurity::parse("tls"), Some(SmtpSecurity::Tls));
+    assert_eq!(SmtpSecurity::parse(" none "), Some(SmtpSecurity::None));
+}
+
+#[test]
+fn test_parse_invalid_smtp_security() {
+    assert_eq!(SmtpSecurity::parse(""), None);
+    assert_eq!(SmtpSecurity::parse("ssl"), None);
+    assert_eq!(SmtpSecurity::parse("STARTTLS"), None);
+}
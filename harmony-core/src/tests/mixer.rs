@@ -0,0 +1,74 @@
+#[cfg(test)]
+use crate::core::playback::dfpwm::DfpwmEncoder;
+#[cfg(test)]
+use crate::core::playback::mixer::Mixer;
+
+#[cfg(test)]
+fn track(bytes: usize, level: i8) -> Vec<u8> {
+    DfpwmEncoder::new().encode(&vec![level; bytes * 8])
+}
+
+#[test]
+fn test_mixer_gapless() {
+    let mut mixer = Mixer::new(0);
+    assert!(mixer.is_finished());
+    mixer.push(track(100, 50));
+    assert!(mixer.needs_track());
+    mixer.push(track(60, -50));
+    assert!(!mixer.needs_track());
+
+    assert_eq!(mixer.read(80).len(), 80);
+    assert_eq!(mixer.take_finished(), 0);
+    assert_eq!(mixer.offset(), 80);
+
+    // Reads run across the track boundary
+    assert_eq!(mixer.read(50).len(), 50);
+    assert_eq!(mixer.take_finished(), 1);
+    assert_eq!(mixer.offset(), 30);
+    assert_eq!(mixer.queued(), 1);
+
+    assert_eq!(mixer.read(100).len(), 30);
+    assert_eq!(mixer.take_finished(), 1);
+    assert!(mixer.is_finished());
+    assert!(mixer.read(100).is_empty());
+}
+
+#[test]
+fn test_mixer_crossfade() {
+    let mut mixer = Mixer::new(20);
+    mixer.push(track(100, 50));
+    mixer.push(track(100, -50));
+
+    // The tracks overlap by the crossfade
+    let rendered = mixer.read(1000);
+    assert_eq!(rendered.len(), 180);
+    assert_eq!(mixer.take_finished(), 2);
+
+    // The crossfade never takes longer than the shorter track
+    let mut mixer = Mixer::new(50);
+    mixer.push(track(100, 50));
+    mixer.push(track(10, -50));
+    assert_eq!(mixer.read(95).len(), 95);
+    assert_eq!(mixer.offset(), 95);
+    assert_eq!(mixer.read(1000).len(), 5);
+    assert_eq!(mixer.take_finished(), 2);
+    assert!(mixer.is_finished());
+}
+
+#[test]
+fn test_mixer_starts_at_offset() {
+    let mut mixer = Mixer::new(10);
+    mixer.push_from(track(100, 50), 40);
+    assert_eq!(mixer.offset(), 40);
+    mixer.push(track(30, 0));
+
+    assert_eq!(mixer.read(55).len(), 55);
+    assert_eq!(mixer.take_finished(), 0);
+    assert_eq!(mixer.offset(), 95);
+
+    // Five bytes into the fade, the next track started five bytes ago
+    assert_eq!(mixer.read(10).len(), 10);
+    assert_eq!(mixer.take_finished(), 1);
+    assert_eq!(mixer.offset(), 15);
+    assert_eq!(mixer.read(100).len(), 15);
+}
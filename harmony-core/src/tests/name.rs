@@ -33,7 +33,7 @@ fn test_invalid_names() {
     let name = Name::new(" JSmith ");
     assert!(name.is_err());
 
-    let name = Name::new("JSmith123456789012345"); // More than 16 characters
+    let name = Name::new("JSmith12345678901234567890123456789"); // More than 32 characters
     assert!(name.is_err());
 
     let name = Name::new("J$mith");
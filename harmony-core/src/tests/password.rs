@@ -1,5 +1,15 @@
 #[cfg(test)]
 use crate::core::validation::validated_types::Password;
+#[cfg(test)]
+use crate::utils::password::{self, HashParams};
+
+// Cheap parameters, so the tests don't spend their time hashing
+#[cfg(test)]
+const PARAMS: HashParams = HashParams {
+    memory: 64,
+    iterations: 1,
+    parallelism: 1,
+};
 
 #[test]
 fn test_valid_passwords() {
@@ -42,3 +52,53 @@ fn test_invalid_passwords() {
     let password = Password::new(" Passw!rd\n123 ");
     assert!(password.is_err());
 }
+
+#[test]
+fn test_temporary_password() {
+    for _ in 0..20 {
+        let temporary = password::generate_temporary();
+        assert!(Password::new(&temporary).is_ok());
+    }
+}
+
+#[test]
+fn test_argon2_hash() {
+    let hash = password::hash_with("P@ssw0rd", PARAMS).unwrap();
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(password::verify("P@ssw0rd", &hash).unwrap());
+    assert!(!password::verify("P@ssw0rd!", &hash).unwrap());
+
+    // Salted, the same password gives another hash
+    assert_ne!(hash, password::hash_with("P@ssw0rd", PARAMS).unwrap());
+
+    // Nothing is cut off, unlike bcrypt which stops after 72 bytes
+    let long = "a1!".repeat(30);
+    let hash = password::hash_with(&long, PARAMS).unwrap();
+    assert!(!password::verify(&format!("{long}b"), &hash).unwrap());
+}
+
+#[test]
+fn test_bcrypt_hash_still_verifies() {
+    let hash = bcrypt::hash("P@ssw0rd", 4).unwrap();
+    assert!(password::verify("P@ssw0rd", &hash).unwrap());
+    assert!(!password::verify("P@ssw0rd!", &hash).unwrap());
+    assert!(password::needs_rehash(&hash, PARAMS));
+}
+
+#[test]
+fn test_needs_rehash() {
+    let hash = password::hash_with("P@ssw0rd", PARAMS).unwrap();
+    assert!(!password::needs_rehash(&hash, PARAMS));
+
+    let stronger = HashParams {
+        iterations: 2,
+        ..PARAMS
+    };
+    assert!(password::needs_rehash(&hash, stronger));
+
+    // Hashes made with other parameters still verify
+    assert!(password::verify("P@ssw0rd", &hash).unwrap());
+
+    assert!(password::needs_rehash("not a hash", PARAMS));
+    assert!(password::verify("P@ssw0rd", "not a hash").is_err());
+}
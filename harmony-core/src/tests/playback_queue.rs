@@ -0,0 +1,84 @@
+#[cfg(test)]
+use crate::core::{
+    model::{encoding::EncodingProfile, song::Song},
+    playback::queue::PlaybackQueue,
+};
+
+#[cfg(test)]
+fn song(id: i32) -> Song {
+    Song {
+        id,
+        name: format!("Song {id}"),
+        author: None,
+        added_by: Some(1),
+        youtube_url: format!("https://youtu.be/{id}"),
+        file_id: format!("file{id}"),
+        created: None,
+        encoding: EncodingProfile::default(),
+    }
+}
+
+#[test]
+fn test_queue_advances_through_tracks() {
+    let mut queue = PlaybackQueue::new(vec![song(1), song(2)]);
+    assert_eq!(queue.current().unwrap().id, 1);
+    assert_eq!(queue.next().unwrap().id, 2);
+
+    queue.advance(100);
+    queue.advance(50);
+    assert_eq!(queue.offset(), 150);
+
+    assert!(queue.skip());
+    assert_eq!(queue.current().unwrap().id, 2);
+    assert_eq!(queue.offset(), 0);
+    assert!(queue.next().is_none());
+
+    assert!(!queue.skip());
+    assert!(queue.is_finished());
+    assert!(queue.current().is_none());
+
+    // Skipping a finished queue keeps it finished
+    assert!(!queue.skip());
+    assert_eq!(queue.index(), 2);
+}
+
+#[test]
+fn test_queue_previous_and_seek() {
+    let mut queue = PlaybackQueue::new(vec![song(1), song(2)]);
+
+    queue.previous();
+    assert_eq!(queue.current().unwrap().id, 1);
+
+    queue.skip();
+    queue.seek(1000);
+    assert_eq!(queue.offset(), 1000);
+
+    queue.previous();
+    assert_eq!(queue.current().unwrap().id, 1);
+    assert_eq!(queue.offset(), 0);
+
+    // Going back from the end replays the last track
+    queue.skip();
+    queue.skip();
+    queue.previous();
+    assert_eq!(queue.current().unwrap().id, 2);
+}
+
+#[test]
+fn test_queue_pause() {
+    let mut queue = PlaybackQueue::new(vec![song(1)]);
+    assert!(!queue.is_paused());
+
+    queue.pause();
+    assert!(queue.is_paused());
+
+    queue.resume();
+    assert!(!queue.is_paused());
+}
+
+#[test]
+fn test_empty_queue() {
+    let queue = PlaybackQueue::new(Vec::new());
+    assert!(queue.is_finished());
+    assert!(queue.current().is_none());
+}
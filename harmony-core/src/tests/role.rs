@@ -0,0 +1,40 @@
+#[cfg(test)]
+use crate::core::model::role::{Permission, Role};
+
+#[test]
+fn test_role_parse() {
+    for role in [
+        Role::Listener,
+        Role::Contributor,
+        Role::Moderator,
+        Role::Admin,
+    ] {
+        assert_eq!(Role::parse(role.as_str()).unwrap(), role);
+    }
+
+    assert!(Role::parse("owner").is_err());
+    assert_eq!(Role::default(), Role::Contributor);
+}
+
+#[test]
+fn test_role_permissions() {
+    assert!(!Role::Listener.allows(Permission::AddSongs));
+    assert!(Role::Contributor.allows(Permission::AddSongs));
+    assert!(!Role::Contributor.allows(Permission::DeleteAnySong));
+    assert!(Role::Moderator.allows(Permission::ManageAnyPlaylist));
+    assert!(!Role::Moderator.allows(Permission::ManageUsers));
+    assert!(Role::Admin.allows(Permission::ManageStations));
+
+    // Every role keeps what the roles below it can do
+    let roles = [
+        Role::Listener,
+        Role::Contributor,
+        Role::Moderator,
+        Role::Admin,
+    ];
+    for pair in roles.windows(2) {
+        for permission in pair[0].permissions() {
+            assert!(pair[1].allows(*permission));
+        }
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+use crate::core::{
+    model::{encoding::EncodingProfile, song::Song},
+    playback::room::Room,
+};
+
+#[cfg(test)]
+fn song(id: i32) -> Song {
+    Song {
+        id,
+        name: format!("Song {id}"),
+        author: None,
+        added_by: Some(1),
+        youtube_url: format!("https://youtu.be/{id}"),
+        file_id: format!("file{id}"),
+        created: None,
+        encoding: EncodingProfile::default(),
+    }
+}
+
+#[test]
+fn test_room_members() {
+    let mut room = Room::new(1, vec![(song(1), 1000)], false, 0);
+    assert_eq!(room.host(), 1);
+    assert!(room.is_member(1));
+    assert!(!room.is_member(2));
+
+    room.join(2);
+    room.join(2);
+    assert!(room.is_member(2));
+    assert_eq!(room.members().len(), 2);
+
+    room.leave(2);
+    assert!(!room.is_member(2));
+    assert_eq!(room.members().len(), 1);
+}
+
+#[test]
+fn test_room_plays_for_everyone_at_once() {
+    let mut room = Room::new(1, vec![(song(1), 1000), (song(2), 1000)], false, 10_000);
+    room.join(2);
+
+    // Members read the same position, only the host moves it
+    let position = room.broadcast().position(11_200).unwrap();
+    assert_eq!((position.index, position.position_ms), (1, 200));
+
+    room.broadcast_mut().pause(11_200);
+    let position = room.broadcast().position(20_000).unwrap();
+    assert_eq!((position.index, position.position_ms), (1, 200));
+}
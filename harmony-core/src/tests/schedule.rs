@@ -0,0 +1,82 @@
+#[cfg(test)]
+use crate::core::model::schedule::Schedule;
+#[cfg(test)]
+use crate::core::validation::validated_types::{CronRule, TimeZone};
+#[cfg(test)]
+use chrono::{DateTime, Utc};
+
+#[cfg(test)]
+fn schedule(id: i32, rule: &str, duration_minutes: i32, timezone: &str, priority: i32) -> Schedule {
+    Schedule {
+        id,
+        name: format!("Schedule {id}"),
+        playlist: 1,
+        station: Some(1),
+        jukebox: None,
+        rule: rule.to_string(),
+        duration_minutes,
+        timezone: timezone.to_string(),
+        priority,
+        created_by: Some(1),
+        created: None,
+    }
+}
+
+#[cfg(test)]
+fn time(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[test]
+fn test_schedule_rules() {
+    assert!(CronRule::new("0 20 * * Sat").is_ok());
+    assert!(CronRule::new("0 0 20 * * Sat *").is_ok());
+    assert!(CronRule::new("every saturday").is_err());
+    assert!(TimeZone::new("Europe/Amsterdam").is_ok());
+    assert!(TimeZone::new("Mars/Olympus").is_err());
+}
+
+#[test]
+fn test_schedule_windows() {
+    // Saturdays from 20:00 to 22:00 in Amsterdam, which is UTC+2 in summer
+    let event = schedule(1, "0 20 * * Sat", 120, "Europe/Amsterdam", 0);
+
+    // 2026-06-06 is a Saturday
+    let window = event.window_at(time("2026-06-06T19:30:00Z")).unwrap();
+    assert_eq!(window.start, time("2026-06-06T18:00:00Z"));
+    assert_eq!(window.end, time("2026-06-06T20:00:00Z"));
+
+    assert!(event.window_at(time("2026-06-06T17:59:59Z")).is_none());
+    assert!(event.window_at(time("2026-06-06T20:00:00Z")).is_none());
+    assert!(event.window_at(time("2026-06-07T19:00:00Z")).is_none());
+
+    // Every night from 22:00 until 06:00, crossing midnight
+    let night = schedule(2, "0 22 * * *", 8 * 60, "UTC", 0);
+    let window = night.window_at(time("2026-06-07T03:00:00Z")).unwrap();
+    assert_eq!(window.start, time("2026-06-06T22:00:00Z"));
+    assert!(night.window_at(time("2026-06-07T12:00:00Z")).is_none());
+}
+
+#[test]
+fn test_schedule_in_effect() {
+    let night = schedule(1, "0 22 * * *", 8 * 60, "UTC", 0);
+    let event = schedule(2, "0 20 * * Sat", 4 * 60, "UTC", 10);
+    let late = schedule(3, "0 23 * * *", 60, "UTC", 0);
+    let schedules = vec![night, event, late];
+
+    // The event outranks the nightly music on Saturday
+    let (schedule, _) = Schedule::in_effect(&schedules, time("2026-06-06T22:30:00Z")).unwrap();
+    assert_eq!(schedule.id, 2);
+
+    // Between equal priorities the window that started last wins
+    let (schedule, _) = Schedule::in_effect(&schedules, time("2026-06-07T23:30:00Z")).unwrap();
+    assert_eq!(schedule.id, 3);
+    assert_eq!(
+        Schedule::active(&schedules, time("2026-06-07T23:30:00Z")).len(),
+        2
+    );
+
+    assert!(Schedule::in_effect(&schedules, time("2026-06-07T12:00:00Z")).is_none());
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+use crate::core::model::share_code::{ShareCode, ShareTarget};
+#[cfg(test)]
+use harmony_data::model::share_code::ShareCodeModel;
+
+#[cfg(test)]
+fn model(song: Option<i32>, playlist: Option<i32>) -> ShareCodeModel {
+    ShareCodeModel {
+        code: "3X7K9Q".to_string(),
+        song,
+        playlist,
+        created: None,
+    }
+}
+
+#[test]
+fn test_share_code_targets() {
+    let share_code = ShareCode::from_model(&model(Some(4), None)).unwrap();
+    assert_eq!(share_code.code, "3X7K9Q");
+    assert_eq!(share_code.target, ShareTarget::Song(4));
+
+    let share_code = ShareCode::from_model(&model(None, Some(7))).unwrap();
+    assert_eq!(share_code.target, ShareTarget::Playlist(7));
+
+    // A code points to exactly one song or playlist
+    assert!(ShareCode::from_model(&model(None, None)).is_err());
+    assert!(ShareCode::from_model(&model(Some(4), Some(7))).is_err());
+}
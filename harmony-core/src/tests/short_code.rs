@@ -0,0 +1,24 @@
+#[cfg(test)]
+use crate::utils::short_code::{generate, normalize, ALPHABET};
+
+#[test]
+fn test_short_code_generate() {
+    for length in [4, 6, 8] {
+        let code = generate(length);
+        assert_eq!(code.chars().count(), length);
+        assert!(code.chars().all(|c| ALPHABET.contains(&c)));
+    }
+
+    // None of the characters that are easily confused
+    for c in ['0', 'O', '1', 'I', 'L', '2', 'Z', '5', 'S', '8', 'B'] {
+        assert!(!ALPHABET.contains(&c));
+    }
+}
+
+#[test]
+fn test_short_code_normalize() {
+    assert_eq!(normalize("abc-def"), "ABCDEF");
+    assert_eq!(normalize(" 3x7 k9q "), "3X7K9Q");
+    assert_eq!(normalize("3X7-K9Q"), "3X7K9Q");
+    assert_eq!(normalize(""), "");
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+use crate::core::playback::shuffle::shuffle;
+
+#[test]
+fn test_shuffle_is_deterministic() {
+    let mut first: Vec<i32> = (0..50).collect();
+    let mut second: Vec<i32> = (0..50).collect();
+    shuffle(&mut first, 42);
+    shuffle(&mut second, 42);
+    assert_eq!(first, second);
+
+    // Still the same items, in a different order
+    assert_ne!(first, (0..50).collect::<Vec<i32>>());
+    let mut sorted = first.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..50).collect::<Vec<i32>>());
+
+    let mut other: Vec<i32> = (0..50).collect();
+    shuffle(&mut other, 43);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn test_shuffle_small_lists() {
+    let mut empty: Vec<i32> = Vec::new();
+    shuffle(&mut empty, 1);
+    assert!(empty.is_empty());
+
+    let mut single = vec![7];
+    shuffle(&mut single, 1);
+    assert_eq!(single, vec![7]);
+}
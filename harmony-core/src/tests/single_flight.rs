@@ -0,0 +1,95 @@
+#[cfg(test)]
+use crate::utils::single_flight::SingleFlight;
+#[cfg(test)]
+use std::sync::atomic::{AtomicU32, Ordering};
+#[cfg(test)]
+use std::sync::{Arc, Barrier};
+#[cfg(test)]
+use std::thread;
+#[cfg(test)]
+use std::time::Duration;
+
+#[test]
+fn test_concurrent_runs_share_one_result() {
+    let flight = Arc::new(SingleFlight::<String, u32>::new());
+    let runs = Arc::new(AtomicU32::new(0));
+    let barrier = Arc::new(Barrier::new(4));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let flight = Arc::clone(&flight);
+            let runs = Arc::clone(&runs);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                flight.run("url".to_string(), || {
+                    // Long enough for the other threads to find the run
+                    thread::sleep(Duration::from_millis(200));
+                    runs.fetch_add(1, Ordering::SeqCst) + 10
+                })
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Some(10));
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_later_runs_start_again() {
+    let flight = SingleFlight::<String, u32>::new();
+
+    assert_eq!(flight.run("url".to_string(), || 1), Some(1));
+    assert_eq!(flight.run("url".to_string(), || 2), Some(2));
+}
+
+#[test]
+fn test_other_keys_run_separately() {
+    let flight = Arc::new(SingleFlight::<String, u32>::new());
+    let barrier = Arc::new(Barrier::new(2));
+
+    let handles: Vec<_> = (0..2)
+        .map(|key| {
+            let flight = Arc::clone(&flight);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                flight.run(key.to_string(), || {
+                    // Both runs have to be going at once to get past this
+                    barrier.wait();
+                    key
+                })
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    assert_eq!(results, vec![Some(0), Some(1)]);
+}
+
+#[test]
+fn test_waiters_are_let_go_when_the_run_panics() {
+    let flight = Arc::new(SingleFlight::<String, u32>::new());
+    let started = Arc::new(Barrier::new(2));
+
+    let leader = {
+        let flight = Arc::clone(&flight);
+        let started = Arc::clone(&started);
+        thread::spawn(move || {
+            flight.run("url".to_string(), || {
+                started.wait();
+                thread::sleep(Duration::from_millis(200));
+                panic!("download failed");
+            })
+        })
+    };
+
+    started.wait();
+    assert_eq!(flight.run("url".to_string(), || 1), None);
+    assert!(leader.join().is_err());
+    assert_eq!(flight.run("url".to_string(), || 2), Some(2));
+}
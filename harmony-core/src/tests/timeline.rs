@@ -0,0 +1,90 @@
+#[cfg(test)]
+use crate::core::playback::timeline::{Timeline, TimelinePosition};
+
+#[test]
+fn test_timeline_follows_the_clock() {
+    let timeline = Timeline::new(vec![1000, 2000], false, 10_000);
+
+    assert_eq!(
+        timeline.position(10_000),
+        Some(TimelinePosition {
+            index: 0,
+            position_ms: 0,
+            track_started_at: 10_000
+        })
+    );
+
+    // A late joiner lands in the middle of the second track
+    assert_eq!(
+        timeline.position(11_500),
+        Some(TimelinePosition {
+            index: 1,
+            position_ms: 500,
+            track_started_at: 11_000
+        })
+    );
+
+    assert_eq!(timeline.position(13_000), None);
+}
+
+#[test]
+fn test_timeline_pause_and_resume() {
+    let mut timeline = Timeline::new(vec![1000, 2000], false, 0);
+
+    timeline.pause(400);
+    assert!(timeline.is_paused());
+    assert_eq!(timeline.position(5000).unwrap().position_ms, 400);
+
+    timeline.resume(5000);
+    let position = timeline.position(5100).unwrap();
+    assert_eq!(position.index, 0);
+    assert_eq!(position.position_ms, 500);
+    assert_eq!(position.track_started_at, 4600);
+}
+
+#[test]
+fn test_timeline_seek_skip_and_previous() {
+    let mut timeline = Timeline::new(vec![1000, 2000, 3000], false, 0);
+
+    timeline.seek(100, 900);
+    assert_eq!(timeline.position(200).unwrap().index, 1);
+
+    timeline.skip(200);
+    let position = timeline.position(200).unwrap();
+    assert_eq!((position.index, position.position_ms), (2, 0));
+
+    timeline.previous(300);
+    let position = timeline.position(300).unwrap();
+    assert_eq!((position.index, position.position_ms), (1, 0));
+
+    timeline.skip(300);
+    timeline.skip(300);
+    assert_eq!(timeline.position(300), None);
+
+    // Going back from the end restarts the last track
+    timeline.previous(400);
+    assert_eq!(timeline.position(400).unwrap().index, 2);
+}
+
+#[test]
+fn test_timeline_repeat() {
+    let mut timeline = Timeline::new(vec![1000, 2000], true, 0);
+
+    // Many passes later the position still follows the loop
+    let position = timeline.position(30_000 * 100 + 1500).unwrap();
+    assert_eq!((position.index, position.position_ms), (1, 500));
+
+    timeline.skip(1500);
+    assert_eq!(timeline.position(1500).unwrap().index, 0);
+
+    assert_eq!(Timeline::new(vec![0, 0], true, 0).position(100), None);
+}
+
+#[test]
+fn test_timeline_starting_at() {
+    let timeline = Timeline::starting_at(vec![1000, 2000], false, 1, 1500, 0);
+
+    let position = timeline.position(200).unwrap();
+    assert_eq!((position.index, position.position_ms), (1, 1700));
+    assert_eq!(timeline.position(600), None);
+}
@@ -1,18 +1,23 @@
+use crate::core::model::encoding::EncodingProfile;
 use crate::result::ServerError;
 use std::process::Command;
 
-// Download a video and return the filename
-pub fn convert_to_dfpwm(file: &str, output_file: &str) -> Result<(), ServerError> {
+// Convert an audio file to the given encoding profile
+pub fn convert_audio(
+    file: &str,
+    output_file: &str,
+    profile: &EncodingProfile,
+) -> Result<(), ServerError> {
     let status = Command::new("ffmpeg")
         .args([
             "-i",
             file,
             "-ac",
-            "1",
+            &profile.channels.to_string(),
             "-ar",
-            "48k",
+            &profile.sample_rate.to_string(),
             "-c:a",
-            "dfpwm",
+            profile.codec.as_str(),
             output_file,
         ])
         .status()
@@ -1,7 +1,8 @@
+use memmap2::Mmap;
 use std::fs;
 use std::fs::File;
-use std::io::Read;
 use std::path::Path;
+use std::time::SystemTime;
 
 use crate::result::ServerError;
 
@@ -16,20 +17,30 @@ pub fn remove(file: &str) -> Result<(), ServerError> {
     }
 }
 
-// Read a specified number of chunks from the converted audio file
-pub fn read_data(file: &str) -> Result<Vec<u8>, ServerError> {
+// Map the converted audio file into memory so ranges can be read without copying
+pub fn map_data(file: &str) -> Result<Mmap, ServerError> {
     // Check if the file exists
     if !Path::new(&file).exists() {
         return Err(ServerError::FileNotFound);
     }
 
     // Open the file
-    let mut file = File::open(file).map_err(|_| ServerError::ExecutionFailed)?;
+    let file = File::open(file).map_err(|_| ServerError::ExecutionFailed)?;
 
-    // Read the entire file into a buffer
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
-        .map_err(|_| ServerError::ExecutionFailed)?;
+    // SAFETY: converted songs are written once by `pull_song` and never modified
+    // afterwards, so the mapping cannot change underneath us
+    unsafe { Mmap::map(&file) }.map_err(|_| ServerError::ExecutionFailed)
+}
+
+// Get the last modification time of a file, if the platform supports it
+pub fn modified(file: &str) -> Option<SystemTime> {
+    fs::metadata(file)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
 
-    Ok(buffer)
+// Get the size of a file in bytes without reading it
+pub fn size(file: &str) -> Result<usize, ServerError> {
+    let metadata = fs::metadata(file).map_err(|_| ServerError::FileNotFound)?;
+    usize::try_from(metadata.len()).map_err(|_| ServerError::ExecutionFailed)
 }
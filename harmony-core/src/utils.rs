@@ -1,2 +1,8 @@
+pub mod byte_size;
 pub mod environment;
+pub mod mail;
+pub mod password;
+pub mod secret;
+pub mod short_code;
+pub mod single_flight;
 pub mod song_file;
@@ -0,0 +1,34 @@
+/// Parses a size the way yt-dlp takes them, such as `10M` or `2G`, suffixes count in 1024s
+#[must_use]
+pub fn parse(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (number, multiplier) = match size.char_indices().last()? {
+        (index, 'K' | 'k') => (&size[..index], 1 << 10),
+        (index, 'M' | 'm') => (&size[..index], 1 << 20),
+        (index, 'G' | 'g') => (&size[..index], 1 << 30),
+        (index, 'T' | 't') => (&size[..index], 1 << 40),
+        _ => (size, 1),
+    };
+
+    number.trim().parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// Formats a size for people to read, such as `1.5 MiB`
+#[must_use]
+pub fn format(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    // Tenths are enough, so the division is done in whole numbers
+    let mut tenths = u128::from(bytes) * 10 / 1024;
+    let mut unit = 0;
+    while tenths >= 10 * 1024 && unit < UNITS.len() - 1 {
+        tenths /= 1024;
+        unit += 1;
+    }
+
+    format!("{}.{} {}", tenths / 10, tenths % 10, UNITS[unit])
+}
@@ -1,6 +1,9 @@
 use dotenvy::dotenv;
 use std::env;
 
+use super::byte_size;
+use super::mail::SmtpSecurity;
+
 fn init_environment() {
     dotenv().ok();
 }
@@ -28,3 +31,108 @@ pub fn get_song_directory() -> String {
     init_environment();
     env::var("SONG_DIRECTORY").expect("SONG_DIRECTORY must be set.")
 }
+
+/// How many songs a user may add a day, unset or 0 for no limit
+#[must_use]
+pub fn get_song_daily_quota() -> Option<u32> {
+    init_environment();
+    env::var("SONG_DAILY_QUOTA")
+        .ok()
+        .map(|quota| {
+            quota
+                .parse::<u32>()
+                .expect("SONG_DAILY_QUOTA must be a valid positive number")
+        })
+        .filter(|quota| *quota > 0)
+}
+
+/// How much audio a user may store in bytes, such as `500M`, unset or 0 for no limit
+#[must_use]
+pub fn get_storage_quota() -> Option<u64> {
+    init_environment();
+    env::var("STORAGE_QUOTA")
+        .ok()
+        .map(|quota| {
+            byte_size::parse(&quota).expect("STORAGE_QUOTA must be a size such as 500M or 2G")
+        })
+        .filter(|quota| *quota > 0)
+}
+
+/// How much memory a password hash uses in KiB, defaults to 19 MiB
+#[must_use]
+pub fn get_password_hash_memory() -> u32 {
+    init_environment();
+    env::var("PASSWORD_HASH_MEMORY").map_or(19_456, |memory| {
+        memory
+            .parse::<u32>()
+            .expect("PASSWORD_HASH_MEMORY must be a valid positive number")
+    })
+}
+
+/// How many passes a password hash makes over its memory, defaults to 2
+#[must_use]
+pub fn get_password_hash_iterations() -> u32 {
+    init_environment();
+    env::var("PASSWORD_HASH_ITERATIONS").map_or(2, |iterations| {
+        iterations
+            .parse::<u32>()
+            .expect("PASSWORD_HASH_ITERATIONS must be a valid positive number")
+    })
+}
+
+/// How many lanes a password hash runs in, defaults to 1
+#[must_use]
+pub fn get_password_hash_parallelism() -> u32 {
+    init_environment();
+    env::var("PASSWORD_HASH_PARALLELISM").map_or(1, |parallelism| {
+        parallelism
+            .parse::<u32>()
+            .expect("PASSWORD_HASH_PARALLELISM must be a valid positive number")
+    })
+}
+
+/// The SMTP server mail is sent through, mail is turned off when unset
+#[must_use]
+pub fn get_smtp_host() -> Option<String> {
+    init_environment();
+    env::var("SMTP_HOST").ok().filter(|host| !host.is_empty())
+}
+
+/// The port of the SMTP server, defaults to the usual one for its security
+#[must_use]
+pub fn get_smtp_port() -> Option<u16> {
+    init_environment();
+    env::var("SMTP_PORT").ok().map(|port| {
+        port.parse::<u16>()
+            .expect("SMTP_PORT must be a valid port number")
+    })
+}
+
+/// How the connection to the SMTP server is secured, defaults to STARTTLS
+#[must_use]
+pub fn get_smtp_security() -> SmtpSecurity {
+    init_environment();
+    env::var("SMTP_SECURITY").map_or(SmtpSecurity::StartTls, |security| {
+        SmtpSecurity::parse(&security).expect("SMTP_SECURITY must be starttls, tls or none")
+    })
+}
+
+#[must_use]
+pub fn get_smtp_username() -> Option<String> {
+    init_environment();
+    env::var("SMTP_USERNAME").ok()
+}
+
+#[must_use]
+pub fn get_smtp_password() -> Option<String> {
+    init_environment();
+    env::var("SMTP_PASSWORD").ok()
+}
+
+/// # Panics
+/// When the environment variable is not set
+#[must_use]
+pub fn get_mail_from() -> String {
+    init_environment();
+    env::var("MAIL_FROM").expect("MAIL_FROM must be set when SMTP_HOST is.")
+}
@@ -0,0 +1,83 @@
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+use crate::result::ServerError;
+
+use super::environment;
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Upgrades a plain connection, usually on port 587
+    StartTls,
+    /// Encrypted from the start, usually on port 465
+    Tls,
+    /// Nothing, only for a mail sink on the same machine
+    None,
+}
+
+impl SmtpSecurity {
+    #[must_use]
+    pub fn parse(security: &str) -> Option<Self> {
+        match security.trim() {
+            "starttls" => Some(SmtpSecurity::StartTls),
+            "tls" => Some(SmtpSecurity::Tls),
+            "none" => Some(SmtpSecurity::None),
+            _ => None,
+        }
+    }
+}
+
+/// Whether an SMTP server is set up, without one nothing can be mailed
+#[must_use]
+pub fn is_configured() -> bool {
+    environment::get_smtp_host().is_some()
+}
+
+/// Sends a plain text mail through the SMTP server of the server
+pub fn send(to: &str, subject: &str, body: String) -> Result<(), ServerError> {
+    let Some(host) = environment::get_smtp_host() else {
+        return Err(ServerError::MailFailed(
+            "No SMTP server is set up".to_string(),
+        ));
+    };
+
+    let from = environment::get_mail_from()
+        .parse::<Mailbox>()
+        .map_err(|error| ServerError::MailFailed(format!("Invalid MAIL_FROM: {error}")))?;
+    let to = to
+        .parse::<Mailbox>()
+        .map_err(|error| ServerError::MailFailed(format!("Invalid address: {error}")))?;
+
+    let message = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)
+        .map_err(|error| ServerError::MailFailed(error.to_string()))?;
+
+    let mut transport = match environment::get_smtp_security() {
+        SmtpSecurity::StartTls => SmtpTransport::starttls_relay(&host),
+        SmtpSecurity::Tls => SmtpTransport::relay(&host),
+        SmtpSecurity::None => Ok(SmtpTransport::builder_dangerous(&host)),
+    }
+    .map_err(|error| ServerError::MailFailed(error.to_string()))?;
+
+    if let Some(port) = environment::get_smtp_port() {
+        transport = transport.port(port);
+    }
+    if let Some(username) = environment::get_smtp_username() {
+        transport = transport.credentials(Credentials::new(
+            username,
+            environment::get_smtp_password().unwrap_or_default(),
+        ));
+    }
+
+    transport
+        .build()
+        .send(&message)
+        .map(|_| ())
+        .map_err(|error| ServerError::MailFailed(error.to_string()))
+}
@@ -0,0 +1,120 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use nanoid::nanoid;
+use std::sync::OnceLock;
+
+use crate::result::ServerError;
+
+use super::environment;
+
+const TEMPORARY_LETTERS: [char; 23] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'j', 'k', 'm', 'n', 'p', 'q', 'r', 's', 't', 'u', 'v',
+    'w', 'x', 'y', 'z',
+];
+const TEMPORARY_DIGITS: [char; 8] = ['2', '3', '4', '5', '6', '7', '8', '9'];
+
+/// How much work an Argon2id hash takes, raising it makes older hashes get redone on login
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashParams {
+    /// Memory used in KiB
+    pub memory: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl HashParams {
+    /// The parameters set for the server
+    #[must_use]
+    pub fn from_environment() -> Self {
+        Self {
+            memory: environment::get_password_hash_memory(),
+            iterations: environment::get_password_hash_iterations(),
+            parallelism: environment::get_password_hash_parallelism(),
+        }
+    }
+
+    fn hasher(self) -> Result<Argon2<'static>, ServerError> {
+        let params = Params::new(self.memory, self.iterations, self.parallelism, None)
+            .map_err(|_| ServerError::HashFailed)?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+/// Hashes a password for storage with the parameters set for the server
+pub fn hash(password: &str) -> Result<String, ServerError> {
+    hash_with(password, HashParams::from_environment())
+}
+
+/// Hashes a password for storage with Argon2id
+pub fn hash_with(password: &str, params: HashParams) -> Result<String, ServerError> {
+    let salt = SaltString::generate(&mut OsRng);
+    params
+        .hasher()?
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|_| ServerError::HashFailed)
+}
+
+/// Checks a password against a stored hash, accounts from before Argon2 still have bcrypt ones
+pub fn verify(password: &str, hash: &str) -> Result<bool, ServerError> {
+    if is_bcrypt(hash) {
+        return bcrypt::verify(password, hash).map_err(|_| ServerError::HashFailed);
+    }
+
+    let parsed = PasswordHash::new(hash).map_err(|_| ServerError::HashFailed)?;
+    // The parameters are read from the hash itself, so any are accepted here
+    match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(_) => Err(ServerError::HashFailed),
+    }
+}
+
+/// Whether a hash should be redone with the password once it is known,
+/// because it is bcrypt or was made with other parameters
+#[must_use]
+pub fn needs_rehash(hash: &str, params: HashParams) -> bool {
+    if is_bcrypt(hash) {
+        return true;
+    }
+
+    let Ok(parsed) = PasswordHash::new(hash) else {
+        return true;
+    };
+    let Ok(current) = Params::try_from(&parsed) else {
+        return true;
+    };
+
+    parsed.algorithm != Algorithm::Argon2id.ident()
+        || parsed.version != Some(Version::V0x13.into())
+        || current.m_cost() != params.memory
+        || current.t_cost() != params.iterations
+        || current.p_cost() != params.parallelism
+}
+
+// bcrypt hashes start with their version, such as $2b$
+fn is_bcrypt(hash: &str) -> bool {
+    hash.starts_with("$2")
+}
+
+/// Checks a password against a hash no account has, to take as long as checking a real one.
+/// Used when the user does not exist, so that can't be found out by timing the login
+pub fn verify_dummy(password: &str) {
+    static DUMMY_HASH: OnceLock<Option<String>> = OnceLock::new();
+
+    if let Some(dummy) = DUMMY_HASH.get_or_init(|| hash(&generate_temporary()).ok()) {
+        let _ = verify(password, dummy);
+    }
+}
+
+/// Generates a password to hand to a user once, such as `kxmwq-48273`.
+/// It passes the password rules, so it can be used to log in right away
+#[must_use]
+pub fn generate_temporary() -> String {
+    format!(
+        "{}-{}",
+        nanoid!(5, &TEMPORARY_LETTERS),
+        nanoid!(5, &TEMPORARY_DIGITS)
+    )
+}
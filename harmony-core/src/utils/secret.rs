@@ -0,0 +1,18 @@
+use nanoid::nanoid;
+use sha2::{Digest, Sha256};
+
+/// Generates a random secret, the prefix tells the kinds of secrets apart
+#[must_use]
+pub fn generate(prefix: &str) -> String {
+    format!("{prefix}{}", nanoid!(40))
+}
+
+/// Hashes a secret for storage. Secrets are random enough that a plain digest
+/// is safe, unlike passwords they don't need a slow hash
+#[must_use]
+pub fn hash(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
@@ -0,0 +1,24 @@
+use nanoid::nanoid;
+
+/// Upper case letters and digits without the ones that are easily confused
+/// when read out loud or typed on an in-game computer (0/O, 1/I/L, 2/Z, 5/S, 8/B)
+pub const ALPHABET: [char; 25] = [
+    '3', '4', '6', '7', '9', 'A', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'M', 'N', 'P', 'Q', 'R',
+    'T', 'U', 'V', 'W', 'X', 'Y',
+];
+
+/// Generates a random code of the given length from the short code alphabet
+#[must_use]
+pub fn generate(length: usize) -> String {
+    nanoid!(length, &ALPHABET)
+}
+
+/// Turns a code as typed by a user into its canonical form,
+/// ignoring case, spaces and dashes
+#[must_use]
+pub fn normalize(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
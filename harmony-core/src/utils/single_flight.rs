@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+enum State<V> {
+    Running,
+    Done(V),
+    // The work panicked, there is nothing to hand out
+    Abandoned,
+}
+
+struct Call<V> {
+    state: Mutex<State<V>>,
+    finished: Condvar,
+}
+
+/// Runs work once per key at a time. Callers that ask for a key while its work is
+/// running wait for it and get a copy of the result, instead of doing it again
+pub struct SingleFlight<K, V> {
+    calls: Mutex<HashMap<K, Arc<Call<V>>>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SingleFlight<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs the work, or waits for the run of the key that already started.
+    /// Returns `None` to the waiters when that run panicked
+    pub fn run(&self, key: K, work: impl FnOnce() -> V) -> Option<V> {
+        let (call, leader) = {
+            let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+            match calls.get(&key) {
+                Some(call) => (Arc::clone(call), false),
+                None => {
+                    let call = Arc::new(Call {
+                        state: Mutex::new(State::Running),
+                        finished: Condvar::new(),
+                    });
+                    calls.insert(key.clone(), Arc::clone(&call));
+                    (call, true)
+                }
+            }
+        };
+
+        if !leader {
+            let state = call.state.lock().unwrap_or_else(PoisonError::into_inner);
+            let state = call
+                .finished
+                .wait_while(state, |state| matches!(state, State::Running))
+                .unwrap_or_else(PoisonError::into_inner);
+
+            return match &*state {
+                State::Done(value) => Some(value.clone()),
+                State::Running | State::Abandoned => None,
+            };
+        }
+
+        // Lets the waiters go even when the work panics
+        let finish = Finish {
+            flight: self,
+            key,
+            call: &call,
+        };
+        let value = work();
+        finish.done(value.clone());
+        Some(value)
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Finish<'a, K: Eq + Hash, V> {
+    flight: &'a SingleFlight<K, V>,
+    key: K,
+    call: &'a Call<V>,
+}
+
+impl<K: Eq + Hash, V> Finish<'_, K, V> {
+    fn done(self, value: V) {
+        *self
+            .call
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = State::Done(value);
+    }
+}
+
+impl<K: Eq + Hash, V> Drop for Finish<'_, K, V> {
+    fn drop(&mut self) {
+        // Callers that come after this start a new run, the result is only shared with the waiters
+        self.flight
+            .calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.key);
+
+        let mut state = self
+            .call
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if matches!(*state, State::Running) {
+            *state = State::Abandoned;
+        }
+        self.call.finished.notify_all();
+    }
+}
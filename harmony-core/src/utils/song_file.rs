@@ -1,5 +1,9 @@
+use memmap2::Mmap;
 use nanoid::nanoid;
+use std::time::SystemTime;
 
+use crate::core::model::encoding::EncodingProfile;
+use crate::core::model::song::Song;
 use crate::result::ServerError;
 use crate::tools::ffmpeg;
 use crate::tools::file;
@@ -19,10 +23,11 @@ fn download_song(youtube_url: &str) -> Result<String, ServerError> {
     Ok(file_id)
 }
 
-fn convert_song(song_id: &str) -> Result<(), ServerError> {
-    ffmpeg::convert_to_dfpwm(
+fn convert_song(song_id: &str, profile: &EncodingProfile) -> Result<(), ServerError> {
+    ffmpeg::convert_audio(
         &format!("{}/{}.mp3", environment::get_download_directory(), song_id).to_string(),
         &format!("{}/{}.dfpwm", environment::get_song_directory(), song_id).to_string(),
+        profile,
     )
 }
 
@@ -30,14 +35,87 @@ fn clean_song(song_id: &str) -> Result<(), ServerError> {
     file::remove(&format!("{}/{}.mp3", environment::get_download_directory(), song_id).to_string())
 }
 
-pub fn get_song_data(file_id: &str) -> Result<Vec<u8>, ServerError> {
-    file::read_data(&format!("{}/{}.dfpwm", environment::get_song_directory(), file_id).to_string())
+/// A read-only view of a converted song, backed by a memory map of the file
+#[derive(Debug)]
+pub struct SongData {
+    map: Mmap,
+    modified: Option<SystemTime>,
+}
+
+impl SongData {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// The time the song file was last written
+    #[must_use]
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+}
+
+impl AsRef<[u8]> for SongData {
+    fn as_ref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+/// Maps the song file into memory, pages are only read from disk once they are accessed
+pub fn get_song_data(file_id: &str) -> Result<SongData, ServerError> {
+    let path = format!("{}/{}.dfpwm", environment::get_song_directory(), file_id);
+    let map = file::map_data(&path)?;
+    let modified = file::modified(&path);
+    Ok(SongData { map, modified })
+}
+
+/// The size of the song file in bytes, without mapping it
+pub fn get_song_size(file_id: &str) -> Result<usize, ServerError> {
+    file::size(&format!(
+        "{}/{}.dfpwm",
+        environment::get_song_directory(),
+        file_id
+    ))
+}
+
+/// How long the song plays, from the size of its file
+/// # Errors
+/// When the song file does not exist
+pub fn get_song_duration(song: &Song) -> Result<u64, ServerError> {
+    let size = get_song_size(&song.file_id)?;
+    Ok(song.encoding.duration_ms(size))
+}
+
+/// Pairs the songs with how long they play, songs without audio get no time
+#[must_use]
+pub fn with_durations(songs: Vec<Song>) -> Vec<(Song, u64)> {
+    songs
+        .into_iter()
+        .map(|song| {
+            let duration_ms = get_song_duration(&song).unwrap_or(0);
+            (song, duration_ms)
+        })
+        .collect()
+}
+
+/// Deletes the converted file of a song, streams that already mapped it keep playing
+pub fn remove_song(file_id: &str) -> Result<(), ServerError> {
+    file::remove(&format!(
+        "{}/{}.dfpwm",
+        environment::get_song_directory(),
+        file_id
+    ))
 }
 
 /// Returns the file ID of the song pulled and converted
-pub fn pull_song(youtube_url: &str) -> Result<String, ServerError> {
+pub fn pull_song(youtube_url: &str, profile: &EncodingProfile) -> Result<String, ServerError> {
     let id = download_song(youtube_url)?;
-    convert_song(&id)?;
+    convert_song(&id, profile)?;
     clean_song(&id)?;
     Ok(id)
 }
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE songs
+  DROP COLUMN codec,
+  DROP COLUMN sample_rate,
+  DROP COLUMN channels
\ No newline at end of file
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+
+ALTER TABLE songs
+  ADD COLUMN codec VARCHAR(16) NOT NULL DEFAULT 'dfpwm',
+  ADD COLUMN sample_rate INTEGER NOT NULL DEFAULT 48000,
+  ADD COLUMN channels INTEGER NOT NULL DEFAULT 1
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+CREATE TABLE stations (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR(32) NOT NULL UNIQUE,
+  playlist INTEGER,
+  shuffle BOOL NOT NULL DEFAULT FALSE,
+  created_by INTEGER NOT NULL,
+  started TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_station_playlist_id FOREIGN KEY (playlist) REFERENCES playlists(id) ON DELETE CASCADE,
+  CONSTRAINT fk_station_user_id FOREIGN KEY (created_by) REFERENCES users(id)
+)
\ No newline at end of file
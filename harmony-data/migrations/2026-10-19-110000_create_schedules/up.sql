@@ -0,0 +1,20 @@
+-- Your SQL goes here
+
+CREATE TABLE schedules (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR(32) NOT NULL,
+  playlist INTEGER NOT NULL,
+  station INTEGER,
+  jukebox VARCHAR(16),
+  rule VARCHAR(255) NOT NULL,
+  duration_minutes INTEGER NOT NULL,
+  timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+  priority INTEGER NOT NULL DEFAULT 0,
+  created_by INTEGER NOT NULL,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_schedule_playlist_id FOREIGN KEY (playlist) REFERENCES playlists(id) ON DELETE CASCADE,
+  CONSTRAINT fk_schedule_station_id FOREIGN KEY (station) REFERENCES stations(id) ON DELETE CASCADE,
+  CONSTRAINT fk_schedule_user_id FOREIGN KEY (created_by) REFERENCES users(id),
+  -- A schedule plays on either a station or a jukebox
+  CONSTRAINT schedule_target CHECK ((station IS NULL) <> (jukebox IS NULL))
+)
\ No newline at end of file
@@ -0,0 +1,38 @@
+-- Your SQL goes here
+
+CREATE TABLE share_codes (
+  code VARCHAR(8) PRIMARY KEY,
+  song INTEGER UNIQUE,
+  playlist INTEGER UNIQUE,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_share_code_song_id FOREIGN KEY (song) REFERENCES songs(id) ON DELETE CASCADE,
+  CONSTRAINT fk_share_code_playlist_id FOREIGN KEY (playlist) REFERENCES playlists(id) ON DELETE CASCADE,
+  -- A code points to either a song or a playlist
+  CONSTRAINT share_code_target CHECK ((song IS NULL) <> (playlist IS NULL))
+);
+
+-- Give every existing song and playlist a code, from the same alphabet as the server uses
+DO $$
+DECLARE
+  alphabet CONSTANT TEXT := '34679ACDEFGHJKMNPQRTUVWXY';
+  target RECORD;
+  new_code TEXT;
+BEGIN
+  FOR target IN
+    SELECT id AS song, NULL::INTEGER AS playlist FROM songs
+    UNION ALL
+    SELECT NULL::INTEGER, id FROM playlists
+  LOOP
+    LOOP
+      new_code := '';
+      FOR i IN 1..6 LOOP
+        new_code := new_code || substr(alphabet, floor(random() * length(alphabet))::INTEGER + 1, 1);
+      END LOOP;
+
+      EXIT WHEN NOT EXISTS (SELECT 1 FROM share_codes WHERE code = new_code);
+    END LOOP;
+
+    INSERT INTO share_codes (code, song, playlist) VALUES (new_code, target.song, target.playlist);
+  END LOOP;
+END
+$$
\ No newline at end of file
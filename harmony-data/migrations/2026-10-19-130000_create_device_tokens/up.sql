@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+CREATE TABLE device_tokens (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  name VARCHAR(32) NOT NULL,
+  -- Only a hash of the token is kept, the token itself is shown once when it is created
+  hash VARCHAR(64) NOT NULL UNIQUE,
+  scopes VARCHAR(64) NOT NULL,
+  last_used TIMESTAMP WITH TIME ZONE,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_device_token_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+)
\ No newline at end of file
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE auth_sessions (
+  id VARCHAR(21) PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  -- Refresh tokens are rotated on every use, the previous one is kept to notice when it is used again
+  refresh_hash VARCHAR(64) NOT NULL UNIQUE,
+  previous_hash VARCHAR(64),
+  expires TIMESTAMP WITH TIME ZONE NOT NULL,
+  refreshed TIMESTAMP WITH TIME ZONE,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_auth_session_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX auth_sessions_previous_hash ON auth_sessions (previous_hash)
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+
+ALTER TABLE users ADD COLUMN role VARCHAR(16) NOT NULL DEFAULT 'contributor'
+  CONSTRAINT user_role CHECK (role IN ('listener', 'contributor', 'moderator', 'admin'));
+
+UPDATE users SET role = 'admin' WHERE admin;
+
+ALTER TABLE users DROP COLUMN admin;
\ No newline at end of file
@@ -0,0 +1,32 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE playlists_songs DROP CONSTRAINT fk_playlist_song_playlist_id;
+ALTER TABLE playlists_songs ADD CONSTRAINT fk_playlist_song_playlist_id
+  FOREIGN KEY (playlist) REFERENCES playlists(id);
+
+ALTER TABLE playlists DROP CONSTRAINT fk_playlist_user_id;
+ALTER TABLE playlists ADD CONSTRAINT fk_playlist_user_id
+  FOREIGN KEY (owner) REFERENCES users(id);
+
+-- Rows left behind by deleted users can't be given back a creator
+DELETE FROM schedules WHERE created_by IS NULL;
+ALTER TABLE schedules DROP CONSTRAINT fk_schedule_user_id;
+ALTER TABLE schedules ADD CONSTRAINT fk_schedule_user_id
+  FOREIGN KEY (created_by) REFERENCES users(id);
+ALTER TABLE schedules ALTER COLUMN created_by SET NOT NULL;
+
+DELETE FROM stations WHERE created_by IS NULL;
+ALTER TABLE stations DROP CONSTRAINT fk_station_user_id;
+ALTER TABLE stations ADD CONSTRAINT fk_station_user_id
+  FOREIGN KEY (created_by) REFERENCES users(id);
+ALTER TABLE stations ALTER COLUMN created_by SET NOT NULL;
+
+DELETE FROM playlists_songs WHERE song IN (SELECT id FROM songs WHERE added_by IS NULL);
+DELETE FROM songs WHERE added_by IS NULL;
+ALTER TABLE songs DROP CONSTRAINT fk_song_user_id;
+ALTER TABLE songs ADD CONSTRAINT fk_song_user_id
+  FOREIGN KEY (added_by) REFERENCES users(id);
+ALTER TABLE songs ALTER COLUMN added_by SET NOT NULL;
+
+ALTER TABLE users DROP COLUMN suspension_reason;
+ALTER TABLE users DROP COLUMN suspended;
\ No newline at end of file
@@ -0,0 +1,29 @@
+-- Your SQL goes here
+
+ALTER TABLE users ADD COLUMN suspended TIMESTAMP WITH TIME ZONE;
+ALTER TABLE users ADD COLUMN suspension_reason VARCHAR(255);
+
+-- Songs, stations and schedules outlive the user who made them
+ALTER TABLE songs ALTER COLUMN added_by DROP NOT NULL;
+ALTER TABLE songs DROP CONSTRAINT fk_song_user_id;
+ALTER TABLE songs ADD CONSTRAINT fk_song_user_id
+  FOREIGN KEY (added_by) REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE stations ALTER COLUMN created_by DROP NOT NULL;
+ALTER TABLE stations DROP CONSTRAINT fk_station_user_id;
+ALTER TABLE stations ADD CONSTRAINT fk_station_user_id
+  FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE schedules ALTER COLUMN created_by DROP NOT NULL;
+ALTER TABLE schedules DROP CONSTRAINT fk_schedule_user_id;
+ALTER TABLE schedules ADD CONSTRAINT fk_schedule_user_id
+  FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL;
+
+-- Playlists go with their owner
+ALTER TABLE playlists DROP CONSTRAINT fk_playlist_user_id;
+ALTER TABLE playlists ADD CONSTRAINT fk_playlist_user_id
+  FOREIGN KEY (owner) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE playlists_songs DROP CONSTRAINT fk_playlist_song_playlist_id;
+ALTER TABLE playlists_songs ADD CONSTRAINT fk_playlist_song_playlist_id
+  FOREIGN KEY (playlist) REFERENCES playlists(id) ON DELETE CASCADE;
\ No newline at end of file
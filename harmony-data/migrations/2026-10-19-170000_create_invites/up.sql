@@ -0,0 +1,16 @@
+-- Your SQL goes here
+
+CREATE TABLE invites (
+  code VARCHAR(16) PRIMARY KEY,
+  created_by INTEGER,
+  max_uses INTEGER,
+  uses INTEGER NOT NULL DEFAULT 0,
+  expires TIMESTAMP WITH TIME ZONE,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_invite_user_id FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL,
+  -- Unlimited invites have no maximum
+  CONSTRAINT invite_max_uses CHECK (max_uses IS NULL OR max_uses > 0)
+);
+
+-- Accounts waiting for an admin when registration needs approval
+ALTER TABLE users ADD COLUMN pending BOOLEAN NOT NULL DEFAULT FALSE;
\ No newline at end of file
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+
+-- Audit log of logins that were locked after too many failures,
+-- by the name that was tried or by the address the attempts came from
+CREATE TABLE login_lockouts (
+  id SERIAL PRIMARY KEY,
+  scope VARCHAR(16) NOT NULL,
+  user_id INTEGER,
+  name VARCHAR(16),
+  address VARCHAR(45),
+  failures INTEGER NOT NULL,
+  locked_until TIMESTAMP WITH TIME ZONE NOT NULL,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_login_lockout_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT login_lockout_scope CHECK (scope IN ('account', 'address'))
+);
\ No newline at end of file
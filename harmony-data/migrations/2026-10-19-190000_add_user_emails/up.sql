@@ -0,0 +1,21 @@
+-- Your SQL goes here
+
+ALTER TABLE users ADD COLUMN email VARCHAR(255);
+ALTER TABLE users ADD COLUMN email_verified TIMESTAMP WITH TIME ZONE;
+
+-- An address can only be verified for one account, however it is written.
+-- Unverified ones may repeat, so nobody can claim an address that is not theirs
+CREATE UNIQUE INDEX users_verified_email ON users (LOWER(email)) WHERE email_verified IS NOT NULL;
+
+-- Single use tokens mailed to a user, only their hash is kept
+CREATE TABLE email_tokens (
+  hash VARCHAR(64) PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  purpose VARCHAR(16) NOT NULL,
+  -- The address the token was sent to, it stops working when the user changes it
+  email VARCHAR(255) NOT NULL,
+  expires TIMESTAMP WITH TIME ZONE NOT NULL,
+  created TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT fk_email_token_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT email_token_purpose CHECK (purpose IN ('verify', 'reset'))
+);
\ No newline at end of file
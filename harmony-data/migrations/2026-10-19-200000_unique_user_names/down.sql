@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX users_name_lower;
+ALTER TABLE users ADD CONSTRAINT users_name_key UNIQUE (name);
\ No newline at end of file
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+
+-- Names that only differ in case can't both stay, list them so an admin can rename one
+-- (UPDATE users SET name = '...' WHERE id = ...) and run the migration again
+DO $$
+DECLARE
+  conflicts TEXT;
+BEGIN
+  SELECT string_agg(names, '; ')
+  INTO conflicts
+  FROM (
+    SELECT string_agg(format('%s (id %s)', name, id), ', ' ORDER BY id) AS names
+    FROM users
+    GROUP BY LOWER(name)
+    HAVING COUNT(*) > 1
+  ) AS groups;
+
+  IF conflicts IS NOT NULL THEN
+    RAISE EXCEPTION 'Usernames that only differ in case: %', conflicts
+      USING HINT = 'Rename all but one account in each group, then run the migration again';
+  END IF;
+END
+$$;
+
+ALTER TABLE users DROP CONSTRAINT users_name_key;
+CREATE UNIQUE INDEX users_name_lower ON users (LOWER(name));
\ No newline at end of file
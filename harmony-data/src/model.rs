@@ -1,4 +1,12 @@
+pub mod auth_session;
+pub mod device_token;
+pub mod email_token;
+pub mod invite;
+pub mod login_lockout;
 pub mod playlist;
 pub mod playlist_song;
+pub mod schedule;
+pub mod share_code;
 pub mod song;
+pub mod station;
 pub mod user;
@@ -0,0 +1,176 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::auth_sessions;
+use crate::schema::auth_sessions::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::auth_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuthSessionModel {
+    pub id: String,
+    pub user_id: i32,
+    pub refresh_hash: String,
+    pub previous_hash: Option<String>,
+    pub expires: DateTime<Utc>,
+    pub refreshed: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = auth_sessions)]
+pub struct NewAuthSessionModel {
+    pub id: String,
+    pub user_id: i32,
+    pub refresh_hash: String,
+    pub expires: DateTime<Utc>,
+}
+
+impl AuthSessionModel {
+    /// Attempts to create a login session
+    /// # Errors
+    /// When the database operation fails
+    pub fn create(session: &NewAuthSessionModel) -> Result<AuthSessionModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::insert_into(auth_sessions::table)
+            .values(session)
+            .get_result::<AuthSessionModel>(connection);
+
+        match result {
+            Ok(session) => Ok(session),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Replaces the refresh token of a session, only when it still has the expected one,
+    /// so two requests can't both rotate the same token
+    /// # Errors
+    /// When the session no longer has the expected token, or the database operation fails
+    pub fn rotate(
+        session_id: &str,
+        current_hash: &str,
+        new_hash: &str,
+        new_expires: DateTime<Utc>,
+    ) -> Result<AuthSessionModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(
+            auth_sessions.filter(id.eq(session_id).and(refresh_hash.eq(current_hash))),
+        )
+        .set((
+            refresh_hash.eq(new_hash),
+            previous_hash.eq(Some(current_hash)),
+            expires.eq(new_expires),
+            refreshed.eq(Some(Utc::now())),
+        ))
+        .get_result::<AuthSessionModel>(connection);
+
+        match result {
+            Ok(session) => Ok(session),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Removes a session
+    /// # Errors
+    /// When the session does not exist, or the database operation fails
+    pub fn remove(session_id: &str) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(auth_sessions.filter(id.eq(session_id))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes every session of a user, returning how many there were
+    /// # Errors
+    /// When the database operation fails
+    pub fn remove_by_user(owner: i32) -> Result<usize, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(auth_sessions.filter(user_id.eq(owner))).execute(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes every session of a user but one, returning how many were removed
+    /// # Errors
+    /// When the database operation fails
+    pub fn remove_by_user_except(owner: i32, keep: &str) -> Result<usize, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(auth_sessions.filter(user_id.eq(owner).and(id.ne(keep))))
+            .execute(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes the sessions of a user that expired
+    /// # Errors
+    /// When the database operation fails
+    pub fn remove_expired(owner: i32) -> Result<usize, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result =
+            diesel::delete(auth_sessions.filter(user_id.eq(owner).and(expires.lt(Utc::now()))))
+                .execute(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to get a session by id
+    /// # Errors
+    /// When the session does not exist, or the database operation fails
+    pub fn get_by_id(session_id: &str) -> Result<AuthSessionModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = auth_sessions
+            .find(session_id)
+            .first::<AuthSessionModel>(connection);
+
+        match result {
+            Ok(session) => Ok(session),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Attempts to get the session a refresh token belongs to,
+    /// also matching the token it replaced
+    /// # Errors
+    /// When no session has the token, or the database operation fails
+    pub fn get_by_refresh_hash(token_hash: &str) -> Result<AuthSessionModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = auth_sessions
+            .filter(refresh_hash.eq(token_hash).or(previous_hash.eq(token_hash)))
+            .first::<AuthSessionModel>(connection);
+
+        match result {
+            Ok(session) => Ok(session),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+}
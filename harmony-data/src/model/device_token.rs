@@ -0,0 +1,118 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::device_tokens;
+use crate::schema::device_tokens::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::device_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeviceTokenModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub hash: String,
+    pub scopes: String,
+    pub last_used: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = device_tokens)]
+pub struct NewDeviceTokenModel {
+    pub user_id: i32,
+    pub name: String,
+    pub hash: String,
+    pub scopes: String,
+}
+
+impl DeviceTokenModel {
+    /// Attempts to create a device token
+    /// # Errors
+    /// When the database operation fails
+    pub fn create(token: &NewDeviceTokenModel) -> Result<DeviceTokenModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::insert_into(device_tokens::table)
+            .values(token)
+            .get_result::<DeviceTokenModel>(connection);
+
+        match result {
+            Ok(token) => Ok(token),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes a device token of a user
+    /// # Errors
+    /// When the user has no such token, or the database operation fails
+    pub fn remove(owner: i32, token_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(device_tokens.filter(id.eq(token_id).and(user_id.eq(owner))))
+            .execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to get a device token by the hash of its secret
+    /// # Errors
+    /// When no token has the hash, or the database operation fails
+    pub fn get_by_hash(token_hash: &str) -> Result<DeviceTokenModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = device_tokens
+            .filter(hash.eq(token_hash))
+            .first::<DeviceTokenModel>(connection);
+
+        match result {
+            Ok(token) => Ok(token),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Marks a device token as used now, unless it was already used since the given time
+    /// # Errors
+    /// When the database operation fails
+    pub fn touch(token_id: i32, since: DateTime<Utc>) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(
+            device_tokens.filter(
+                id.eq(token_id)
+                    .and(last_used.is_null().or(last_used.lt(since))),
+            ),
+        )
+        .set(last_used.eq(Utc::now()))
+        .execute(connection);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets the device tokens of a user
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_by_user(owner: i32) -> Result<Vec<DeviceTokenModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = device_tokens
+            .filter(user_id.eq(owner))
+            .order(created.asc())
+            .load::<DeviceTokenModel>(connection);
+
+        match result {
+            Ok(tokens) => Ok(tokens),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::email_tokens;
+use crate::schema::email_tokens::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::email_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct EmailTokenModel {
+    pub hash: String,
+    pub user_id: i32,
+    pub purpose: String,
+    pub email: String,
+    pub expires: DateTime<Utc>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = email_tokens)]
+pub struct NewEmailTokenModel {
+    pub hash: String,
+    pub user_id: i32,
+    pub purpose: String,
+    pub email: String,
+    pub expires: DateTime<Utc>,
+}
+
+impl EmailTokenModel {
+    /// Attempts to store a token, replacing the ones the user had for the same purpose
+    /// # Errors
+    /// When the database operation fails
+    pub fn create(token: &NewEmailTokenModel) -> Result<EmailTokenModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = connection.transaction(|connection| {
+            diesel::delete(
+                email_tokens.filter(user_id.eq(token.user_id).and(purpose.eq(&token.purpose))),
+            )
+            .execute(connection)?;
+
+            diesel::insert_into(email_tokens::table)
+                .values(token)
+                .get_result::<EmailTokenModel>(connection)
+        });
+
+        match result {
+            Ok(token) => Ok(token),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes a token that has not expired and returns it, so it can only be used once
+    /// # Errors
+    /// When there is no such token, or the database operation fails
+    pub fn consume(
+        token_hash: &str,
+        token_purpose: &str,
+    ) -> Result<EmailTokenModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(
+            email_tokens.filter(
+                hash.eq(token_hash)
+                    .and(purpose.eq(token_purpose))
+                    .and(expires.gt(Utc::now())),
+            ),
+        )
+        .get_result::<EmailTokenModel>(connection);
+
+        match result {
+            Ok(token) => Ok(token),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Removes the tokens a user has, returning how many there were
+    /// # Errors
+    /// When the database operation fails
+    pub fn remove_by_user(token_user_id: i32) -> Result<usize, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result =
+            diesel::delete(email_tokens.filter(user_id.eq(token_user_id))).execute(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+}
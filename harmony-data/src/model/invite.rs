@@ -0,0 +1,111 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::invites;
+use crate::schema::invites::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::invites)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InviteModel {
+    pub code: String,
+    pub created_by: Option<i32>,
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    pub expires: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = invites)]
+pub struct NewInviteModel {
+    pub code: String,
+    pub created_by: i32,
+    pub max_uses: Option<i32>,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl InviteModel {
+    /// Attempts to create an invite
+    /// # Errors
+    /// When the database operation fails
+    pub fn create(invite: &NewInviteModel) -> Result<InviteModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::insert_into(invites::table)
+            .values(invite)
+            .get_result::<InviteModel>(connection);
+
+        match result {
+            Ok(invite) => Ok(invite),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Counts one use of an invite, only when it has uses left and has not expired.
+    /// Runs on the connection of the caller, so the use is undone when its transaction fails
+    /// # Errors
+    /// When the invite does not exist or can't be used anymore, or the database operation fails
+    pub fn use_code(
+        connection: &mut PgConnection,
+        invite_code: &str,
+    ) -> Result<InviteModel, diesel::result::Error> {
+        diesel::update(
+            invites.filter(
+                code.eq(invite_code)
+                    .and(max_uses.is_null().or(uses.lt(max_uses.assume_not_null())))
+                    .and(expires.is_null().or(expires.gt(Utc::now()))),
+            ),
+        )
+        .set(uses.eq(uses + 1))
+        .get_result::<InviteModel>(connection)
+    }
+
+    /// Returns no error if the code is taken
+    /// # Errors
+    /// When the code is free, or the database operation fails
+    pub fn exists(invite_code: &str) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = invites.find(invite_code).first::<InviteModel>(connection);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Removes an invite, accounts created with it are kept
+    /// # Errors
+    /// When the invite does not exist, or the database operation fails
+    pub fn remove(invite_code: &str) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(invites.find(invite_code)).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets every invite, newest first
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_list() -> Result<Vec<InviteModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = invites
+            .order(created.desc())
+            .load::<InviteModel>(connection);
+
+        match result {
+            Ok(invite_list) => Ok(invite_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::login_lockouts;
+use crate::schema::login_lockouts::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::login_lockouts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginLockoutModel {
+    pub id: i32,
+    pub scope: String,
+    pub user_id: Option<i32>,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub failures: i32,
+    pub locked_until: DateTime<Utc>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = login_lockouts)]
+pub struct NewLoginLockoutModel {
+    pub scope: String,
+    pub user_id: Option<i32>,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub failures: i32,
+    pub locked_until: DateTime<Utc>,
+}
+
+impl LoginLockoutModel {
+    /// Attempts to record a lockout
+    /// # Errors
+    /// When the database operation fails
+    pub fn create(
+        lockout: &NewLoginLockoutModel,
+    ) -> Result<LoginLockoutModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::insert_into(login_lockouts::table)
+            .values(lockout)
+            .get_result::<LoginLockoutModel>(connection);
+
+        match result {
+            Ok(lockout) => Ok(lockout),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets the most recent lockouts, newest first
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_recent(count: i64) -> Result<Vec<LoginLockoutModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = login_lockouts
+            .order(id.desc())
+            .limit(count)
+            .load::<LoginLockoutModel>(connection);
+
+        match result {
+            Ok(lockout_list) => Ok(lockout_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+}
@@ -78,6 +78,30 @@ impl PlaylistModel {
         }
     }
 
+    /// Attempts to get a playlist by id that is public or owned by the calling user,
+    /// without a user only public ones
+    /// # Errors
+    /// When the playlist does not exist or is not visible, or the database operation fails
+    pub fn get_visible(
+        playlist_id: i32,
+        calling_user: Option<i32>,
+    ) -> Result<PlaylistModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = playlists
+            .find(playlist_id)
+            .filter(public.eq(true).or(owner.nullable().eq(calling_user)))
+            .first::<PlaylistModel>(connection);
+
+        match result {
+            Ok(playlist) => Ok(playlist),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
     /// Find a playlist by name
     /// # Errors
     /// When the database operation fails
@@ -144,4 +168,21 @@ impl PlaylistModel {
             Err(err) => Err(crate::result::Error::Database(err.to_string())),
         }
     }
+
+    /// Counts the playlists a user owns
+    /// # Errors
+    /// When the database operation fails
+    pub fn count_by_user(user_id: i32) -> Result<i64, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = playlists
+            .filter(owner.eq(user_id))
+            .count()
+            .get_result::<i64>(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
 }
@@ -87,6 +87,20 @@ impl PlaylistSongModel {
         }
     }
 
+    /// Removes a song from every playlist it is in
+    /// # Errors
+    /// When the database operation fails
+    pub fn remove_song_from_all_playlists(song_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(playlists_songs.filter(song.eq(song_id))).execute(connection);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
     pub fn song_in_playlist(playlist_id: i32, song_id: i32) -> Result<(), crate::result::Error> {
         let connection = &mut crate::establish_connection();
 
@@ -0,0 +1,150 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::schedules;
+use crate::schema::schedules::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::schedules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ScheduleModel {
+    pub id: i32,
+    pub name: String,
+    pub playlist: i32,
+    pub station: Option<i32>,
+    pub jukebox: Option<String>,
+    pub rule: String,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub priority: i32,
+    pub created_by: Option<i32>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = schedules)]
+pub struct NewScheduleModel {
+    pub name: String,
+    pub playlist: i32,
+    pub station: Option<i32>,
+    pub jukebox: Option<String>,
+    pub rule: String,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub priority: i32,
+    pub created_by: i32,
+}
+
+impl ScheduleModel {
+    /// Attempts to create a schedule
+    /// # Errors
+    /// When the database operation fails
+    pub fn create(schedule: &NewScheduleModel) -> Result<ScheduleModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::insert_into(schedules::table)
+            .values(schedule)
+            .get_result::<ScheduleModel>(connection);
+
+        match result {
+            Ok(schedule) => Ok(schedule),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes a schedule
+    /// # Errors
+    /// When the schedule does not exist, or the database operation fails
+    pub fn remove(schedule_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(schedules.filter(id.eq(schedule_id))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes the schedules of a jukebox, returning how many there were
+    /// # Errors
+    /// When the database operation fails
+    pub fn remove_by_jukebox(jukebox_code: &str) -> Result<usize, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(schedules.filter(jukebox.eq(jukebox_code))).execute(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to get a schedule by id
+    /// # Errors
+    /// When the schedule does not exist, or the database operation fails
+    pub fn get_by_id(schedule_id: i32) -> Result<ScheduleModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = schedules
+            .find(schedule_id)
+            .first::<ScheduleModel>(connection);
+
+        match result {
+            Ok(schedule) => Ok(schedule),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Gets the schedules of a station
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_by_station(station_id: i32) -> Result<Vec<ScheduleModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = schedules
+            .filter(station.eq(station_id))
+            .load::<ScheduleModel>(connection);
+
+        match result {
+            Ok(schedule_list) => Ok(schedule_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets the schedules of a jukebox
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_by_jukebox(jukebox_code: &str) -> Result<Vec<ScheduleModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = schedules
+            .filter(jukebox.eq(jukebox_code))
+            .load::<ScheduleModel>(connection);
+
+        match result {
+            Ok(schedule_list) => Ok(schedule_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets a list of schedules
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_list() -> Result<Vec<ScheduleModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = schedules
+            .order((priority.desc(), name.asc()))
+            .load::<ScheduleModel>(connection);
+
+        match result {
+            Ok(schedule_list) => Ok(schedule_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::share_codes;
+use crate::schema::share_codes::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::share_codes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ShareCodeModel {
+    pub code: String,
+    pub song: Option<i32>,
+    pub playlist: Option<i32>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = share_codes)]
+pub struct NewShareCodeModel {
+    pub code: String,
+    pub song: Option<i32>,
+    pub playlist: Option<i32>,
+}
+
+impl ShareCodeModel {
+    /// Creates a share code, removing the code its song or playlist had in the same transaction
+    /// # Errors
+    /// When the code is taken, or the database operation fails
+    pub fn replace(share_code: &NewShareCodeModel) -> Result<ShareCodeModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = connection.transaction(|connection| {
+            match (share_code.song, share_code.playlist) {
+                (Some(song_id), _) => {
+                    diesel::delete(share_codes.filter(song.eq(song_id))).execute(connection)?;
+                }
+                (None, Some(playlist_id)) => {
+                    diesel::delete(share_codes.filter(playlist.eq(playlist_id)))
+                        .execute(connection)?;
+                }
+                (None, None) => {}
+            }
+
+            diesel::insert_into(share_codes::table)
+                .values(share_code)
+                .get_result::<ShareCodeModel>(connection)
+        });
+
+        result.map_err(crate::result::Error::from)
+    }
+
+    /// Removes the code of a song
+    /// # Errors
+    /// When the song has no code, or the database operation fails
+    pub fn remove_by_song(song_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(share_codes.filter(song.eq(song_id))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes the code of a playlist
+    /// # Errors
+    /// When the playlist has no code, or the database operation fails
+    pub fn remove_by_playlist(playlist_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result =
+            diesel::delete(share_codes.filter(playlist.eq(playlist_id))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to get a share code
+    /// # Errors
+    /// When the code does not exist, or the database operation fails
+    pub fn get_by_code(share_code: &str) -> Result<ShareCodeModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = share_codes
+            .find(share_code)
+            .first::<ShareCodeModel>(connection);
+
+        match result {
+            Ok(share_code) => Ok(share_code),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Attempts to get the code of a song
+    /// # Errors
+    /// When the song has no code, or the database operation fails
+    pub fn get_by_song(song_id: i32) -> Result<ShareCodeModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = share_codes
+            .filter(song.eq(song_id))
+            .first::<ShareCodeModel>(connection);
+
+        match result {
+            Ok(share_code) => Ok(share_code),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Attempts to get the code of a playlist
+    /// # Errors
+    /// When the playlist has no code, or the database operation fails
+    pub fn get_by_playlist(playlist_id: i32) -> Result<ShareCodeModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = share_codes
+            .filter(playlist.eq(playlist_id))
+            .first::<ShareCodeModel>(connection);
+
+        match result {
+            Ok(share_code) => Ok(share_code),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::model::playlist_song::PlaylistSongModel;
 #[allow(clippy::module_name_repetitions)]
 use crate::schema::songs;
 use crate::schema::songs::dsl::*;
+use crate::schema::users;
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use std::cmp::{Eq, Ord, PartialEq, PartialOrd};
@@ -12,10 +14,13 @@ pub struct SongModel {
     pub id: i32,
     pub name: String,
     pub author: Option<String>,
-    pub added_by: i32,
+    pub added_by: Option<i32>,
     pub youtube_url: String,
     pub file_id: String,
     pub created: Option<DateTime<Utc>>,
+    pub codec: String,
+    pub sample_rate: i32,
+    pub channels: i32,
 }
 
 #[derive(Insertable)]
@@ -26,12 +31,15 @@ pub struct NewSongModel {
     pub added_by: i32,
     pub youtube_url: String,
     pub file_id: String,
+    pub codec: String,
+    pub sample_rate: i32,
+    pub channels: i32,
 }
 
 impl SongModel {
     /// Attempts to create a song
     /// # Errors
-    /// When the database operation fails
+    /// When a song has the url, or the database operation fails
     pub fn create(song: &NewSongModel) -> Result<SongModel, crate::result::Error> {
         let connection: &mut PgConnection = &mut crate::establish_connection();
 
@@ -39,10 +47,43 @@ impl SongModel {
             .values(song)
             .get_result::<SongModel>(connection);
 
-        match result {
-            Ok(song) => Ok(song),
-            Err(err) => Err(crate::result::Error::Database(err.to_string())),
-        }
+        result.map_err(crate::result::Error::from)
+    }
+
+    /// Attempts to create a song for a user who may only add `limit` songs from `since` on,
+    /// returns none when they already did. Their row stays locked while counting, so
+    /// requests of the same user take turns and can't all take the last song of the day
+    /// # Errors
+    /// When the user does not exist, a song has the url or file, or the database operation fails
+    pub fn create_limited(
+        song: &NewSongModel,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Option<SongModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = connection.transaction(|connection| {
+            users::table
+                .find(song.added_by)
+                .select(users::id)
+                .for_update()
+                .first::<i32>(connection)?;
+
+            let added: i64 = songs
+                .filter(added_by.eq(song.added_by).and(created.ge(since)))
+                .count()
+                .get_result(connection)?;
+            if added >= limit {
+                return Ok(None);
+            }
+
+            diesel::insert_into(songs::table)
+                .values(song)
+                .get_result::<SongModel>(connection)
+                .map(Some)
+        });
+
+        result.map_err(crate::result::Error::from)
     }
 
     /// Returns no error if the song does not exist
@@ -81,6 +122,25 @@ impl SongModel {
         }
     }
 
+    /// Attempts to get a song by the id of its audio file
+    /// # Errors
+    /// When the song does not exist, or the database operation fails
+    pub fn get_by_file_id(song_file_id: &str) -> Result<SongModel, crate::result::Error> {
+        let connection: &mut PgConnection = &mut crate::establish_connection();
+
+        let result = songs
+            .filter(file_id.eq(song_file_id))
+            .first::<SongModel>(connection);
+
+        match result {
+            Ok(song) => Ok(song),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
     /// Find a song by user or author
     /// # Errors
     /// When the database operation fails
@@ -119,4 +179,74 @@ impl SongModel {
             Err(err) => Err(crate::result::Error::Database(err.to_string())),
         }
     }
+
+    /// Attempts to remove a song, taking it out of every playlist first
+    /// # Errors
+    /// When the song does not exist, or the database operation fails
+    pub fn remove(song_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        PlaylistSongModel::remove_song_from_all_playlists(song_id)?;
+        let result = diesel::delete(songs.filter(id.eq(song_id))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Counts the songs a user added
+    /// # Errors
+    /// When the database operation fails
+    pub fn count_by_user(user_id: i32) -> Result<i64, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = songs
+            .filter(added_by.eq(user_id))
+            .count()
+            .get_result::<i64>(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets the songs a user added since the given moment, oldest first
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_added_since(
+        user_id: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<SongModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = songs
+            .filter(added_by.eq(user_id).and(created.ge(since)))
+            .order(created.asc())
+            .load::<SongModel>(connection);
+
+        match result {
+            Ok(song_list) => Ok(song_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets the file ids of the songs a user added
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_file_ids_by_user(user_id: i32) -> Result<Vec<String>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = songs
+            .filter(added_by.eq(user_id))
+            .select(file_id)
+            .load::<String>(connection);
+
+        match result {
+            Ok(file_ids) => Ok(file_ids),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
 }
@@ -0,0 +1,107 @@
+#[allow(clippy::module_name_repetitions)]
+use crate::schema::stations;
+use crate::schema::stations::dsl::*;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::stations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct StationModel {
+    pub id: i32,
+    pub name: String,
+    pub playlist: Option<i32>,
+    pub shuffle: bool,
+    pub created_by: Option<i32>,
+    pub started: DateTime<Utc>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = stations)]
+pub struct NewStationModel {
+    pub name: String,
+    pub playlist: Option<i32>,
+    pub shuffle: bool,
+    pub created_by: i32,
+}
+
+impl StationModel {
+    /// Attempts to create a station
+    /// # Errors
+    /// When a station has the name, or the database operation fails
+    pub fn create(station: &NewStationModel) -> Result<StationModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::insert_into(stations::table)
+            .values(station)
+            .get_result::<StationModel>(connection);
+
+        result.map_err(crate::result::Error::from)
+    }
+
+    /// Removes a station
+    /// # Errors
+    /// When the station does not exist, or the database operation fails
+    pub fn remove(station_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(stations.filter(id.eq(station_id))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to get a station by id
+    /// # Errors
+    /// When the station does not exist, or the database operation fails
+    pub fn get_by_id(station_id: i32) -> Result<StationModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = stations.find(station_id).first::<StationModel>(connection);
+
+        match result {
+            Ok(station) => Ok(station),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Checks if a station with the name exists
+    /// # Errors
+    /// When no station has the name, or the database operation fails
+    pub fn exists(station_name: &str) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = stations
+            .filter(name.eq(station_name))
+            .first::<StationModel>(connection);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Gets a list of stations
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_list() -> Result<Vec<StationModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = stations.order(name.asc()).load::<StationModel>(connection);
+
+        match result {
+            Ok(station_list) => Ok(station_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+}
@@ -1,10 +1,16 @@
+use crate::model::invite::InviteModel;
+use crate::schema::playlists;
 #[allow(clippy::module_name_repetitions)]
 use crate::schema::users;
 use crate::schema::users::dsl::*;
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
 use std::cmp::{Eq, Ord, PartialEq, PartialOrd};
 
+// Names and addresses are compared the way the unique indexes on them do
+define_sql_function!(fn lower(text: Nullable<Text>) -> Nullable<Text>);
+
 // Queryable will generate the code needed to load the struct from an SQL statement
 #[derive(Queryable, Selectable, Ord, Eq, PartialEq, PartialOrd)]
 #[diesel(table_name = crate::schema::users)]
@@ -13,8 +19,13 @@ pub struct UserModel {
     pub id: i32,
     pub name: String,
     pub hash: String,
-    pub admin: bool,
     pub created: Option<DateTime<Utc>>,
+    pub role: String,
+    pub suspended: Option<DateTime<Utc>>,
+    pub suspension_reason: Option<String>,
+    pub pending: bool,
+    pub email: Option<String>,
+    pub email_verified: Option<DateTime<Utc>>,
 }
 
 #[derive(Insertable)]
@@ -22,12 +33,13 @@ pub struct UserModel {
 pub struct NewUserModel {
     pub name: String,
     pub hash: String,
+    pub pending: bool,
 }
 
 impl UserModel {
     /// Attempts to create a user
     /// # Errors
-    /// When the database operation fails
+    /// When a user has the name, or the database operation fails
     pub fn create(user: NewUserModel) -> Result<UserModel, crate::result::Error> {
         let connection: &mut PgConnection = &mut crate::establish_connection();
 
@@ -35,20 +47,38 @@ impl UserModel {
             .values(&user)
             .get_result::<UserModel>(connection);
 
-        match result {
-            Ok(user) => Ok(user),
-            Err(err) => Err(crate::result::Error::Database(err.to_string())),
-        }
+        result.map_err(crate::result::Error::from)
+    }
+
+    /// Attempts to create a user with an invite, counting its use in the same transaction
+    /// # Errors
+    /// When the invite does not exist or can't be used anymore, a user has the name,
+    /// or the database operation fails
+    pub fn create_with_invite(
+        user: NewUserModel,
+        invite_code: &str,
+    ) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = connection.transaction(|connection| {
+            InviteModel::use_code(connection, invite_code)?;
+
+            diesel::insert_into(users::table)
+                .values(&user)
+                .get_result::<UserModel>(connection)
+        });
+
+        result.map_err(crate::result::Error::from)
     }
 
-    /// Returns no error if the user exists
+    /// Returns no error if a user has this name, however it is capitalised
     /// # Errors
     /// When the user does not exist or the database operation fails
-    pub fn exists(username: &String) -> Result<(), crate::result::Error> {
+    pub fn exists(username: &str) -> Result<(), crate::result::Error> {
         let connection: &mut PgConnection = &mut crate::establish_connection();
 
         let result = users
-            .filter(name.ilike(format!("%{username}%")))
+            .filter(lower(name.nullable()).eq(username.to_lowercase()))
             .first::<UserModel>(connection);
 
         match result {
@@ -80,17 +110,17 @@ impl UserModel {
     /// Find a user by name
     /// # Errors
     /// When the user does not exist or the database operation fails
-    pub fn get_by_name(username: &String) -> Result<UserModel, crate::result::Error> {
+    pub fn get_by_name(username: &str) -> Result<UserModel, crate::result::Error> {
         let connection = &mut crate::establish_connection();
 
-        // Perform case-insensitive search
+        // Names are unique however they are written, so this finds at most one
         let result = users
-            .order(name.desc())
-            .filter(name.eq(username))
+            .filter(lower(name.nullable()).eq(username.to_lowercase()))
             .first::<UserModel>(connection);
 
         match result {
             Ok(user) => Ok(user),
+            Err(diesel::result::Error::NotFound) => Err(crate::result::Error::NotFound),
             Err(err) => Err(crate::result::Error::Database(err.to_string())),
         }
     }
@@ -130,4 +160,256 @@ impl UserModel {
             Err(err) => Err(crate::result::Error::Database(err.to_string())),
         }
     }
+
+    /// Attempts to change the role of a user
+    /// # Errors
+    /// When the user does not exist, or the database operation fails
+    pub fn set_role(user_id: i32, new_role: &str) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.find(user_id))
+            .set(role.eq(new_role))
+            .get_result::<UserModel>(connection);
+
+        match result {
+            Ok(user) => Ok(user),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Attempts to suspend a user, or lift the suspension when `since` is `None`
+    /// # Errors
+    /// When the user does not exist, or the database operation fails
+    pub fn set_suspended(
+        user_id: i32,
+        since: Option<DateTime<Utc>>,
+        reason: Option<&str>,
+    ) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.find(user_id))
+            .set((suspended.eq(since), suspension_reason.eq(reason)))
+            .get_result::<UserModel>(connection);
+
+        match result {
+            Ok(user) => Ok(user),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Attempts to replace the password hash of a user
+    /// # Errors
+    /// When the user does not exist, or the database operation fails
+    pub fn set_hash(user_id: i32, new_hash: &str) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.find(user_id))
+            .set(hash.eq(new_hash))
+            .execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to give a user another email address, or none, it has to be verified again
+    /// # Errors
+    /// When the user does not exist, or the database operation fails
+    pub fn set_email(user_id: i32, new_email: Option<&str>) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.find(user_id))
+            .set((
+                email.eq(new_email),
+                email_verified.eq(None::<DateTime<Utc>>),
+            ))
+            .execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Marks the email address of a user as verified, as long as it is still the given one
+    /// # Errors
+    /// When the user does not exist or has another address, another user verified the address,
+    /// or the database operation fails
+    pub fn verify_email(user_id: i32, address: &str) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.filter(id.eq(user_id).and(email.eq(address))))
+            .set(email_verified.eq(Utc::now()))
+            .get_result::<UserModel>(connection);
+
+        result.map_err(crate::result::Error::from)
+    }
+
+    /// Find the user an email address was verified for, however it is written
+    /// # Errors
+    /// When no user verified the address, or the database operation fails
+    pub fn get_by_verified_email(address: &str) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = users
+            .filter(lower(email).eq(address.to_lowercase()))
+            .filter(email_verified.is_not_null())
+            .first::<UserModel>(connection);
+
+        match result {
+            Ok(user) => Ok(user),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Removes a user along with their playlists, sessions and device tokens.
+    /// Songs, stations and schedules they made are kept without a creator
+    /// # Errors
+    /// When the user does not exist, or the database operation fails
+    pub fn remove(user_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::delete(users.find(user_id)).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Returns whether another user has this name, however it is written
+    /// # Errors
+    /// When the database operation fails
+    pub fn name_taken(user_id: i32, username: &str) -> Result<bool, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::select(diesel::dsl::exists(
+            users
+                .filter(lower(name.nullable()).eq(username.to_lowercase()))
+                .filter(id.ne(user_id)),
+        ))
+        .get_result::<bool>(connection);
+
+        match result {
+            Ok(taken) => Ok(taken),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Attempts to rename a user
+    /// # Errors
+    /// When the user does not exist, another user has the name, or the database operation fails
+    pub fn set_name(user_id: i32, new_name: &str) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.find(user_id))
+            .set(name.eq(new_name))
+            .get_result::<UserModel>(connection);
+
+        result.map_err(crate::result::Error::from)
+    }
+
+    /// Counts the users with a role
+    /// # Errors
+    /// When the database operation fails
+    pub fn count_by_role(user_role: &str) -> Result<i64, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = users
+            .filter(role.eq(user_role))
+            .count()
+            .get_result::<i64>(connection);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Removes a user like `remove`, but first hands their playlists to another user
+    /// in the same transaction
+    /// # Errors
+    /// When the user does not exist, or the database operation fails
+    pub fn remove_transferring(user_id: i32, new_owner: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = connection.transaction(|connection| {
+            diesel::update(playlists::table.filter(playlists::owner.eq(user_id)))
+                .set(playlists::owner.eq(new_owner))
+                .execute(connection)?;
+
+            diesel::delete(users.find(user_id)).execute(connection)
+        });
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Gets the users waiting for approval, oldest first
+    /// # Errors
+    /// When the database operation fails
+    pub fn get_pending() -> Result<Vec<UserModel>, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = users
+            .filter(pending.eq(true))
+            .order(created.asc())
+            .load::<UserModel>(connection);
+
+        match result {
+            Ok(user_list) => Ok(user_list),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
+
+    /// Lets a user that was waiting for approval log in
+    /// # Errors
+    /// When no such user is waiting, or the database operation fails
+    pub fn approve(user_id: i32) -> Result<UserModel, crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result = diesel::update(users.filter(id.eq(user_id).and(pending.eq(true))))
+            .set(pending.eq(false))
+            .get_result::<UserModel>(connection);
+
+        match result {
+            Ok(user) => Ok(user),
+            Err(err) => match err {
+                diesel::result::Error::NotFound => Err(crate::result::Error::NotFound),
+                _ => Err(crate::result::Error::Database(err.to_string())),
+            },
+        }
+    }
+
+    /// Removes a user that was waiting for approval
+    /// # Errors
+    /// When no such user is waiting, or the database operation fails
+    pub fn reject(user_id: i32) -> Result<(), crate::result::Error> {
+        let connection = &mut crate::establish_connection();
+
+        let result =
+            diesel::delete(users.filter(id.eq(user_id).and(pending.eq(true)))).execute(connection);
+
+        match result {
+            Ok(0) => Err(crate::result::Error::NotFound),
+            Ok(_) => Ok(()),
+            Err(err) => Err(crate::result::Error::Database(err.to_string())),
+        }
+    }
 }
@@ -1,8 +1,25 @@
+use diesel::result::DatabaseErrorKind;
+
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
 #[non_exhaustive]
 pub enum Error {
     NotFound,
     AlreadyExists,
+    /// A write would have broken a unique constraint, named when the database tells which.
+    /// Checking before writing can't rule this out, another request may write in between
+    UniqueViolation(Option<String>),
     Database(String),
 }
+
+impl From<diesel::result::Error> for Error {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => Error::NotFound,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                Error::UniqueViolation(info.constraint_name().map(str::to_string))
+            }
+            error => Error::Database(error.to_string()),
+        }
+    }
+}
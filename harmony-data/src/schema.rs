@@ -1,5 +1,77 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    auth_sessions (id) {
+        #[max_length = 21]
+        id -> Varchar,
+        user_id -> Int4,
+        #[max_length = 64]
+        refresh_hash -> Varchar,
+        #[max_length = 64]
+        previous_hash -> Nullable<Varchar>,
+        expires -> Timestamptz,
+        refreshed -> Nullable<Timestamptz>,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    device_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 32]
+        name -> Varchar,
+        #[max_length = 64]
+        hash -> Varchar,
+        #[max_length = 64]
+        scopes -> Varchar,
+        last_used -> Nullable<Timestamptz>,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    email_tokens (hash) {
+        #[max_length = 64]
+        hash -> Varchar,
+        user_id -> Int4,
+        #[max_length = 16]
+        purpose -> Varchar,
+        #[max_length = 255]
+        email -> Varchar,
+        expires -> Timestamptz,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    invites (code) {
+        #[max_length = 16]
+        code -> Varchar,
+        created_by -> Nullable<Int4>,
+        max_uses -> Nullable<Int4>,
+        uses -> Int4,
+        expires -> Nullable<Timestamptz>,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    login_lockouts (id) {
+        id -> Int4,
+        #[max_length = 16]
+        scope -> Varchar,
+        user_id -> Nullable<Int4>,
+        #[max_length = 16]
+        name -> Nullable<Varchar>,
+        #[max_length = 45]
+        address -> Nullable<Varchar>,
+        failures -> Int4,
+        locked_until -> Timestamptz,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     playlists (id) {
         id -> Int4,
@@ -20,6 +92,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    schedules (id) {
+        id -> Int4,
+        #[max_length = 32]
+        name -> Varchar,
+        playlist -> Int4,
+        station -> Nullable<Int4>,
+        #[max_length = 16]
+        jukebox -> Nullable<Varchar>,
+        #[max_length = 255]
+        rule -> Varchar,
+        duration_minutes -> Int4,
+        #[max_length = 64]
+        timezone -> Varchar,
+        priority -> Int4,
+        created_by -> Nullable<Int4>,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    share_codes (code) {
+        #[max_length = 8]
+        code -> Varchar,
+        song -> Nullable<Int4>,
+        playlist -> Nullable<Int4>,
+        created -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     songs (id) {
         id -> Int4,
@@ -27,12 +129,29 @@ diesel::table! {
         name -> Varchar,
         #[max_length = 255]
         author -> Nullable<Varchar>,
-        added_by -> Int4,
+        added_by -> Nullable<Int4>,
         #[max_length = 255]
         youtube_url -> Varchar,
         #[max_length = 60]
         file_id -> Varchar,
         created -> Nullable<Timestamptz>,
+        #[max_length = 16]
+        codec -> Varchar,
+        sample_rate -> Int4,
+        channels -> Int4,
+    }
+}
+
+diesel::table! {
+    stations (id) {
+        id -> Int4,
+        #[max_length = 32]
+        name -> Varchar,
+        playlist -> Nullable<Int4>,
+        shuffle -> Bool,
+        created_by -> Nullable<Int4>,
+        started -> Timestamptz,
+        created -> Nullable<Timestamptz>,
     }
 }
 
@@ -43,14 +162,47 @@ diesel::table! {
         name -> Varchar,
         #[max_length = 255]
         hash -> Varchar,
-        admin -> Bool,
         created -> Nullable<Timestamptz>,
+        #[max_length = 16]
+        role -> Varchar,
+        suspended -> Nullable<Timestamptz>,
+        #[max_length = 255]
+        suspension_reason -> Nullable<Varchar>,
+        pending -> Bool,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        email_verified -> Nullable<Timestamptz>,
     }
 }
 
+diesel::joinable!(auth_sessions -> users (user_id));
+diesel::joinable!(device_tokens -> users (user_id));
+diesel::joinable!(email_tokens -> users (user_id));
+diesel::joinable!(invites -> users (created_by));
+diesel::joinable!(login_lockouts -> users (user_id));
 diesel::joinable!(playlists -> users (owner));
 diesel::joinable!(playlists_songs -> playlists (playlist));
 diesel::joinable!(playlists_songs -> songs (song));
+diesel::joinable!(schedules -> playlists (playlist));
+diesel::joinable!(schedules -> stations (station));
+diesel::joinable!(schedules -> users (created_by));
+diesel::joinable!(share_codes -> playlists (playlist));
+diesel::joinable!(share_codes -> songs (song));
 diesel::joinable!(songs -> users (added_by));
+diesel::joinable!(stations -> playlists (playlist));
+diesel::joinable!(stations -> users (created_by));
 
-diesel::allow_tables_to_appear_in_same_query!(playlists, playlists_songs, songs, users,);
+diesel::allow_tables_to_appear_in_same_query!(
+    auth_sessions,
+    device_tokens,
+    email_tokens,
+    invites,
+    login_lockouts,
+    playlists,
+    playlists_songs,
+    schedules,
+    share_codes,
+    songs,
+    stations,
+    users,
+);